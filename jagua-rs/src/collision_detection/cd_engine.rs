@@ -1,13 +1,20 @@
+use std::sync::Arc;
+
 use indexmap::IndexSet;
+use rayon::prelude::*;
 use tribool::Tribool;
 
+#[cfg(feature = "cde-stats")]
+use crate::collision_detection::cde_stats::{CDECounters, CDEStats};
 use crate::collision_detection::hazard::Hazard;
 use crate::collision_detection::hazard::HazardEntity;
 use crate::collision_detection::hpg::grid::Grid;
 use crate::collision_detection::hpg::hazard_proximity_grid::{DirtyState, HazardProximityGrid};
 use crate::collision_detection::hpg::hpg_cell::HPGCell;
+use crate::collision_detection::quadtree::qt_hazard::QTHazPresence;
 use crate::collision_detection::quadtree::qt_node::QTNode;
 use crate::collision_detection::quadtree::qt_traits::QTQueryable;
+use crate::collision_detection::sweep;
 use crate::fsize;
 use crate::geometry::fail_fast::sp_surrogate::SPSurrogate;
 use crate::geometry::geo_enums::{GeoPosition, GeoRelation};
@@ -33,12 +40,15 @@ pub struct CDEngine {
     config: CDEConfig,
     bbox: AARectangle,
     uncommitted_deregisters: Vec<Hazard>,
+    #[cfg(feature = "cde-stats")]
+    counters: CDECounters,
 }
 
 /// Snapshot of the state of [CDEngine] at a given time.
 /// The [CDEngine] can take snapshots of itself at any time, and use them to restore to that state later.
 #[derive(Clone, Debug)]
 pub struct CDESnapshot {
+    static_hazards: Vec<Hazard>,
     dynamic_hazards: Vec<Hazard>,
     grid: Option<Grid<HPGCell>>,
 }
@@ -68,43 +78,62 @@ impl CDEngine {
             config,
             bbox,
             uncommitted_deregisters: vec![],
+            #[cfg(feature = "cde-stats")]
+            counters: CDECounters::default(),
         }
     }
 
     /// Registers a new hazard in the CDE.
     pub fn register_hazard(&mut self, hazard: Hazard) {
+        self.register_hazard_components(vec![hazard]);
+    }
+
+    /// Registers a new entity consisting of multiple disjoint components in the CDE, one hazard per component.
+    /// All hazards should share the same entity, which is treated as a whole in the rest of the CDE.
+    pub fn register_hazard_components(&mut self, components: Vec<Hazard>) {
+        let entity = components
+            .first()
+            .expect("no components to register")
+            .entity;
+        assert!(
+            components.iter().all(|h| h.entity == entity),
+            "components of different entities"
+        );
         debug_assert!(
-            !self
-                .dynamic_hazards
-                .iter()
-                .any(|h| h.entity == hazard.entity),
+            !self.dynamic_hazards.iter().any(|h| h.entity == entity),
             "Hazard already registered"
         );
-        let hazard_in_uncommitted_deregs = self
+        let uncommitted_components = self
             .uncommitted_deregisters
             .iter()
-            .position(|h| h.entity == hazard.entity);
+            .filter(|h| h.entity == entity)
+            .cloned()
+            .collect::<Vec<_>>();
 
-        let hazard = match hazard_in_uncommitted_deregs {
-            Some(index) => {
-                let unc_hazard = self.uncommitted_deregisters.swap_remove(index);
-                self.quadtree.activate_hazard(unc_hazard.entity);
-                unc_hazard
+        let components = match uncommitted_components.is_empty() {
+            false => {
+                self.uncommitted_deregisters.retain(|h| h.entity != entity);
+                self.quadtree.activate_hazard(entity);
+                uncommitted_components
             }
-            None => {
-                self.quadtree.register_hazard((&hazard).into());
-                hazard
+            true => {
+                for hazard in components.iter() {
+                    self.quadtree.register_hazard(hazard.into());
+                }
+                components
             }
         };
         if let Some(hpg) = self.haz_prox_grid.as_mut() {
-            hpg.register_hazard(&hazard)
+            for hazard in components.iter() {
+                hpg.register_hazard(hazard)
+            }
         }
-        self.dynamic_hazards.push(hazard);
+        self.dynamic_hazards.extend(components);
 
         debug_assert!(assertions::qt_contains_no_dangling_hazards(self));
     }
 
-    /// Removes a hazard from the CDE.
+    /// Removes a hazard (all of its components) from the CDE.
     /// If `commit_instant` the deregistration is fully executed immediately.
     /// If not, the deregistration causes the hazard to be deactivated in the quadtree and
     /// the hazard_proximity_grid to become dirty (and therefore inaccessible).
@@ -115,24 +144,24 @@ impl CDEngine {
     /// Call [`Self::commit_deregisters`] to commit all uncommitted deregisters in both quadtree & hazard proximity grid
     /// or [`Self::flush_haz_prox_grid`] to just clear the hazard proximity grid.
     pub fn deregister_hazard(&mut self, hazard_entity: HazardEntity, commit_instant: bool) {
-        let haz_index = self
-            .dynamic_hazards
-            .iter()
-            .position(|h| h.entity == hazard_entity)
-            .expect("Hazard not found");
-
-        let hazard = self.dynamic_hazards.swap_remove(haz_index);
+        let (components, remaining) = std::mem::take(&mut self.dynamic_hazards)
+            .into_iter()
+            .partition::<Vec<_>, _>(|h| h.entity == hazard_entity);
+        assert!(!components.is_empty(), "Hazard not found");
+        self.dynamic_hazards = remaining;
 
+        if let Some(hpg) = self.haz_prox_grid.as_mut() {
+            for hazard in components.iter() {
+                hpg.deregister_hazard(hazard, self.dynamic_hazards.iter(), commit_instant)
+            }
+        }
         match commit_instant {
             true => self.quadtree.deregister_hazard(hazard_entity),
             false => {
                 self.quadtree.deactivate_hazard(hazard_entity);
-                self.uncommitted_deregisters.push(hazard);
+                self.uncommitted_deregisters.extend(components);
             }
         }
-        if let Some(hpg) = self.haz_prox_grid.as_mut() {
-            hpg.deregister_hazard(hazard_entity, self.dynamic_hazards.iter(), commit_instant)
-        }
         debug_assert!(assertions::qt_contains_no_dangling_hazards(self));
     }
 
@@ -143,6 +172,7 @@ impl CDEngine {
             .as_ref()
             .map_or(true, |hpg| !hpg.is_dirty()));
         CDESnapshot {
+            static_hazards: self.static_hazards.clone(),
             dynamic_hazards: self.dynamic_hazards.clone(),
             grid: self.haz_prox_grid.as_ref().map(|hpg| hpg.grid.clone()),
         }
@@ -151,47 +181,54 @@ impl CDEngine {
     /// Restores the CDE to a previous state, as described by the snapshot.
     pub fn restore(&mut self, snapshot: &CDESnapshot) {
         //Quadtree
-        let mut hazards_to_remove = self
+        let current_entities = self
+            .dynamic_hazards
+            .iter()
+            .map(|h| h.entity)
+            .collect::<IndexSet<HazardEntity>>();
+        let snapshot_entities = snapshot
             .dynamic_hazards
             .iter()
             .map(|h| h.entity)
             .collect::<IndexSet<HazardEntity>>();
-        debug_assert!(hazards_to_remove.len() == self.dynamic_hazards.len());
-        let mut hazards_to_add = vec![];
 
-        for hazard in snapshot.dynamic_hazards.iter() {
-            let hazard_already_present = hazards_to_remove.swap_remove(&hazard.entity);
-            if !hazard_already_present {
-                hazards_to_add.push(hazard.clone());
-            }
-        }
+        //Hazards in the snapshot, but not currently registered in the CDE
+        let mut hazards_to_add = snapshot
+            .dynamic_hazards
+            .iter()
+            .filter(|h| !current_entities.contains(&h.entity))
+            .cloned()
+            .collect::<Vec<_>>();
 
         //Hazards currently registered in the CDE, but not in the snapshot
-        for haz_entity in hazards_to_remove.iter() {
-            let haz_index = self
-                .dynamic_hazards
-                .iter()
-                .position(|h| &h.entity == haz_entity)
-                .expect("Hazard not found");
-            self.dynamic_hazards.swap_remove(haz_index);
+        for haz_entity in current_entities.difference(&snapshot_entities) {
+            self.dynamic_hazards.retain(|h| &h.entity != haz_entity);
             self.quadtree.deregister_hazard(*haz_entity);
         }
 
         //Some of the uncommitted deregisters might be in present in snapshot, if so we can just reactivate them
-        for unc_haz in self.uncommitted_deregisters.drain(..) {
-            if let Some(pos) = hazards_to_add
-                .iter()
-                .position(|h| h.entity == unc_haz.entity)
-            {
+        let uncommitted_entities = self
+            .uncommitted_deregisters
+            .iter()
+            .map(|h| h.entity)
+            .collect::<IndexSet<HazardEntity>>();
+        for unc_entity in uncommitted_entities {
+            if hazards_to_add.iter().any(|h| h.entity == unc_entity) {
                 //the uncommitted removed hazard needs to be activated again
-                self.quadtree.activate_hazard(unc_haz.entity);
-                self.dynamic_hazards.push(unc_haz);
-                hazards_to_add.swap_remove(pos);
+                self.quadtree.activate_hazard(unc_entity);
+                hazards_to_add.retain(|h| h.entity != unc_entity);
+                self.dynamic_hazards.extend(
+                    self.uncommitted_deregisters
+                        .iter()
+                        .filter(|h| h.entity == unc_entity)
+                        .cloned(),
+                );
             } else {
                 //uncommitted deregister is not preset in the snapshot, delete it from the quadtree
-                self.quadtree.deregister_hazard(unc_haz.entity);
+                self.quadtree.deregister_hazard(unc_entity);
             }
         }
+        self.uncommitted_deregisters.clear();
 
         for hazard in hazards_to_add {
             self.quadtree.register_hazard((&hazard).into());
@@ -199,13 +236,122 @@ impl CDEngine {
         }
 
         //Hazard proximity grid
-        if let Some(hpg) = self.haz_prox_grid.as_mut() {
-            hpg.restore(snapshot.grid.clone().expect("no hpg in snapshot"));
+        let static_hazards_unchanged = self
+            .static_hazards
+            .iter()
+            .map(|h| (h.entity, Arc::as_ptr(&h.shape)))
+            .eq(snapshot
+                .static_hazards
+                .iter()
+                .map(|h| (h.entity, Arc::as_ptr(&h.shape))));
+        match static_hazards_unchanged {
+            true => {
+                if let Some(hpg) = self.haz_prox_grid.as_mut() {
+                    hpg.restore(snapshot.grid.clone().expect("no hpg in snapshot"));
+                }
+            }
+            //static hazards are not part of the snapshot, so the grid of the snapshot no longer applies
+            false => self.rebuild_haz_prox_grid(),
         }
 
         debug_assert!(self.dynamic_hazards.len() == snapshot.dynamic_hazards.len());
     }
 
+    /// Registers a new static hazard in the CDE.
+    /// Static hazards are not part of the [CDESnapshot]s and therefore persist when restoring to a snapshot.
+    /// Only hazards induced by [HazardEntity::Custom] entities can be registered at runtime.
+    pub fn register_static_hazard(&mut self, hazard: Hazard) {
+        assert!(
+            matches!(hazard.entity, HazardEntity::Custom { .. }),
+            "only custom hazards can be registered at runtime"
+        );
+        assert!(
+            !self.all_hazards().any(|h| h.entity == hazard.entity),
+            "Hazard already registered"
+        );
+        self.commit_deregisters();
+        self.quadtree.register_hazard((&hazard).into());
+        if let Some(hpg) = self.haz_prox_grid.as_mut() {
+            hpg.register_static_hazard(&hazard);
+        }
+        self.static_hazards.push(hazard);
+
+        debug_assert!(assertions::qt_contains_no_dangling_hazards(self));
+    }
+
+    /// Removes a static hazard, previously registered with [`Self::register_static_hazard`], from the CDE.
+    pub fn deregister_static_hazard(&mut self, hazard_entity: HazardEntity) -> Hazard {
+        assert!(
+            matches!(hazard_entity, HazardEntity::Custom { .. }),
+            "only custom hazards can be deregistered at runtime"
+        );
+        let haz_index = self
+            .static_hazards
+            .iter()
+            .position(|h| h.entity == hazard_entity)
+            .expect("Hazard not found");
+
+        self.commit_deregisters();
+        let hazard = self.static_hazards.remove(haz_index);
+        self.quadtree.deregister_hazard(hazard_entity);
+        if let Some(hpg) = self.haz_prox_grid.as_mut() {
+            hpg.deregister_static_hazard(
+                &hazard,
+                &self.static_hazards,
+                self.dynamic_hazards.iter(),
+            );
+        }
+
+        debug_assert!(assertions::qt_contains_no_dangling_hazards(self));
+        hazard
+    }
+
+    /// Replaces the shape of the [HazardEntity::BinExterior] hazard, for bins which change shape at runtime (e.g. strips).
+    /// Only the parts of the quadtree and hazard proximity grid in the vicinity of the old and new bin are updated.
+    /// The new shape should lie within the bounding box of the CDE.
+    pub fn update_bin_exterior(&mut self, shape: Arc<SimplePolygon>) {
+        assert!(
+            matches!(
+                self.bbox.relation_to(&shape.bbox()),
+                GeoRelation::Surrounding
+            ),
+            "new bin exterior exceeds the bounding box of the CDE"
+        );
+        self.commit_deregisters();
+
+        let haz_index = self
+            .static_hazards
+            .iter()
+            .position(|h| h.entity == HazardEntity::BinExterior)
+            .expect("no bin exterior hazard");
+        let new_hazard = Hazard::new(HazardEntity::BinExterior, shape);
+        let old_hazard = std::mem::replace(&mut self.static_hazards[haz_index], new_hazard);
+
+        self.quadtree.deregister_hazard(HazardEntity::BinExterior);
+        self.quadtree
+            .register_hazard((&self.static_hazards[haz_index]).into());
+
+        if let Some(hpg) = self.haz_prox_grid.as_mut() {
+            hpg.update_bin_exterior(
+                &old_hazard.shape.bbox(),
+                &self.static_hazards,
+                self.dynamic_hazards.iter(),
+            );
+        }
+
+        debug_assert!(assertions::qt_contains_no_dangling_hazards(self));
+    }
+
+    fn rebuild_haz_prox_grid(&mut self) {
+        if let Some(hpg) = self.haz_prox_grid.as_mut() {
+            let n_cells = self.config.hpg_n_cells;
+            *hpg = HazardProximityGrid::new(self.bbox.clone(), &self.static_hazards, n_cells);
+            for hazard in self.dynamic_hazards.iter() {
+                hpg.register_hazard(hazard);
+            }
+        }
+    }
+
     /// Commits all pending deregisters by actually removing them from the quadtree
     /// and flushing the hazard proximity grid.
     pub fn commit_deregisters(&mut self) {
@@ -257,6 +403,17 @@ impl CDEngine {
         }
     }
 
+    /// Returns the statistics of all queries resolved by this CDE since its creation (or last reset).
+    #[cfg(feature = "cde-stats")]
+    pub fn stats(&self) -> CDEStats {
+        self.counters.stats()
+    }
+
+    #[cfg(feature = "cde-stats")]
+    pub fn reset_stats(&mut self) {
+        self.counters.reset()
+    }
+
     pub fn has_uncommitted_deregisters(&self) -> bool {
         !self.uncommitted_deregisters.is_empty()
     }
@@ -266,7 +423,8 @@ impl CDEngine {
         &self.dynamic_hazards
     }
 
-    /// Returns all hazards in the CDE, which cannot change during the lifetime of the CDE.
+    /// Returns all static hazards in the CDE.
+    /// These only change when custom hazards are registered with [`Self::register_static_hazard`].
     pub fn static_hazards(&self) -> &Vec<Hazard> {
         &self.static_hazards
     }
@@ -304,6 +462,38 @@ impl CDEngine {
         }
     }
 
+    /// Evaluates a batch of transformations of a reference simple polygon in parallel.
+    /// Returns for every transformation whether the transformed shape collides with any of the (relevant) hazards,
+    /// as determined by [`Self::surrogate_or_poly_collides`].
+    /// The work is divided over rayon's thread pool, each thread using its own buffer shape.
+    /// # Arguments
+    /// * `reference_shape` - The shape to be checked for collisions, requires a surrogate
+    /// * `transforms` - The transformations to be applied to the reference shape
+    /// * `irrelevant_hazards` - entities to be ignored during the check
+    pub fn evaluate_batch(
+        &self,
+        reference_shape: &SimplePolygon,
+        transforms: &[Transformation],
+        irrelevant_hazards: &[HazardEntity],
+    ) -> Vec<bool> {
+        let create_buffer = || {
+            let mut buffer = reference_shape.clone();
+            buffer.surrogate = None; //the buffer shape does not need a surrogate
+            buffer
+        };
+        transforms
+            .par_iter()
+            .map_init(create_buffer, |buffer, transform| {
+                self.surrogate_or_poly_collides(
+                    reference_shape,
+                    transform,
+                    buffer,
+                    irrelevant_hazards,
+                )
+            })
+            .collect()
+    }
+
     ///Checks whether a simple polygon collides with any of the (relevant) hazards
     /// # Arguments
     /// * `shape` - The shape (already transformed) to be checked for collisions
@@ -313,12 +503,49 @@ impl CDEngine {
         shape: &SimplePolygon,
         irrelevant_hazards: &[HazardEntity],
     ) -> bool {
+        #[cfg(feature = "cde-stats")]
+        let start = std::time::Instant::now();
+
+        let detection = self.detect_poly_collision(shape, irrelevant_hazards);
+
+        #[cfg(feature = "cde-stats")]
+        self.counters.record_poly_query(detection, start.elapsed());
+
+        detection.is_some()
+    }
+
+    fn detect_poly_collision(
+        &self,
+        shape: &SimplePolygon,
+        irrelevant_hazards: &[HazardEntity],
+    ) -> Option<Detection> {
         match self.bbox.relation_to(&shape.bbox()) {
             //Not fully inside bbox => definite collision
-            GeoRelation::Disjoint | GeoRelation::Enclosed | GeoRelation::Intersecting => true,
+            GeoRelation::Disjoint | GeoRelation::Enclosed | GeoRelation::Intersecting => {
+                Some(Detection::BBox)
+            }
             GeoRelation::Surrounding => {
-                self.poly_collides_by_edge_intersection(shape, irrelevant_hazards)
-                    || self.poly_collides_by_containment(shape, irrelevant_hazards)
+                #[cfg(feature = "cde-stats")]
+                let start = std::time::Instant::now();
+
+                let detection = self.poly_collides_by_edge_intersection(shape, irrelevant_hazards);
+
+                #[cfg(feature = "cde-stats")]
+                self.counters.record_edge_intersection_time(start.elapsed());
+
+                if detection.is_some() {
+                    return detection;
+                }
+
+                #[cfg(feature = "cde-stats")]
+                let start = std::time::Instant::now();
+
+                let contained = self.poly_collides_by_containment(shape, irrelevant_hazards);
+
+                #[cfg(feature = "cde-stats")]
+                self.counters.record_containment_time(start.elapsed());
+
+                contained.then_some(Detection::Containment)
             }
         }
     }
@@ -334,27 +561,102 @@ impl CDEngine {
         transform: &Transformation,
         irrelevant_hazards: &[HazardEntity],
     ) -> bool {
+        #[cfg(feature = "cde-stats")]
+        let start = std::time::Instant::now();
+
+        let detection =
+            self.detect_surrogate_collision(base_surrogate, transform, irrelevant_hazards);
+
+        #[cfg(feature = "cde-stats")]
+        self.counters
+            .record_surrogate_query(detection, start.elapsed());
+
+        detection.is_some()
+    }
+
+    fn detect_surrogate_collision(
+        &self,
+        base_surrogate: &SPSurrogate,
+        transform: &Transformation,
+        irrelevant_hazards: &[HazardEntity],
+    ) -> Option<Detection> {
+        let robust = self.config.robust_predicates;
         for pole in base_surrogate.ff_poles() {
             let t_pole = pole.transform_clone(transform);
-            if self
+            if let Some(hz) = self
                 .quadtree
-                .collides(&t_pole, irrelevant_hazards)
-                .is_some()
+                .collides_hazard(&t_pole, irrelevant_hazards, robust)
             {
-                return true;
+                return Some(Detection::Pole {
+                    entire: matches!(hz.presence, QTHazPresence::Entire),
+                });
             }
         }
         for pier in base_surrogate.ff_piers() {
             let t_pier = pier.transform_clone(transform);
-            if self
+            if let Some(hz) = self
                 .quadtree
-                .collides(&t_pier, irrelevant_hazards)
-                .is_some()
+                .collides_hazard(&t_pier, irrelevant_hazards, robust)
             {
-                return true;
+                return Some(Detection::Pier {
+                    entire: matches!(hz.presence, QTHazPresence::Entire),
+                });
             }
         }
-        false
+        None
+    }
+
+    /// Determines how far a shape can be translated in a direction before it collides with any of the (relevant) hazards.
+    /// Returns the travel distance and the hazard which blocks further movement (if any).
+    /// The exact distance is computed by sweeping the edges and vertices of the shape
+    /// against those of the hazards registered in the quadtree.
+    /// Leaving the bounding box of the CDE is considered a collision with [HazardEntity::BinExterior].
+    /// # Arguments
+    /// * `shape` - The shape (already transformed) to be translated, assumed to be collision-free
+    /// * `direction` - The direction of the translation, does not need to be normalized
+    /// * `irrelevant_hazards` - entities to be ignored during the check
+    pub fn max_translation(
+        &self,
+        shape: &SimplePolygon,
+        direction: (fsize, fsize),
+        irrelevant_hazards: &[HazardEntity],
+    ) -> (fsize, Option<HazardEntity>) {
+        let length = (direction.0.powi(2) + direction.1.powi(2)).sqrt();
+        assert!(length > 0.0, "direction of translation has no length");
+        let direction = (direction.0 / length, direction.1 / length);
+
+        //distance until the shape leaves the bounding box of the CDE
+        let s_bbox = &shape.bbox;
+        let bbox_limit = |d: fsize, to_min: fsize, to_max: fsize| match d {
+            d if d > 0.0 => to_max / d,
+            d if d < 0.0 => to_min / d,
+            _ => fsize::INFINITY,
+        };
+        let bbox_distance = fsize::min(
+            bbox_limit(
+                direction.0,
+                self.bbox.x_min - s_bbox.x_min,
+                self.bbox.x_max - s_bbox.x_max,
+            ),
+            bbox_limit(
+                direction.1,
+                self.bbox.y_min - s_bbox.y_min,
+                self.bbox.y_max - s_bbox.y_max,
+            ),
+        );
+
+        let mut closest = (
+            fsize::max(bbox_distance, 0.0),
+            Some(HazardEntity::BinExterior),
+        );
+        sweep::sweep_quadtree(
+            &self.quadtree,
+            shape,
+            direction,
+            irrelevant_hazards,
+            &mut closest,
+        );
+        closest
     }
 
     /// Checks whether a point definitely collides with any of the (relevant) hazards.
@@ -398,10 +700,24 @@ impl CDEngine {
         &self,
         shape: &SimplePolygon,
         irrelevant_hazards: &[HazardEntity],
-    ) -> bool {
-        shape
-            .edge_iter()
-            .any(|e| self.quadtree.collides(&e, irrelevant_hazards).is_some())
+    ) -> Option<Detection> {
+        let robust = self.config.robust_predicates;
+        let colliding_hazard = match shape.edge_bvh.as_ref() {
+            Some(edge_bvh) => self.quadtree.collides_hazard_edge_chain(
+                shape,
+                edge_bvh,
+                edge_bvh.root(),
+                irrelevant_hazards,
+                robust,
+            ),
+            None => shape.border_edge_iter().find_map(|e| {
+                self.quadtree
+                    .collides_hazard(&e, irrelevant_hazards, robust)
+            }),
+        };
+        colliding_hazard.map(|hz| Detection::EdgeIntersection {
+            entire: matches!(hz.presence, QTHazPresence::Entire),
+        })
     }
 
     fn poly_collides_by_containment(
@@ -445,7 +761,7 @@ impl CDEngine {
                 return collides;
             }
         }
-        let inclusion = s_omega.collides_with(&s_mu.poi.center);
+        let inclusion = s_omega.contains_point(&s_mu.poi.center, self.config.robust_predicates);
 
         match haz.entity.position() {
             GeoPosition::Interior => inclusion,
@@ -466,7 +782,8 @@ impl CDEngine {
         detected.extend(irrelevant_hazards.iter().cloned());
         let irrelevant_range = n_init_detected..detected.len();
 
-        self.quadtree.collect_collisions(entity, detected);
+        self.quadtree
+            .collect_collisions(entity, detected, self.config.robust_predicates);
 
         //drain the irrelevant hazards, leaving only the non-ignored colliding entities
         detected.drain(irrelevant_range);
@@ -490,6 +807,8 @@ impl CDEngine {
         irrelevant_hazards: &[HazardEntity],
         detected: &mut Vec<HazardEntity>,
     ) {
+        let robust = self.config.robust_predicates;
+
         //temporarily add the irrelevant hazards to the buffer
         let n_init_detected = detected.len();
         detected.extend(irrelevant_hazards.iter().cloned());
@@ -497,8 +816,8 @@ impl CDEngine {
 
         //collect all colliding entities due to edge intersection
         shape
-            .edge_iter()
-            .for_each(|e| self.quadtree.collect_collisions(&e, detected));
+            .border_edge_iter()
+            .for_each(|e| self.quadtree.collect_collisions(&e, detected, robust));
 
         //collect all colliding entities due to containment
         //TODO: check if gathering the hazards inside the bbox using the quadtree is faster
@@ -521,6 +840,8 @@ impl CDEngine {
         irrelevant_hazards: &[HazardEntity],
         detected: &mut Vec<HazardEntity>,
     ) {
+        let robust = self.config.robust_predicates;
+
         //temporarily add the irrelevant hazards to the buffer
         let n_init_detected = detected.len();
         detected.extend(irrelevant_hazards.iter().cloned());
@@ -528,14 +849,31 @@ impl CDEngine {
 
         for pole in base_surrogate.ff_poles() {
             let t_pole = pole.transform_clone(transform);
-            self.quadtree.collect_collisions(&t_pole, detected)
+            self.quadtree.collect_collisions(&t_pole, detected, robust)
         }
         for pier in base_surrogate.ff_piers() {
             let t_pier = pier.transform_clone(transform);
-            self.quadtree.collect_collisions(&t_pier, detected);
+            self.quadtree.collect_collisions(&t_pier, detected, robust);
         }
 
         //drain the irrelevant hazards, leaving only the colliding entities
         detected.drain(irrelevant_range);
     }
 }
+
+/// How a collision was detected by the [CDEngine], used to collect [statistics](crate::collision_detection::cde_stats::CDEStats).
+/// `entire` indicates whether the collision was resolved by a quadtree node entirely covered by a hazard.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(not(feature = "cde-stats"), allow(dead_code))]
+pub(crate) enum Detection {
+    /// By one of the poles of the surrogate
+    Pole { entire: bool },
+    /// By one of the piers of the surrogate
+    Pier { entire: bool },
+    /// The shape is not fully inside the bounding box of the CDE
+    BBox,
+    /// By an intersection between the edges of the shape and those of a hazard
+    EdgeIntersection { entire: bool },
+    /// The shape is contained in a hazard or vice versa
+    Containment,
+}
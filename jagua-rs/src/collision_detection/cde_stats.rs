@@ -0,0 +1,203 @@
+use std::fmt::{Display, Formatter};
+use std::ops::{Add, AddAssign};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use crate::collision_detection::cd_engine::Detection;
+
+/// Statistics on the queries resolved by a [CDEngine](crate::collision_detection::cd_engine::CDEngine).
+/// Shows in which stage collisions are detected, and thus where fail-fast pays off.
+/// Only collected when feature **cde-stats** is enabled.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct CDEStats {
+    /// Number of queries on surrogates
+    pub n_surrogate_queries: u64,
+    /// Number of surrogate queries in which a collision was detected by one of the poles
+    pub n_detected_by_poles: u64,
+    /// Number of surrogate queries in which a collision was detected by one of the piers
+    pub n_detected_by_piers: u64,
+    /// Number of queries on polygons
+    pub n_poly_queries: u64,
+    /// Number of polygon queries in which the polygon was not fully inside the bounding box of the CDE
+    pub n_detected_by_bbox: u64,
+    /// Number of polygon queries in which a collision was detected by edge intersection
+    pub n_detected_by_edge_intersection: u64,
+    /// Number of polygon queries in which a collision was detected by containment
+    pub n_detected_by_containment: u64,
+    /// Number of collisions detected by poles, piers or edges which were resolved by a quadtree node entirely covered by a hazard
+    pub n_detected_by_entire_nodes: u64,
+    /// Total time spent on surrogate queries
+    pub surrogate_time: Duration,
+    /// Total time spent on polygon queries
+    pub poly_time: Duration,
+    /// Time spent on the edge intersection phase of polygon queries
+    pub edge_intersection_time: Duration,
+    /// Time spent on the containment phase of polygon queries
+    pub containment_time: Duration,
+}
+
+impl CDEStats {
+    /// Number of surrogate queries in which no collision was detected
+    pub fn n_surrogate_passed(&self) -> u64 {
+        self.n_surrogate_queries - self.n_detected_by_poles - self.n_detected_by_piers
+    }
+
+    /// Number of polygon queries in which no collision was detected
+    pub fn n_poly_passed(&self) -> u64 {
+        self.n_poly_queries
+            - self.n_detected_by_bbox
+            - self.n_detected_by_edge_intersection
+            - self.n_detected_by_containment
+    }
+}
+
+impl Add for CDEStats {
+    type Output = CDEStats;
+
+    fn add(self, other: CDEStats) -> CDEStats {
+        CDEStats {
+            n_surrogate_queries: self.n_surrogate_queries + other.n_surrogate_queries,
+            n_detected_by_poles: self.n_detected_by_poles + other.n_detected_by_poles,
+            n_detected_by_piers: self.n_detected_by_piers + other.n_detected_by_piers,
+            n_poly_queries: self.n_poly_queries + other.n_poly_queries,
+            n_detected_by_bbox: self.n_detected_by_bbox + other.n_detected_by_bbox,
+            n_detected_by_edge_intersection: self.n_detected_by_edge_intersection
+                + other.n_detected_by_edge_intersection,
+            n_detected_by_containment: self.n_detected_by_containment
+                + other.n_detected_by_containment,
+            n_detected_by_entire_nodes: self.n_detected_by_entire_nodes
+                + other.n_detected_by_entire_nodes,
+            surrogate_time: self.surrogate_time + other.surrogate_time,
+            poly_time: self.poly_time + other.poly_time,
+            edge_intersection_time: self.edge_intersection_time + other.edge_intersection_time,
+            containment_time: self.containment_time + other.containment_time,
+        }
+    }
+}
+
+impl AddAssign for CDEStats {
+    fn add_assign(&mut self, other: CDEStats) {
+        *self = *self + other;
+    }
+}
+
+impl Display for CDEStats {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let pct = |n: u64, total: u64| match total {
+            0 => 0.0,
+            _ => n as f64 / total as f64 * 100.0,
+        };
+        let s_total = self.n_surrogate_queries;
+        let p_total = self.n_poly_queries;
+        write!(
+            f,
+            "surrogate: {} queries in {:.3}ms (poles: {:.1}%, piers: {:.1}%, passed: {:.1}%), \
+            poly: {} queries in {:.3}ms (bbox: {:.1}%, edges: {:.1}% [{:.3}ms], containment: {:.1}% [{:.3}ms], passed: {:.1}%), \
+            entire nodes: {} detections",
+            s_total,
+            self.surrogate_time.as_secs_f64() * 1000.0,
+            pct(self.n_detected_by_poles, s_total),
+            pct(self.n_detected_by_piers, s_total),
+            pct(self.n_surrogate_passed(), s_total),
+            p_total,
+            self.poly_time.as_secs_f64() * 1000.0,
+            pct(self.n_detected_by_bbox, p_total),
+            pct(self.n_detected_by_edge_intersection, p_total),
+            self.edge_intersection_time.as_secs_f64() * 1000.0,
+            pct(self.n_detected_by_containment, p_total),
+            self.containment_time.as_secs_f64() * 1000.0,
+            pct(self.n_poly_passed(), p_total),
+            self.n_detected_by_entire_nodes,
+        )
+    }
+}
+
+/// Thread-safe counters from which [CDEStats] are derived.
+/// Cloning produces a fresh set of counters, so every [CDEngine](crate::collision_detection::cd_engine::CDEngine)
+/// only tracks the queries resolved by itself.
+#[derive(Debug, Default)]
+pub struct CDECounters {
+    n_surrogate_queries: AtomicU64,
+    n_detected_by_poles: AtomicU64,
+    n_detected_by_piers: AtomicU64,
+    n_poly_queries: AtomicU64,
+    n_detected_by_bbox: AtomicU64,
+    n_detected_by_edge_intersection: AtomicU64,
+    n_detected_by_containment: AtomicU64,
+    n_detected_by_entire_nodes: AtomicU64,
+    surrogate_time_ns: AtomicU64,
+    poly_time_ns: AtomicU64,
+    edge_intersection_time_ns: AtomicU64,
+    containment_time_ns: AtomicU64,
+}
+
+impl CDECounters {
+    pub(crate) fn record_surrogate_query(&self, detection: Option<Detection>, elapsed: Duration) {
+        increment(&self.n_surrogate_queries, 1);
+        increment(&self.surrogate_time_ns, elapsed.as_nanos() as u64);
+        self.record_detection(detection);
+    }
+
+    pub(crate) fn record_poly_query(&self, detection: Option<Detection>, elapsed: Duration) {
+        increment(&self.n_poly_queries, 1);
+        increment(&self.poly_time_ns, elapsed.as_nanos() as u64);
+        self.record_detection(detection);
+    }
+
+    pub(crate) fn record_edge_intersection_time(&self, elapsed: Duration) {
+        increment(&self.edge_intersection_time_ns, elapsed.as_nanos() as u64);
+    }
+
+    pub(crate) fn record_containment_time(&self, elapsed: Duration) {
+        increment(&self.containment_time_ns, elapsed.as_nanos() as u64);
+    }
+
+    fn record_detection(&self, detection: Option<Detection>) {
+        let (counter, entire) = match detection {
+            None => return,
+            Some(Detection::Pole { entire }) => (&self.n_detected_by_poles, entire),
+            Some(Detection::Pier { entire }) => (&self.n_detected_by_piers, entire),
+            Some(Detection::BBox) => (&self.n_detected_by_bbox, false),
+            Some(Detection::EdgeIntersection { entire }) => {
+                (&self.n_detected_by_edge_intersection, entire)
+            }
+            Some(Detection::Containment) => (&self.n_detected_by_containment, false),
+        };
+        increment(counter, 1);
+        if entire {
+            increment(&self.n_detected_by_entire_nodes, 1);
+        }
+    }
+
+    pub fn stats(&self) -> CDEStats {
+        let load = |c: &AtomicU64| c.load(Ordering::Relaxed);
+        CDEStats {
+            n_surrogate_queries: load(&self.n_surrogate_queries),
+            n_detected_by_poles: load(&self.n_detected_by_poles),
+            n_detected_by_piers: load(&self.n_detected_by_piers),
+            n_poly_queries: load(&self.n_poly_queries),
+            n_detected_by_bbox: load(&self.n_detected_by_bbox),
+            n_detected_by_edge_intersection: load(&self.n_detected_by_edge_intersection),
+            n_detected_by_containment: load(&self.n_detected_by_containment),
+            n_detected_by_entire_nodes: load(&self.n_detected_by_entire_nodes),
+            surrogate_time: Duration::from_nanos(load(&self.surrogate_time_ns)),
+            poly_time: Duration::from_nanos(load(&self.poly_time_ns)),
+            edge_intersection_time: Duration::from_nanos(load(&self.edge_intersection_time_ns)),
+            containment_time: Duration::from_nanos(load(&self.containment_time_ns)),
+        }
+    }
+
+    pub fn reset(&mut self) {
+        *self = CDECounters::default();
+    }
+}
+
+impl Clone for CDECounters {
+    fn clone(&self) -> Self {
+        CDECounters::default()
+    }
+}
+
+fn increment(counter: &AtomicU64, value: u64) {
+    counter.fetch_add(value, Ordering::Relaxed);
+}
@@ -33,8 +33,13 @@ pub enum HazardEntity {
     BinExterior,
     /// Represents a hole in the bin.
     BinHole { id: usize },
+    /// Represents an item fixed in place in the bin, which can never be removed.
+    FixedItem { id: usize },
     /// Represents a zone in the bin with a specific quality level that is inferior to the base quality.
     InferiorQualityZone { quality: usize, id: usize },
+    /// A user-defined hazard registered at runtime (e.g. a clamp, a reserved zone or a defect).
+    /// `position` defines whether the inside or the outside of its shape is hazardous.
+    Custom { id: usize, position: GeoPosition },
 }
 
 impl HazardEntity {
@@ -44,7 +49,9 @@ impl HazardEntity {
             HazardEntity::PlacedItem(_) => GeoPosition::Interior,
             HazardEntity::BinExterior => GeoPosition::Exterior,
             HazardEntity::BinHole { .. } => GeoPosition::Interior,
+            HazardEntity::FixedItem { .. } => GeoPosition::Interior,
             HazardEntity::InferiorQualityZone { .. } => GeoPosition::Interior,
+            HazardEntity::Custom { position, .. } => *position,
         }
     }
 
@@ -54,7 +61,9 @@ impl HazardEntity {
             HazardEntity::PlacedItem(_) => true,
             HazardEntity::BinExterior => false,
             HazardEntity::BinHole { .. } => false,
+            HazardEntity::FixedItem { .. } => false,
             HazardEntity::InferiorQualityZone { .. } => false,
+            HazardEntity::Custom { .. } => true,
         }
     }
 
@@ -64,7 +73,9 @@ impl HazardEntity {
             HazardEntity::PlacedItem(_) => true,
             HazardEntity::BinExterior => true,
             HazardEntity::BinHole { .. } => true,
+            HazardEntity::FixedItem { .. } => true,
             HazardEntity::InferiorQualityZone { .. } => false,
+            HazardEntity::Custom { .. } => true,
         }
     }
 }
@@ -33,6 +33,10 @@ pub struct QZHazardFilter(pub usize);
 /// Deems hazards induced by specific entities as irrelevant.
 pub struct EntityHazardFilter(pub Vec<HazardEntity>);
 
+/// Deems hazards induced by custom entities with specific ids as irrelevant.
+#[derive(Clone, Debug)]
+pub struct CustomHazardFilter(pub Vec<usize>);
+
 /// Combines multiple `HazardFilter`s into a single filter.
 pub struct CombinedHazardFilter<'a> {
     pub filters: Vec<Box<&'a dyn HazardFilter>>,
@@ -44,7 +48,9 @@ impl HazardFilter for BinHazardFilter {
             HazardEntity::PlacedItem(_) => false,
             HazardEntity::BinExterior => true,
             HazardEntity::BinHole { .. } => true,
+            HazardEntity::FixedItem { .. } => false,
             HazardEntity::InferiorQualityZone { .. } => true,
+            HazardEntity::Custom { .. } => false,
         }
     }
 }
@@ -61,6 +67,15 @@ impl HazardFilter for EntityHazardFilter {
     }
 }
 
+impl HazardFilter for CustomHazardFilter {
+    fn is_irrelevant(&self, entity: &HazardEntity) -> bool {
+        match entity {
+            HazardEntity::Custom { id, .. } => self.0.contains(id),
+            _ => false,
+        }
+    }
+}
+
 impl HazardFilter for QZHazardFilter {
     fn is_irrelevant(&self, entity: &HazardEntity) -> bool {
         match entity {
@@ -67,6 +67,28 @@ impl<T> Grid<T> {
         }
     }
 
+    /// Adds new elements to the grid, introducing new rows and columns where necessary.
+    /// Elements at the coordinates of existing cells replace them.
+    pub fn extend(&mut self, elements: Vec<(T, Point)>) {
+        let existing = std::mem::take(&mut self.cells)
+            .into_iter()
+            .enumerate()
+            .filter_map(|(i, cell)| {
+                let (row, col) = (i / self.n_cols, i % self.n_cols);
+                cell.map(|c| (c, Point(*self.cols[col], *self.rows[row])))
+            });
+        let all_elements = existing.chain(elements).collect_vec();
+
+        *self = Self::new(all_elements);
+    }
+
+    /// Returns the index of the cell with exactly these coordinates, if the grid has such a row and column
+    pub fn index_of(&self, Point(x, y): Point) -> Option<usize> {
+        let row = self.rows.binary_search(&NotNan::new(y).ok()?).ok()?;
+        let col = self.cols.binary_search(&NotNan::new(x).ok()?).ok()?;
+        self.to_index(row, col).ok()
+    }
+
     //returns the range of row indices to completely cover the coordinate range
     pub fn rows_in_range(&self, y_range: RangeInclusive<fsize>) -> RangeInclusive<usize> {
         let start_range = NotNan::new(*y_range.start()).expect("start is NaN");
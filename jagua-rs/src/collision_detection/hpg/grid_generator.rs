@@ -14,7 +14,13 @@ const MAX_ITERATIONS: usize = 25;
 /// All hazards are regarded as exterior regions, and will not contain cells.
 /// The number of cells is approximately equal to target_n_cells, but can be slightly more or less
 /// This is due to the fact that the cells are always remain square, so we cannot guarantee an exact number of cells
-pub fn generate(bbox: AARectangle, hazards: &[Hazard], target_n_cells: usize) -> Vec<AARectangle> {
+/// <br>
+/// Returns the relevant cells, together with the dimension of the lattice they are part of.
+pub fn generate(
+    bbox: AARectangle,
+    hazards: &[Hazard],
+    target_n_cells: usize,
+) -> (Vec<AARectangle>, fsize) {
     assert!(bbox.area() > 0.0, "bbox has zero area");
 
     let mut cells = vec![];
@@ -23,23 +29,16 @@ pub fn generate(bbox: AARectangle, hazards: &[Hazard], target_n_cells: usize) ->
     let mut step_size = 0.1;
     let mut n_iters = 0;
     let mut previous_attempt = None;
+    let mut cell_dim;
 
     loop {
-        let cell_dim = fsize::sqrt(bbox.area() / target_n_cells as fsize) * correction_factor; //square cells
-        let n_cells_in_x = fsize::ceil(bbox.width() / cell_dim) as usize;
-        let n_cells_in_y = fsize::ceil(bbox.height() / cell_dim) as usize;
-        let cell_radius = fsize::sqrt(2.0 * (cell_dim / 2.0).powi(2)); //half of the maximum distance between two cell centers
+        cell_dim = fsize::sqrt(bbox.area() / target_n_cells as fsize) * correction_factor; //square cells
+        let (n_cells_in_x, n_cells_in_y) = lattice_size(&bbox, cell_dim);
 
         for i in 0..n_cells_in_x {
-            let x_min = bbox.x_min + cell_dim * i as fsize;
-            let x_max = x_min + cell_dim;
             for j in 0..n_cells_in_y {
-                let y_min = bbox.y_min + cell_dim * j as fsize;
-                let y_max = y_min + cell_dim;
-                let rect = AARectangle::new(x_min, y_min, x_max, y_max);
-                //test if the cell is relevant
-                let distance_to_hazard = distance_to_hazard(&rect.centroid(), hazards.iter());
-                if distance_to_hazard + cell_radius > 0.0 {
+                let rect = lattice_cell(&bbox, cell_dim, i, j);
+                if is_relevant(&rect, cell_dim, hazards) {
                     cells.push(rect);
                 }
             }
@@ -75,7 +74,29 @@ pub fn generate(bbox: AARectangle, hazards: &[Hazard], target_n_cells: usize) ->
         previous_attempt = Some(attempt);
         n_iters += 1;
     }
-    cells
+    (cells, cell_dim)
+}
+
+/// Number of columns and rows of a lattice of square cells covering the bounding box
+pub fn lattice_size(bbox: &AARectangle, cell_dim: fsize) -> (usize, usize) {
+    let n_cells_in_x = fsize::ceil(bbox.width() / cell_dim) as usize;
+    let n_cells_in_y = fsize::ceil(bbox.height() / cell_dim) as usize;
+    (n_cells_in_x, n_cells_in_y)
+}
+
+/// The cell in column `i` and row `j` of the lattice starting at the bottom-left corner of the bounding box
+pub fn lattice_cell(bbox: &AARectangle, cell_dim: fsize, i: usize, j: usize) -> AARectangle {
+    let x_min = bbox.x_min + cell_dim * i as fsize;
+    let x_max = x_min + cell_dim;
+    let y_min = bbox.y_min + cell_dim * j as fsize;
+    let y_max = y_min + cell_dim;
+    AARectangle::new(x_min, y_min, x_max, y_max)
+}
+
+/// A cell is relevant if it is not entirely inside any of the hazards
+pub fn is_relevant(cell: &AARectangle, cell_dim: fsize, hazards: &[Hazard]) -> bool {
+    let cell_radius = fsize::sqrt(2.0 * (cell_dim / 2.0).powi(2)); //half of the maximum distance between two cell centers
+    distance_to_hazard(&cell.centroid(), hazards.iter()) + cell_radius > 0.0
 }
 
 fn distance_to_hazard<'a, I>(point: &Point, hazards: I) -> fsize
@@ -1,5 +1,4 @@
 use std::fmt::{Display, Formatter};
-use std::iter;
 
 use itertools::Itertools;
 
@@ -22,18 +21,18 @@ pub struct HazardProximityGrid {
     pub bbox: AARectangle,
     pub grid: Grid<HPGCell>,
     pub cell_radius: fsize,
-    uncommitted_deregisters: Vec<HazardEntity>,
+    cell_dim: fsize,
+    uncommitted_deregisters: Vec<Hazard>,
 }
 
 impl HazardProximityGrid {
     pub fn new(bbox: AARectangle, static_hazards: &[Hazard], n_cells: usize) -> Self {
         assert!(n_cells > 0);
 
-        let cells = {
-            //all universal hazards are applicable for the grid generator
+        let (cells, cell_dim) = {
             let uni_hazards = static_hazards
                 .iter()
-                .filter(|h| h.entity.is_universal())
+                .filter(|h| shapes_grid(h))
                 .cloned()
                 .collect_vec();
             grid_generator::generate(bbox.clone(), &uni_hazards, n_cells)
@@ -57,6 +56,89 @@ impl HazardProximityGrid {
             grid,
             uncommitted_deregisters: vec![],
             cell_radius,
+            cell_dim,
+        }
+    }
+
+    /// Updates the grid after the shape of the [HazardEntity::BinExterior] hazard in `static_hazards` was modified.
+    /// Only cells in the vicinity of the old and new bin are revisited:
+    /// cells which are no longer relevant are removed, cells which have become relevant are added,
+    /// and cells whose proximity to the static hazards changed are recalculated.
+    pub fn update_bin_exterior<'a, I>(
+        &mut self,
+        old_bin_bbox: &AARectangle,
+        static_hazards: &[Hazard],
+        dynamic_hazards: I,
+    ) where
+        I: Iterator<Item = &'a Hazard> + Clone,
+    {
+        assert!(!self.is_dirty(), "grid should be flushed before updating");
+        let new_bin_bbox = static_hazards
+            .iter()
+            .find(|h| h.entity == HazardEntity::BinExterior)
+            .expect("no bin exterior hazard")
+            .shape
+            .bbox();
+        let uni_hazards = static_hazards
+            .iter()
+            .filter(|h| shapes_grid(h))
+            .cloned()
+            .collect_vec();
+
+        //Cells further than their radius outside both bins were and remain irrelevant.
+        //All others are located within the bounding box of both bins, inflated by a cell.
+        let region = AARectangle::bounding_rectangle(old_bin_bbox, &new_bin_bbox);
+        let (n_cells_in_x, n_cells_in_y) = grid_generator::lattice_size(&self.bbox, self.cell_dim);
+        let to_lattice_range = |min: fsize, max: fsize, n: usize| {
+            let start = (fsize::floor(min / self.cell_dim) - 1.0).max(0.0) as usize;
+            let end = (fsize::ceil(max / self.cell_dim) + 1.0).max(0.0) as usize;
+            start..usize::min(end, n)
+        };
+        let i_range = to_lattice_range(
+            region.x_min - self.bbox.x_min,
+            region.x_max - self.bbox.x_min,
+            n_cells_in_x,
+        );
+        let j_range = to_lattice_range(
+            region.y_min - self.bbox.y_min,
+            region.y_max - self.bbox.y_min,
+            n_cells_in_y,
+        );
+
+        let mut new_cells = vec![];
+        for i in i_range {
+            for j in j_range.clone() {
+                let rect = grid_generator::lattice_cell(&self.bbox, self.cell_dim, i, j);
+                let relevant = grid_generator::is_relevant(&rect, self.cell_dim, &uni_hazards);
+                let index = self.grid.index_of(rect.centroid());
+                match (index, relevant) {
+                    (Some(index), true) => {
+                        if let Some(cell) = self.grid.cells[index].as_mut() {
+                            let mut new_cell = HPGCell::new(rect, static_hazards);
+                            if new_cell.static_uni_prox != cell.static_uni_prox {
+                                new_cell.register_hazards(dynamic_hazards.clone());
+                                *cell = new_cell;
+                            }
+                        } else {
+                            let mut new_cell = HPGCell::new(rect, static_hazards);
+                            new_cell.register_hazards(dynamic_hazards.clone());
+                            self.grid.cells[index] = Some(new_cell);
+                        }
+                    }
+                    (Some(index), false) => self.grid.cells[index] = None,
+                    (None, true) => {
+                        //the cell lies outside the current rows and columns of the grid
+                        let mut new_cell = HPGCell::new(rect, static_hazards);
+                        new_cell.register_hazards(dynamic_hazards.clone());
+                        let pos = new_cell.centroid;
+                        new_cells.push((new_cell, pos));
+                    }
+                    (None, false) => {}
+                }
+            }
+        }
+        if !new_cells.is_empty() {
+            self.grid.extend(new_cells);
         }
     }
 
@@ -67,6 +149,13 @@ impl HazardProximityGrid {
     }
 
     pub fn register_hazard(&mut self, to_register: &Hazard) {
+        if to_register.entity.position() == GeoPosition::Exterior {
+            //exterior hazards can affect cells anywhere in the grid, no use in a boundary fill
+            self.grid.cells.iter_mut().flatten().for_each(|cell| {
+                cell.register_hazard(to_register);
+            });
+            return;
+        }
         let shape = &to_register.shape;
         let poles = &shape.surrogate().poles;
 
@@ -109,20 +198,94 @@ impl HazardProximityGrid {
         ));
     }
 
+    /// Registers a static hazard induced by a [HazardEntity::Custom] in the grid.
+    /// Only the static proximity of the cells is updated, the layout of the grid itself remains unchanged.
+    pub fn register_static_hazard(&mut self, to_register: &Hazard) {
+        debug_assert!(!shapes_grid(to_register));
+        if to_register.entity.position() == GeoPosition::Exterior {
+            self.grid.cells.iter_mut().flatten().for_each(|cell| {
+                cell.register_static_hazard(to_register);
+            });
+            return;
+        }
+
+        //The exact distance to the hazard is used, so a single boundary fill seeded around the entire hazard suffices
+        let mut b_fill =
+            BoundaryFillHPG::new(&self.grid, &self.seed_box(&to_register.shape.bbox()));
+        while let Some(next_cell) = b_fill.pop() {
+            let position_in_bf = match self.grid.cells[next_cell].as_mut() {
+                Some(cell) => match cell.register_static_hazard(to_register) {
+                    HPGCellUpdate::Affected | HPGCellUpdate::NotAffected => GeoPosition::Interior,
+                    HPGCellUpdate::NeighborsNotAffected => GeoPosition::Exterior,
+                },
+                None => GeoPosition::Exterior,
+            };
+            b_fill.report_position(next_cell, position_in_bf, &self.grid);
+        }
+    }
+
+    /// Deregisters a static hazard induced by a [HazardEntity::Custom] from the grid.
+    /// Only the cells for which it was the closest static hazard are recalculated, using the remaining `static_hazards`.
+    pub fn deregister_static_hazard<'a, I>(
+        &mut self,
+        to_deregister: &Hazard,
+        static_hazards: &[Hazard],
+        dynamic_hazards: I,
+    ) where
+        I: Iterator<Item = &'a Hazard> + Clone,
+    {
+        debug_assert!(!shapes_grid(to_deregister));
+        if to_deregister.entity.position() == GeoPosition::Exterior {
+            self.grid.cells.iter_mut().flatten().for_each(|cell| {
+                cell.deregister_static_hazard(
+                    to_deregister,
+                    static_hazards,
+                    dynamic_hazards.clone(),
+                );
+            });
+            return;
+        }
+
+        let mut b_fill =
+            BoundaryFillHPG::new(&self.grid, &self.seed_box(&to_deregister.shape.bbox()));
+        while let Some(next_cell) = b_fill.pop() {
+            let position_in_bf = match self.grid.cells[next_cell].as_mut() {
+                Some(cell) => match cell.deregister_static_hazard(
+                    to_deregister,
+                    static_hazards,
+                    dynamic_hazards.clone(),
+                ) {
+                    HPGCellUpdate::Affected | HPGCellUpdate::NotAffected => GeoPosition::Interior,
+                    HPGCellUpdate::NeighborsNotAffected => GeoPosition::Exterior,
+                },
+                None => GeoPosition::Exterior,
+            };
+            b_fill.report_position(next_cell, position_in_bf, &self.grid);
+        }
+    }
+
+    /// Region around `bbox` which is guaranteed to contain the centroid of at least one cell
+    fn seed_box(&self, bbox: &AARectangle) -> AARectangle {
+        AARectangle::new(
+            bbox.x_min - 2.0 * self.cell_radius,
+            bbox.y_min - 2.0 * self.cell_radius,
+            bbox.x_max + 2.0 * self.cell_radius,
+            bbox.y_max + 2.0 * self.cell_radius,
+        )
+    }
+
     pub fn deregister_hazard<'a, I>(
         &mut self,
-        to_deregister: HazardEntity,
+        to_deregister: &Hazard,
         remaining: I,
         process_now: bool,
     ) where
         I: Iterator<Item = &'a Hazard> + Clone,
     {
         if process_now {
-            for cell in self.grid.cells.iter_mut().flatten() {
-                cell.deregister_hazards(iter::once(to_deregister), remaining.clone());
-            }
+            self.process_deregister(to_deregister, remaining);
         } else {
-            self.uncommitted_deregisters.push(to_deregister);
+            self.uncommitted_deregisters.push(to_deregister.clone());
         }
     }
 
@@ -130,15 +293,72 @@ impl HazardProximityGrid {
     where
         I: Iterator<Item = &'a Hazard> + Clone,
     {
-        if self.is_dirty() {
-            //deregister all pending hazards at once
-            let to_deregister = self.uncommitted_deregisters.iter().cloned();
-            for cell in self.grid.cells.iter_mut().flatten() {
-                cell.deregister_hazards(to_deregister.clone(), remaining.clone());
+        let to_deregister = std::mem::take(&mut self.uncommitted_deregisters);
+        for hazard in to_deregister.iter() {
+            //hazards which have been registered again in the meantime are left untouched
+            let registered_again = remaining.clone().any(|h| h.entity == hazard.entity);
+            if !registered_again {
+                self.process_deregister(hazard, remaining.clone());
             }
+        }
+    }
+
+    fn process_deregister<'a, I>(&mut self, to_deregister: &Hazard, remaining: I)
+    where
+        I: Iterator<Item = &'a Hazard> + Clone,
+    {
+        if to_deregister.entity.position() == GeoPosition::Exterior {
+            self.grid.cells.iter_mut().flatten().for_each(|cell| {
+                cell.deregister_hazard(to_deregister, remaining.clone());
+            });
+            return;
+        }
+        let poles = &to_deregister.shape.surrogate().poles;
+
+        //Only the cells for which the hazard was the closest are affected.
+        //These are located with a boundary fill algorithm, starting from each pole, similar to registration.
+        let mut b_fill = BoundaryFillHPG::new(&self.grid, &to_deregister.shape.bbox());
+        //cells inside the boundary of a previous fill, poles within it do not need to be filled again
+        let mut inside_boundary = vec![false; self.grid.cells.len()];
+
+        for pole in poles {
+            let center_row = self.grid.rows_in_range(pole.center.1..=pole.center.1);
+            let center_col = self.grid.cols_in_range(pole.center.0..=pole.center.0);
+            let center_cell = self.grid.to_index(*center_row.start(), *center_col.start());
+            if center_cell.is_ok_and(|i| inside_boundary[i]) {
+                continue;
+            }
+
+            let seed_box = AARectangle::new(
+                pole.bbox().x_min - 2.0 * self.cell_radius,
+                pole.bbox().y_min - 2.0 * self.cell_radius,
+                pole.bbox().x_max + 2.0 * self.cell_radius,
+                pole.bbox().y_max + 2.0 * self.cell_radius,
+            );
+
+            b_fill = b_fill.reset(&self.grid, &seed_box);
 
-            self.uncommitted_deregisters.clear();
+            while let Some(next_cell) = b_fill.pop() {
+                let position_in_bf = match self.grid.cells[next_cell].as_mut() {
+                    Some(cell) => match cell.deregister_hazard(to_deregister, remaining.clone()) {
+                        //Cell was directly affected, inside the boundary
+                        HPGCellUpdate::Affected => GeoPosition::Interior,
+                        //Cell was not affected, but its neighbors might be, so it is considered inside the boundary
+                        HPGCellUpdate::NotAffected => GeoPosition::Interior,
+                        //Cell was not affected and its neighbors are not affected, so it is considered outside the boundary
+                        HPGCellUpdate::NeighborsNotAffected => GeoPosition::Exterior,
+                    },
+                    //cell does not exist, mark as exterior
+                    None => GeoPosition::Exterior,
+                };
+                inside_boundary[next_cell] |= position_in_bf == GeoPosition::Interior;
+                b_fill.report_position(next_cell, position_in_bf, &self.grid);
+            }
         }
+        debug_assert!(assertions::hpg_deregister_no_affected_cells_remain(
+            to_deregister,
+            self
+        ));
     }
 
     pub fn is_dirty(&self) -> bool {
@@ -146,6 +366,12 @@ impl HazardProximityGrid {
     }
 }
 
+/// Whether the hazard determines which cells make up the grid.
+/// Custom hazards do not, so they can be registered and deregistered at runtime without regenerating the grid.
+fn shapes_grid(hazard: &Hazard) -> bool {
+    hazard.entity.is_universal() && !matches!(hazard.entity, HazardEntity::Custom { .. })
+}
+
 /// Error type for when the `HazardProximityGrid` is in a dirty state.
 /// This can happen when the grid is accessed after a hazard has been deregistered but with "process_now" set to false.
 /// The grid should be flushed to ensure all changes are processed.
@@ -1,6 +1,5 @@
 use std::cmp::Ordering;
 
-use itertools::Itertools;
 use ordered_float::NotNan;
 
 use crate::collision_detection::hazard::Hazard;
@@ -45,7 +44,10 @@ impl HPGCell {
                 false => distance,
             };
             match &hazard.entity {
-                HazardEntity::BinExterior | HazardEntity::BinHole { .. } => {
+                HazardEntity::BinExterior
+                | HazardEntity::BinHole { .. }
+                | HazardEntity::FixedItem { .. }
+                | HazardEntity::Custom { .. } => {
                     if prox < static_uni_prox.0 {
                         static_uni_prox = (prox, hazard.entity);
                     }
@@ -132,9 +134,8 @@ impl HPGCell {
             GeoPosition::Interior => {
                 distance_to_surrogate_poles_border(self, &to_register.shape.surrogate().poles)
             }
-            GeoPosition::Exterior => {
-                panic!("No implementation yet for dynamic exterior hazards")
-            }
+            //Exterior hazards have no meaningful poles, the exact distance is used instead
+            GeoPosition::Exterior => distance_to_hazard_border(self, to_register),
         };
 
         match haz_prox.partial_cmp(&current_prox).unwrap() {
@@ -195,23 +196,115 @@ impl HPGCell {
         }
     }
 
-    pub fn deregister_hazards<'a, I, J>(
+    /// Deregisters a hazard from the cell. If it was the closest hazard, the proximity is recalculated from the remaining hazards.
+    /// Otherwise, the hazard might still have been the closest one for any of the neighbors.
+    pub fn deregister_hazard<'a, I>(
         &mut self,
-        mut to_deregister: J,
+        to_deregister: &Hazard,
         remaining: I,
     ) -> HPGCellUpdate
     where
         I: Iterator<Item = &'a Hazard>,
-        J: Iterator<Item = HazardEntity>,
     {
-        if to_deregister.contains(&self.uni_prox.1) {
+        let current_prox = self.uni_prox.0;
+
+        if self.uni_prox.1 == to_deregister.entity {
             //closest current hazard has to be deregistered
             self.uni_prox = self.static_uni_prox;
 
             self.register_hazards(remaining);
             HPGCellUpdate::Affected
         } else {
-            HPGCellUpdate::NotAffected
+            //maximum distance between neighboring cells
+            let max_neighbor_distance = 2.0 * self.radius;
+            let current_prox_upper_bound = current_prox + max_neighbor_distance;
+
+            //the current hazard is closer for all neighbors, the deregistered one cannot have been the closest for any of them
+            let neighbors_not_affected =
+                |haz_prox: fsize| haz_prox - max_neighbor_distance > current_prox_upper_bound;
+
+            if to_deregister.entity.position() == GeoPosition::Exterior {
+                return match neighbors_not_affected(distance_to_hazard_border(self, to_deregister))
+                {
+                    true => HPGCellUpdate::NeighborsNotAffected,
+                    false => HPGCellUpdate::NotAffected,
+                };
+            }
+
+            //the bounding circle of the poles serves as a cheap lower bound for the proximity of the hazard
+            let surrogate = to_deregister.shape.surrogate();
+            let bounding_prox = match surrogate
+                .poles_bounding_circle
+                .distance_from_border(&self.centroid)
+            {
+                (GeoPosition::Interior, _) => 0.0,
+                (GeoPosition::Exterior, dist) => dist.abs(),
+            };
+
+            if neighbors_not_affected(bounding_prox)
+                || neighbors_not_affected(distance_to_surrogate_poles_border(
+                    self,
+                    &surrogate.poles,
+                ))
+            {
+                HPGCellUpdate::NeighborsNotAffected
+            } else {
+                //the hazard might have been the closest for one of the neighbors
+                HPGCellUpdate::NotAffected
+            }
+        }
+    }
+
+    /// Registers a static hazard in the cell, using the exact distance to the hazard like [`HPGCell::new`] does.
+    pub fn register_static_hazard(&mut self, to_register: &Hazard) -> HPGCellUpdate {
+        let current_static_prox = self.static_uni_prox.0;
+        let haz_prox = distance_to_hazard_border(self, to_register);
+
+        if haz_prox < current_static_prox {
+            //new hazard is the closest static one, and possibly the closest overall
+            self.static_uni_prox = (haz_prox, to_register.entity);
+            if haz_prox < self.uni_prox.0 {
+                self.uni_prox = self.static_uni_prox;
+            }
+            HPGCellUpdate::Affected
+        } else {
+            //maximum distance between neighboring cells
+            let max_neighbor_distance = 2.0 * self.radius;
+            match haz_prox - max_neighbor_distance > current_static_prox + max_neighbor_distance {
+                true => HPGCellUpdate::NeighborsNotAffected,
+                false => HPGCellUpdate::NotAffected,
+            }
+        }
+    }
+
+    /// Deregisters a static hazard from the cell. If it was the closest static hazard, the static proximity is recalculated from `static_hazards`.
+    /// If it was also the closest hazard overall, the `dynamic_hazards` are registered again.
+    pub fn deregister_static_hazard<'a, I>(
+        &mut self,
+        to_deregister: &Hazard,
+        static_hazards: &[Hazard],
+        dynamic_hazards: I,
+    ) -> HPGCellUpdate
+    where
+        I: Iterator<Item = &'a Hazard>,
+    {
+        if self.static_uni_prox.1 == to_deregister.entity {
+            self.static_uni_prox = HPGCell::new(self.bbox.clone(), static_hazards).static_uni_prox;
+            if self.uni_prox.1 == to_deregister.entity {
+                self.uni_prox = self.static_uni_prox;
+                self.register_hazards(dynamic_hazards);
+            }
+            HPGCellUpdate::Affected
+        } else {
+            //maximum distance between neighboring cells
+            let max_neighbor_distance = 2.0 * self.radius;
+            let haz_prox = distance_to_hazard_border(self, to_deregister);
+            match haz_prox - max_neighbor_distance > self.static_uni_prox.0 + max_neighbor_distance
+            {
+                //the hazard cannot have been the closest static hazard for any of the neighbors
+                true => HPGCellUpdate::NeighborsNotAffected,
+                false => HPGCellUpdate::NotAffected,
+            }
         }
     }
 
@@ -255,6 +348,15 @@ pub fn distance_to_surrogate_poles_border(hp_cell: &HPGCell, poles: &[Circle]) -
         .unwrap()
 }
 
+/// Exact distance from the centroid of the cell to the border of the hazard, zero if the centroid lies within the hazard
+pub fn distance_to_hazard_border(hp_cell: &HPGCell, hazard: &Hazard) -> fsize {
+    let (pos, distance) = hazard.shape.distance_from_border(&hp_cell.centroid);
+    match pos == hazard.entity.position() {
+        true => 0.0,
+        false => distance,
+    }
+}
+
 ///All possible results of an update on a cell in the `HazardProximityGrid`
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum HPGCellUpdate {
@@ -1,8 +1,12 @@
 /// Collision detection engine itself
 pub mod cd_engine;
+/// Statistics on the queries resolved by the collision detection engine
+#[cfg(feature = "cde-stats")]
+pub mod cde_stats;
 pub mod hazard;
 pub mod hazard_filter;
 
 /// Everything related to the Hazard Proximity Grid
 pub mod hpg;
 pub mod quadtree;
+mod sweep;
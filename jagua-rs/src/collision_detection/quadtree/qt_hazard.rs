@@ -82,7 +82,7 @@ impl QTHazard {
                     //Add the relevant edges to the presences in the quadrants
                     match &partial_haz.edges {
                         RelevantEdges::All => {
-                            for edge_i in 0..shape.number_of_points() {
+                            for edge_i in 0..shape.number_of_border_edges() {
                                 q_presences = Self::add_edge_to_q_presences(
                                     edge_i,
                                     &shape,
@@ -149,7 +149,7 @@ impl QTHazard {
         quadrants: [&AARectangle; 4],
         mut q_presences: [Option<QTHazPresence>; 4],
     ) -> [Option<QTHazPresence>; 4] {
-        let edge = shape.get_edge(edge_index);
+        let edge = shape.get_border_edge(edge_index);
         //check for which quadrants the edge is relevant
         for (q_index, quad) in quadrants.iter().enumerate() {
             if quad.collides_with(&edge) {
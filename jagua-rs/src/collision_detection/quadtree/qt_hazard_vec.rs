@@ -8,6 +8,8 @@ use crate::collision_detection::quadtree::qt_hazard::QTHazard;
 /// <br>
 /// This is a performance optimization to be able to quickly return the "strongest" hazard
 /// Strongest meaning the first active hazard with the highest presence (`Entire` > `Partial` > `None`)
+/// <br>
+/// Entities consisting of multiple components can be present multiple times, once per component.
 #[derive(Clone, Debug)]
 pub struct QTHazardVec {
     hazards: Vec<QTHazard>,
@@ -23,15 +25,6 @@ impl QTHazardVec {
     }
 
     pub fn add(&mut self, haz: QTHazard) {
-        debug_assert!(
-            self.hazards
-                .iter()
-                .filter(|other| other.entity == haz.entity
-                    && matches!(haz.entity, HazardEntity::PlacedItem(_)))
-                .count()
-                == 0,
-            "More than one hazard from same item entity in the vector! (This should never happen!)"
-        );
         match self
             .hazards
             .binary_search_by(|probe| order_by_descending_strength(probe, &haz))
@@ -43,25 +36,17 @@ impl QTHazardVec {
         }
     }
 
-    pub fn remove(&mut self, haz_entity: HazardEntity) -> Option<QTHazard> {
-        debug_assert!(
-            self.hazards
-                .iter()
-                .filter(|ch| ch.entity == haz_entity
-                    && matches!(ch.entity, HazardEntity::PlacedItem(_)))
-                .count()
-                <= 1,
-            "More than one hazard from same item entity in the vector! (This should never happen!)"
-        );
-        let pos = self.hazards.iter().position(|ch| ch.entity == haz_entity);
-        match pos {
-            Some(pos) => {
-                let haz = self.hazards.remove(pos);
-                self.n_active -= haz.active as usize;
-                Some(haz)
-            }
-            None => None,
-        }
+    /// Removes all hazards of the entity (one per component), returns whether any were present
+    pub fn remove(&mut self, haz_entity: HazardEntity) -> bool {
+        let n_before = self.hazards.len();
+        let n_active_removed = self
+            .hazards
+            .iter()
+            .filter(|hz| hz.entity == haz_entity && hz.active)
+            .count();
+        self.hazards.retain(|hz| hz.entity != haz_entity);
+        self.n_active -= n_active_removed;
+        self.hazards.len() != n_before
     }
 
     #[inline(always)]
@@ -90,6 +75,7 @@ impl QTHazardVec {
         }
     }
 
+    /// Returns the strongest active hazard of the entity (if any)
     pub fn get(&self, entity: HazardEntity) -> Option<&QTHazard> {
         self.hazards
             .iter()
@@ -97,31 +83,32 @@ impl QTHazardVec {
             .find(|hz| hz.entity == entity)
     }
 
+    /// Activates all hazards of the entity, returns whether any were modified
     pub fn activate_hazard(&mut self, entity: HazardEntity) -> bool {
-        match self.hazards.iter_mut().position(|hz| hz.entity == entity) {
-            Some(index) => {
-                let mut hazard = self.hazards.remove(index);
-                debug_assert!(!hazard.active);
-                hazard.active = true;
-                self.add(hazard);
-                true
-            }
-            None => false,
-        }
+        self.set_activation(entity, true)
     }
 
+    /// Deactivates all hazards of the entity, returns whether any were modified
     pub fn deactivate_hazard(&mut self, entity: HazardEntity) -> bool {
-        match self.hazards.iter_mut().position(|hz| hz.entity == entity) {
-            Some(index) => {
-                let mut hazard = self.hazards.remove(index);
-                debug_assert!(hazard.active);
-                hazard.active = false;
+        self.set_activation(entity, false)
+    }
+
+    fn set_activation(&mut self, entity: HazardEntity, active: bool) -> bool {
+        let mut modified = false;
+        while let Some(index) = self
+            .hazards
+            .iter()
+            .position(|hz| hz.entity == entity && hz.active != active)
+        {
+            let mut hazard = self.hazards.remove(index);
+            if hazard.active {
                 self.n_active -= 1;
-                self.add(hazard);
-                true
             }
-            None => false,
+            hazard.active = active;
+            self.add(hazard);
+            modified = true;
         }
+        modified
     }
 
     pub fn active_hazards(&self) -> &[QTHazard] {
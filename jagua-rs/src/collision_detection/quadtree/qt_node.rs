@@ -5,8 +5,10 @@ use crate::collision_detection::quadtree::qt_hazard::QTHazPresence;
 use crate::collision_detection::quadtree::qt_hazard::QTHazard;
 use crate::collision_detection::quadtree::qt_hazard_vec::QTHazardVec;
 use crate::collision_detection::quadtree::qt_traits::QTQueryable;
-use crate::geometry::geo_traits::CollidesWith;
+use crate::geometry::edge_bvh::{EdgeBVH, EdgeBVHNode};
+use crate::geometry::geo_traits::{CollidesWith, Shape};
 use crate::geometry::primitives::aa_rectangle::AARectangle;
+use crate::geometry::primitives::simple_polygon::SimplePolygon;
 
 /// A node in the quadtree
 #[derive(Clone, Debug)]
@@ -62,9 +64,9 @@ impl QTNode {
     }
 
     pub fn deregister_hazard(&mut self, hazard_entity: HazardEntity) {
-        let removed_ch = self.hazards.remove(hazard_entity);
+        let removed = self.hazards.remove(hazard_entity);
 
-        if removed_ch.is_some() && self.has_children() {
+        if removed && self.has_children() {
             if self.hazards.is_empty() || self.hazards.has_only_entire_hazards() {
                 //If there are no hazards, or only entire hazards, drop the children
                 self.children = None;
@@ -130,7 +132,23 @@ impl QTNode {
         &self,
         entity: &T,
         irrelevant_hazards: &[HazardEntity],
+        robust: bool,
     ) -> Option<&HazardEntity>
+    where
+        T: QTQueryable,
+    {
+        self.collides_hazard(entity, irrelevant_hazards, robust)
+            .map(|hz| &hz.entity)
+    }
+
+    /// Same as [`Self::collides`], but returns the [QTHazard] through which the collision was detected.
+    /// Its presence reveals whether the collision was resolved by a node entirely covered by the hazard or by edge intersection.
+    pub fn collides_hazard<T>(
+        &self,
+        entity: &T,
+        irrelevant_hazards: &[HazardEntity],
+        robust: bool,
+    ) -> Option<&QTHazard>
     where
         T: QTQueryable,
     {
@@ -140,13 +158,15 @@ impl QTNode {
                 false => None,
                 true => match strongest_hazard.presence {
                     QTHazPresence::None => None,
-                    QTHazPresence::Entire => Some(&strongest_hazard.entity),
+                    QTHazPresence::Entire => Some(strongest_hazard),
                     QTHazPresence::Partial(_) => match &self.children {
                         Some(children) => {
                             //Check if any of the children intersect with the entity
                             children
                                 .iter()
-                                .map(|child| child.collides(entity, irrelevant_hazards))
+                                .map(|child| {
+                                    child.collides_hazard(entity, irrelevant_hazards, robust)
+                                })
                                 .find(|x| x.is_some())
                                 .flatten()
                         }
@@ -158,15 +178,15 @@ impl QTNode {
                                 .iter()
                                 .filter(|hz| !irrelevant_hazards.contains(&hz.entity));
 
-                            relevant_hazards
-                                .find(|hz| match &hz.presence {
-                                    QTHazPresence::None => false,
-                                    QTHazPresence::Entire => {
-                                        unreachable!("should have been handled above")
-                                    }
-                                    QTHazPresence::Partial(p_haz) => p_haz.collides_with(entity),
-                                })
-                                .map(|hz| &hz.entity)
+                            relevant_hazards.find(|hz| match &hz.presence {
+                                QTHazPresence::None => false,
+                                QTHazPresence::Entire => {
+                                    unreachable!("should have been handled above")
+                                }
+                                QTHazPresence::Partial(p_haz) => {
+                                    p_haz.collides_with(entity, robust)
+                                }
+                            })
                         }
                     },
                 },
@@ -174,9 +194,55 @@ impl QTNode {
         }
     }
 
+    /// Same as [`Self::collides_hazard`], but for all edges in a chain of an [EdgeBVH] of `shape` at once.
+    /// Chains which are disjoint from a node are rejected entirely, without testing their individual edges.
+    /// Both trees are descended simultaneously, always splitting the larger of the node and the chain.
+    pub fn collides_hazard_edge_chain(
+        &self,
+        shape: &SimplePolygon,
+        edge_bvh: &EdgeBVH,
+        chain: &EdgeBVHNode,
+        irrelevant_hazards: &[HazardEntity],
+        robust: bool,
+    ) -> Option<&QTHazard> {
+        if !chain.bbox.collides_with(&self.bbox) {
+            return None;
+        }
+        let strongest_hazard = self.hazards.strongest(irrelevant_hazards)?;
+        match (&strongest_hazard.presence, &self.children, chain.children) {
+            (QTHazPresence::None, _, _) => None,
+            (_, _, None) => chain.edges.clone().find_map(|i| {
+                self.collides_hazard(&shape.get_border_edge(i), irrelevant_hazards, robust)
+            }),
+            (QTHazPresence::Partial(_), Some(children), Some(_))
+                if chain.bbox.area() <= self.bbox.area() =>
+            {
+                children.iter().find_map(|child| {
+                    child.collides_hazard_edge_chain(
+                        shape,
+                        edge_bvh,
+                        chain,
+                        irrelevant_hazards,
+                        robust,
+                    )
+                })
+            }
+            (_, _, Some(sub_chains)) => sub_chains.iter().find_map(|&c| {
+                let sub_chain = &edge_bvh.nodes[c];
+                self.collides_hazard_edge_chain(
+                    shape,
+                    edge_bvh,
+                    sub_chain,
+                    irrelevant_hazards,
+                    robust,
+                )
+            }),
+        }
+    }
+
     /// Gathers all hazards that collide with the entity and stores them in the `detected` vector.
     /// All hazards already present in the `detected` vector are ignored.
-    pub fn collect_collisions<T>(&self, entity: &T, detected: &mut Vec<HazardEntity>)
+    pub fn collect_collisions<T>(&self, entity: &T, detected: &mut Vec<HazardEntity>, robust: bool)
     where
         T: QTQueryable,
     {
@@ -190,9 +256,9 @@ impl QTNode {
                     QTHazPresence::Partial(_) => match &self.children {
                         Some(children) => {
                             //Check if any of the children intersect with the entity
-                            children
-                                .iter()
-                                .for_each(|child| child.collect_collisions(entity, detected))
+                            children.iter().for_each(|child| {
+                                child.collect_collisions(entity, detected, robust)
+                            })
                         }
                         None => {
                             //Check if any of the partially present (and active) hazards collide with the entity
@@ -204,7 +270,7 @@ impl QTNode {
                                             unreachable!("should have been handled above")
                                         }
                                         QTHazPresence::Partial(p_haz) => {
-                                            if p_haz.collides_with(entity) {
+                                            if p_haz.collides_with(entity, robust) {
                                                 detected.push(hz.entity);
                                             }
                                         }
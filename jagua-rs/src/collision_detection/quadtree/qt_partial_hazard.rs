@@ -4,7 +4,7 @@ use std::sync::{Arc, Weak};
 
 use crate::collision_detection::hazard::Hazard;
 use crate::collision_detection::quadtree::qt_traits::QTQueryable;
-use crate::geometry::geo_traits::{CollidesWith, Shape};
+use crate::geometry::geo_traits::Shape;
 use crate::geometry::primitives::simple_polygon::SimplePolygon;
 
 /// Defines a set of edges from a hazard that is partially active in the [QTNode](crate::collision_detection::quadtree::qt_node::QTNode).
@@ -59,29 +59,30 @@ const BBOX_CHECK_THRESHOLD: usize = 10;
 
 const BBOX_CHECK_THRESHOLD_MINUS_1: usize = BBOX_CHECK_THRESHOLD - 1;
 
-impl<T> CollidesWith<T> for PartialQTHaz
-where
-    T: QTQueryable,
-{
-    fn collides_with(&self, entity: &T) -> bool {
+impl PartialQTHaz {
+    /// Checks whether the entity collides with any of the relevant edges of the hazard.
+    /// `robust` selects exact [predicates](crate::util::predicates) for the edge intersection tests.
+    pub fn collides_with<T: QTQueryable>(&self, entity: &T, robust: bool) -> bool {
         let shape = self.shape_arc();
         match &self.edges {
             RelevantEdges::All => match entity.collides_with(&shape.bbox()) {
                 false => false,
-                true => shape.edge_iter().any(|e| entity.collides_with(&e)),
+                true => shape
+                    .border_edge_iter()
+                    .any(|e| entity.collides_with_edge(&e, robust)),
             },
             RelevantEdges::Some(indices) => match indices.len() {
                 0 => unreachable!("edge indices should not be empty"),
                 1..=BBOX_CHECK_THRESHOLD_MINUS_1 => indices
                     .iter()
-                    .any(|&i| entity.collides_with(&shape.get_edge(i))),
+                    .any(|&i| entity.collides_with_edge(&shape.get_border_edge(i), robust)),
                 BBOX_CHECK_THRESHOLD.. => {
                     if !entity.collides_with(&shape.bbox()) {
                         return false;
                     }
                     indices
                         .iter()
-                        .any(|&i| entity.collides_with(&shape.get_edge(i)))
+                        .any(|&i| entity.collides_with_edge(&shape.get_border_edge(i), robust))
                 }
             },
         }
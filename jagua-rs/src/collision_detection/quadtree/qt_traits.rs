@@ -6,8 +6,18 @@ use crate::geometry::primitives::edge::Edge;
 /// Common trait for all geometric primitives that can be directly queried in the quadtree
 /// for collisions with the edges of the registered hazards.
 /// These include: [AARectangle], [Edge] and [Circle].
-pub trait QTQueryable: Shape + CollidesWith<Edge> + CollidesWith<AARectangle> {}
+pub trait QTQueryable: Shape + CollidesWith<Edge> + CollidesWith<AARectangle> {
+    /// Checks whether the entity collides with an edge of a hazard.
+    /// `robust` selects exact [predicates](crate::util::predicates) where the entity supports them.
+    fn collides_with_edge(&self, edge: &Edge, _robust: bool) -> bool {
+        self.collides_with(edge)
+    }
+}
 
 impl QTQueryable for AARectangle {}
-impl QTQueryable for Edge {}
+impl QTQueryable for Edge {
+    fn collides_with_edge(&self, edge: &Edge, robust: bool) -> bool {
+        self.intersects(edge, robust)
+    }
+}
 impl QTQueryable for Circle {}
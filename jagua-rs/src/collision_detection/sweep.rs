@@ -0,0 +1,175 @@
+use std::cmp::Ordering;
+
+use crate::collision_detection::hazard::HazardEntity;
+use crate::collision_detection::quadtree::qt_hazard::QTHazPresence;
+use crate::collision_detection::quadtree::qt_node::QTNode;
+use crate::collision_detection::quadtree::qt_partial_hazard::RelevantEdges;
+use crate::fsize;
+use crate::geometry::geo_enums::GeoPosition;
+use crate::geometry::geo_traits::{CollidesWith, Shape};
+use crate::geometry::primitives::aa_rectangle::AARectangle;
+use crate::geometry::primitives::edge::Edge;
+use crate::geometry::primitives::point::Point;
+use crate::geometry::primitives::simple_polygon::SimplePolygon;
+
+/// Sweeps `shape` along the unit vector `direction` through the quadtree.
+/// `closest` holds the furthest distance the shape can currently travel and the hazard blocking it.
+/// Only the hazard edges in nodes which intersect the region swept over this distance are checked,
+/// so every blocking edge found further narrows down the search.
+pub fn sweep_quadtree(
+    node: &QTNode,
+    shape: &SimplePolygon,
+    direction: (fsize, fsize),
+    irrelevant_hazards: &[HazardEntity],
+    closest: &mut (fsize, Option<HazardEntity>),
+) {
+    let strongest = match node.hazards.strongest(irrelevant_hazards) {
+        None => return,
+        Some(strongest) => strongest,
+    };
+    if !swept_bbox(&shape.bbox, direction, closest.0).collides_with(&node.bbox) {
+        return;
+    }
+    match (&strongest.presence, &node.children) {
+        //the shape cannot reach the inside of a hazard without first crossing its edges, which reside in other nodes
+        (QTHazPresence::None, _) | (QTHazPresence::Entire, _) => (),
+        (QTHazPresence::Partial(_), Some(children)) => {
+            //visit the children in the order the shape encounters them, to narrow down the search as soon as possible
+            let progress = |n: &QTNode| {
+                let Point(x, y) = n.bbox.centroid();
+                x * direction.0 + y * direction.1
+            };
+            let mut order = [0, 1, 2, 3];
+            order.sort_by(|&a, &b| {
+                progress(&children[a])
+                    .partial_cmp(&progress(&children[b]))
+                    .unwrap_or(Ordering::Equal)
+            });
+            for i in order {
+                sweep_quadtree(&children[i], shape, direction, irrelevant_hazards, closest);
+            }
+        }
+        (QTHazPresence::Partial(_), None) => {
+            let relevant_hazards = node
+                .hazards
+                .active_hazards()
+                .iter()
+                .filter(|hz| !irrelevant_hazards.contains(&hz.entity));
+
+            for hz in relevant_hazards {
+                if let QTHazPresence::Partial(p_haz) = &hz.presence {
+                    let haz_shape = p_haz.shape_arc();
+                    let mut check_edge = |i: usize| {
+                        //the hazard lies on the other side of the edges of its holes
+                        let haz_position = match haz_shape.is_hole_edge(i) {
+                            false => hz.entity.position(),
+                            true => flip(hz.entity.position()),
+                        };
+                        let edge = haz_shape.get_border_edge(i);
+                        let distance = edge_travel_distance(shape, &edge, haz_position, direction);
+                        if distance < closest.0 {
+                            *closest = (distance, Some(hz.entity));
+                        }
+                    };
+                    match &p_haz.edges {
+                        RelevantEdges::All => {
+                            (0..haz_shape.number_of_border_edges()).for_each(&mut check_edge)
+                        }
+                        RelevantEdges::Some(indices) => indices.iter().for_each(|&i| check_edge(i)),
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Bounding box of the region swept by `bbox` when translated `distance` along `direction`
+pub fn swept_bbox(bbox: &AARectangle, direction: (fsize, fsize), distance: fsize) -> AARectangle {
+    let (dx, dy) = (direction.0 * distance, direction.1 * distance);
+    AARectangle::new(
+        bbox.x_min + fsize::min(dx, 0.0),
+        bbox.y_min + fsize::min(dy, 0.0),
+        bbox.x_max + fsize::max(dx, 0.0),
+        bbox.y_max + fsize::max(dy, 0.0),
+    )
+}
+
+/// Distance `shape` can travel along `direction` before running into `edge` of a hazard.
+/// Contacts where the shape slides along or moves away from the hazard do not block the shape.
+/// Returns [fsize::INFINITY] if the edge never blocks the shape.
+fn edge_travel_distance(
+    shape: &SimplePolygon,
+    edge: &Edge,
+    haz_position: GeoPosition,
+    direction: (fsize, fsize),
+) -> fsize {
+    let mut distance = fsize::INFINITY;
+
+    //vertices of the shape moving into the hazard through the edge
+    //(the interior of a hazard lies to the left of its counterclockwise edges)
+    let towards_left = cross(edge_vector(edge), direction);
+    let enters_hazard = match haz_position {
+        GeoPosition::Interior => towards_left > 0.0,
+        GeoPosition::Exterior => towards_left < 0.0,
+    };
+    if enters_hazard {
+        let hole_points = shape.holes.iter().flat_map(|h| h.points.iter());
+        for p in shape.points.iter().chain(hole_points) {
+            if let Some(d) = ray_edge_distance(*p, direction, edge) {
+                distance = fsize::min(distance, d);
+            }
+        }
+    }
+
+    //vertices of the edge moving into the shape (relative to the shape they move in the opposite direction)
+    let reverse = (-direction.0, -direction.1);
+    for (i, s_edge) in shape.border_edge_iter().enumerate() {
+        //the interior of the shape lies to the left of its outer edges, but to the right of the edges of its holes
+        let towards_left = cross(edge_vector(&s_edge), reverse);
+        let enters_shape = match shape.is_hole_edge(i) {
+            false => towards_left > 0.0,
+            true => towards_left < 0.0,
+        };
+        if enters_shape {
+            for q in [edge.start, edge.end] {
+                if let Some(d) = ray_edge_distance(q, reverse, &s_edge) {
+                    distance = fsize::min(distance, d);
+                }
+            }
+        }
+    }
+
+    distance
+}
+
+/// Distance along the ray from `origin` in `direction` to the point where it hits `edge`, if it does so.
+/// Rays parallel to the edge are considered not to hit it.
+fn ray_edge_distance(origin: Point, direction: (fsize, fsize), edge: &Edge) -> Option<fsize> {
+    let e = edge_vector(edge);
+    let denom = cross(direction, e);
+    if denom == 0.0 {
+        return None;
+    }
+    let w = (edge.start.0 - origin.0, edge.start.1 - origin.1);
+    let t = cross(w, e) / denom;
+    let s = cross(w, direction) / denom;
+    match t >= 0.0 && (0.0..=1.0).contains(&s) {
+        true => Some(t),
+        false => None,
+    }
+}
+
+fn flip(position: GeoPosition) -> GeoPosition {
+    match position {
+        GeoPosition::Interior => GeoPosition::Exterior,
+        GeoPosition::Exterior => GeoPosition::Interior,
+    }
+}
+
+fn edge_vector(edge: &Edge) -> (fsize, fsize) {
+    (edge.end.0 - edge.start.0, edge.end.1 - edge.start.1)
+}
+
+fn cross(a: (fsize, fsize), b: (fsize, fsize)) -> fsize {
+    a.0 * b.1 - a.1 * b.0
+}
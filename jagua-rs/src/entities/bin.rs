@@ -5,29 +5,39 @@ use itertools::Itertools;
 use crate::collision_detection::cd_engine::CDEngine;
 use crate::collision_detection::hazard::Hazard;
 use crate::collision_detection::hazard::HazardEntity;
+use crate::entities::placed_item::PlacedItem;
 use crate::entities::quality_zone::InferiorQualityZone;
 use crate::entities::quality_zone::N_QUALITIES;
 use crate::fsize;
+use crate::geometry::geo_enums::GeoRelation;
 use crate::geometry::geo_traits::Shape;
 use crate::geometry::primitives::aa_rectangle::AARectangle;
 use crate::geometry::primitives::simple_polygon::SimplePolygon;
 use crate::geometry::transformation::Transformation;
 use crate::util::config::CDEConfig;
 
+/// Horizontal headroom of the `CDEngine` of a strip on either side, relative to the width of the strip
+pub const STRIP_CDE_HEADROOM: fsize = 1.0;
+
 /// A container in which items can be placed.
 #[derive(Clone, Debug)]
 pub struct Bin {
     pub id: usize,
-    /// The contour of the bin
+    /// The contour of the bin.
+    /// For bins consisting of multiple disjoint sheets, this is a rectangle enclosing all of them.
     pub outer: Arc<SimplePolygon>,
     /// The cost of using the bin
     pub value: u64,
     /// Transformation applied to the shape with respect to the original shape in the input file (for example to center it).
     pub pretransform: Transformation,
-    /// Shapes of holes/defects in the bins, if any
+    /// Shapes of holes/defects in the bins, if any.
+    /// For bins consisting of multiple disjoint sheets, the space between the sheets is also modeled as holes.
     pub holes: Vec<Arc<SimplePolygon>>,
     /// Zones of different qualities in the bin, stored per quality.
     pub quality_zones: [Option<InferiorQualityZone>; N_QUALITIES],
+    /// Items which are fixed in place in the bin, such as parts already cut from the sheet.
+    /// They count toward the demand of their item and can never be removed.
+    pub fixed_items: Vec<PlacedItem>,
     /// The starting state of the `CDEngine` for this bin.
     pub base_cde: Arc<CDEngine>,
     pub area: fsize,
@@ -79,21 +89,106 @@ impl Bin {
             pretransform,
             holes,
             quality_zones,
+            fixed_items: vec![],
             base_cde,
             area,
         }
     }
 
+    /// Returns a copy of this `Bin` with items fixed in place, replacing any previously fixed items.
+    /// Every fixed item is registered as a static [HazardEntity::FixedItem] in the base `CDEngine`.
+    pub fn with_fixed_items(self, fixed_items: Vec<PlacedItem>) -> Self {
+        let static_hazards = self
+            .base_cde
+            .static_hazards()
+            .iter()
+            .filter(|h| !matches!(h.entity, HazardEntity::FixedItem { .. }))
+            .cloned()
+            .chain(fixed_item_hazards(&fixed_items))
+            .collect_vec();
+        let base_cde = CDEngine::new(
+            self.base_cde.bbox().clone(),
+            static_hazards,
+            self.base_cde.config(),
+        );
+
+        Self {
+            fixed_items,
+            base_cde: Arc::new(base_cde),
+            ..self
+        }
+    }
+
     /// Create a new `Bin` for a strip-packing problem. Instead of a shape, the bin is always rectangular.
+    /// The bounding box of its `CDEngine` extends [STRIP_CDE_HEADROOM] times the width of the strip on either side,
+    /// so the strip can be resized afterwards without rebuilding the `CDEngine` (see [Bin::resize_strip]).
     pub fn from_strip(rect: AARectangle, cde_config: CDEConfig) -> Self {
         let id = 0;
         //The "original" x_min and y_min of the strip should always be at (0, 0)
         let pretransform = Transformation::from_translation((rect.x_min, rect.y_min));
 
-        let poly = SimplePolygon::from(rect);
-        let value = poly.area() as u64;
+        let outer = Arc::new(SimplePolygon::from(rect.clone()));
+        let quality_zones = <[_; N_QUALITIES]>::default();
+        let cde_bbox = {
+            let square = rect.inflate_to_square();
+            let headroom = rect.width() * STRIP_CDE_HEADROOM;
+            AARectangle::new(
+                square.x_min - headroom,
+                square.y_min,
+                square.x_max + headroom,
+                square.y_max,
+            )
+        };
+        let bin_hazards = generate_bin_hazards(&outer, &[], &quality_zones);
+        let base_cde = CDEngine::new(cde_bbox, bin_hazards, cde_config);
 
-        Bin::new(id, poly, value, pretransform, vec![], vec![], cde_config)
+        Self {
+            id,
+            value: outer.area() as u64,
+            area: outer.area(),
+            outer,
+            pretransform,
+            holes: vec![],
+            quality_zones,
+            fixed_items: vec![],
+            base_cde: Arc::new(base_cde),
+        }
+    }
+
+    /// Creates a copy of this strip `Bin` with a different rectangle.
+    /// As long as the rectangle fits inside the bounding box of the current `CDEngine`, the new `CDEngine` is
+    /// derived from the current one by only updating the exterior of the bin, instead of building it from scratch.
+    /// Otherwise, a new `CDEngine` is built with headroom proportional to the new width, so a strip which keeps
+    /// growing only triggers a logarithmic number of rebuilds.
+    pub fn resize_strip(&self, rect: AARectangle) -> Self {
+        debug_assert!(self.holes.is_empty() && self.quality_zones.iter().all(|qz| qz.is_none()));
+        assert!(
+            self.fixed_items
+                .iter()
+                .all(|fi| rect.relation_to(&fi.bbox()) == GeoRelation::Surrounding),
+            "the strip should contain all fixed items"
+        );
+        let cde_config = self.base_cde.config();
+        if self.base_cde.bbox().relation_to(&rect) != GeoRelation::Surrounding {
+            return Bin::from_strip(rect, cde_config).with_fixed_items(self.fixed_items.clone());
+        }
+        let pretransform = Transformation::from_translation((rect.x_min, rect.y_min));
+        let outer = Arc::new(SimplePolygon::from(rect));
+
+        let mut base_cde = self.base_cde.as_ref().clone();
+        base_cde.update_bin_exterior(outer.clone());
+
+        Self {
+            id: self.id,
+            value: outer.area() as u64,
+            area: outer.area(),
+            outer,
+            pretransform,
+            holes: vec![],
+            quality_zones: <[_; N_QUALITIES]>::default(),
+            fixed_items: self.fixed_items.clone(),
+            base_cde: Arc::new(base_cde),
+        }
     }
 
     pub fn bbox(&self) -> AARectangle {
@@ -127,3 +222,10 @@ fn generate_bin_hazards(
     }
     hazards
 }
+
+fn fixed_item_hazards(fixed_items: &[PlacedItem]) -> impl Iterator<Item = Hazard> + '_ {
+    fixed_items.iter().enumerate().flat_map(|(id, fi)| {
+        fi.shapes()
+            .map(move |shape| Hazard::new(HazardEntity::FixedItem { id }, shape.clone()))
+    })
+}
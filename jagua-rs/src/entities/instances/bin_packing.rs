@@ -2,7 +2,6 @@ use crate::entities::bin::Bin;
 use crate::entities::instances::instance_generic::InstanceGeneric;
 use crate::entities::item::Item;
 use crate::fsize;
-use crate::geometry::geo_traits::Shape;
 use crate::util::assertions;
 
 /// Bin-packing problem instance: a set of items to be packed into a set of bins.
@@ -23,7 +22,7 @@ impl BPInstance {
 
         let item_area = items
             .iter()
-            .map(|(item, qty)| item.shape.area() * *qty as fsize)
+            .map(|(item, qty)| item.area() * *qty as fsize)
             .sum();
 
         Self {
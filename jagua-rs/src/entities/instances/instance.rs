@@ -1,5 +1,7 @@
 use crate::entities::instances::bin_packing::BPInstance;
 use crate::entities::instances::instance_generic::InstanceGeneric;
+use crate::entities::instances::knapsack::KPInstance;
+use crate::entities::instances::open_dimension::ODInstance;
 use crate::entities::instances::strip_packing::SPInstance;
 use crate::entities::item::Item;
 use crate::fsize;
@@ -11,6 +13,8 @@ use crate::fsize;
 pub enum Instance {
     SP(SPInstance),
     BP(BPInstance),
+    KP(KPInstance),
+    OD(ODInstance),
 }
 
 impl InstanceGeneric for Instance {
@@ -18,6 +22,8 @@ impl InstanceGeneric for Instance {
         match self {
             Instance::SP(instance) => instance.items(),
             Instance::BP(instance) => instance.items(),
+            Instance::KP(instance) => instance.items(),
+            Instance::OD(instance) => instance.items(),
         }
     }
 
@@ -25,6 +31,8 @@ impl InstanceGeneric for Instance {
         match self {
             Instance::SP(instance) => instance.item_area(),
             Instance::BP(instance) => instance.item_area(),
+            Instance::KP(instance) => instance.item_area(),
+            Instance::OD(instance) => instance.item_area(),
         }
     }
 }
@@ -40,3 +48,15 @@ impl From<BPInstance> for Instance {
         Instance::BP(instance)
     }
 }
+
+impl From<KPInstance> for Instance {
+    fn from(instance: KPInstance) -> Self {
+        Instance::KP(instance)
+    }
+}
+
+impl From<ODInstance> for Instance {
+    fn from(instance: ODInstance) -> Self {
+        Instance::OD(instance)
+    }
+}
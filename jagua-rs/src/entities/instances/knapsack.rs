@@ -0,0 +1,53 @@
+use crate::entities::bin::Bin;
+use crate::entities::instances::instance_generic::InstanceGeneric;
+use crate::entities::item::Item;
+use crate::fsize;
+use crate::util::assertions;
+
+/// Knapsack problem instance: a set of items of which a subset is to be packed into a fixed set of bins.
+/// The items are to be selected in such a way that the total value of the packed items is maximized.
+#[derive(Debug, Clone)]
+pub struct KPInstance {
+    /// Items which can be packed in the instance, along with their available quantities
+    pub items: Vec<(Item, usize)>,
+    /// Total area of all items in the instance
+    pub item_area: fsize,
+    /// Set of bins in which the items can be packed, along with their quantities.
+    /// All bins are always available, regardless of whether items are packed in them.
+    pub bins: Vec<(Bin, usize)>,
+}
+
+impl KPInstance {
+    pub fn new(items: Vec<(Item, usize)>, bins: Vec<(Bin, usize)>) -> Self {
+        assert!(assertions::instance_item_bin_ids_correct(&items, &bins));
+
+        let item_area = items
+            .iter()
+            .map(|(item, qty)| item.area() * *qty as fsize)
+            .sum();
+
+        Self {
+            items,
+            item_area,
+            bins,
+        }
+    }
+
+    /// Total value of all items in the instance, an upper bound for the value of any solution
+    pub fn item_value(&self) -> u64 {
+        self.items
+            .iter()
+            .map(|(item, qty)| item.value * *qty as u64)
+            .sum()
+    }
+}
+
+impl InstanceGeneric for KPInstance {
+    fn items(&self) -> &[(Item, usize)] {
+        &self.items
+    }
+
+    fn item_area(&self) -> fsize {
+        self.item_area
+    }
+}
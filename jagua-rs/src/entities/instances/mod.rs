@@ -1,4 +1,6 @@
 pub mod bin_packing;
 pub mod instance;
 pub mod instance_generic;
+pub mod knapsack;
+pub mod open_dimension;
 pub mod strip_packing;
@@ -0,0 +1,54 @@
+use crate::entities::instances::instance_generic::InstanceGeneric;
+use crate::entities::item::Item;
+use crate::fsize;
+use crate::util::assertions;
+
+/// Open dimension problem instance: a set of items to be packed into a single rectangular container,
+/// of which one or both dimensions are variable.
+/// The items are to be packed in such a way that the open dimension(s) of the container are minimized.
+#[derive(Debug, Clone)]
+pub struct ODInstance {
+    /// The items to be packed and their quantities
+    pub items: Vec<(Item, usize)>,
+    /// The total area of the items
+    pub item_area: fsize,
+    /// Which dimensions of the container are open
+    pub open_dim: OpenDimension,
+}
+
+/// The dimension(s) of the container which are to be minimized
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OpenDimension {
+    /// The width of the container is fixed, its height is minimized
+    Height { width: fsize },
+    /// Both dimensions are open, the area of the container is minimized.
+    /// If an aspect ratio (width / height) is defined, the container is restricted to it.
+    Area { aspect_ratio: Option<fsize> },
+}
+
+impl ODInstance {
+    pub fn new(items: Vec<(Item, usize)>, open_dim: OpenDimension) -> Self {
+        assert!(assertions::instance_item_bin_ids_correct(&items, &[]));
+
+        let item_area = items
+            .iter()
+            .map(|(item, qty)| item.area() * *qty as fsize)
+            .sum();
+
+        Self {
+            items,
+            item_area,
+            open_dim,
+        }
+    }
+}
+
+impl InstanceGeneric for ODInstance {
+    fn items(&self) -> &[(Item, usize)] {
+        &self.items
+    }
+
+    fn item_area(&self) -> fsize {
+        self.item_area
+    }
+}
@@ -1,7 +1,7 @@
 use crate::entities::instances::instance_generic::InstanceGeneric;
 use crate::entities::item::Item;
+use crate::entities::placed_item::PlacedItem;
 use crate::fsize;
-use crate::geometry::geo_traits::Shape;
 use crate::util::assertions;
 
 /// Strip-packing problem instance: a set of items to be packed into a single strip.
@@ -14,21 +14,29 @@ pub struct SPInstance {
     pub item_area: fsize,
     /// The (fixed) height of the strip
     pub strip_height: fsize,
+    /// Items which are fixed in place in the strip, positioned relative to its start at x = 0
+    pub fixed_items: Vec<PlacedItem>,
 }
 
 impl SPInstance {
-    pub fn new(items: Vec<(Item, usize)>, strip_height: fsize) -> Self {
+    pub fn new(
+        items: Vec<(Item, usize)>,
+        strip_height: fsize,
+        fixed_items: Vec<PlacedItem>,
+    ) -> Self {
         assert!(assertions::instance_item_bin_ids_correct(&items, &[]));
+        assert!(fixed_items.iter().all(|fi| fi.item_id < items.len()));
 
         let item_area = items
             .iter()
-            .map(|(item, qty)| item.shape.area() * *qty as fsize)
+            .map(|(item, qty)| item.area() * *qty as fsize)
             .sum();
 
         Self {
             items,
             item_area,
             strip_height,
+            fixed_items,
         }
     }
 }
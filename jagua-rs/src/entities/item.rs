@@ -1,7 +1,11 @@
 use std::sync::Arc;
 
+use itertools::Itertools;
+
 use crate::collision_detection::hazard_filter::QZHazardFilter;
-use crate::geometry::geo_enums::AllowedRotation;
+use crate::fsize;
+use crate::geometry::geo_enums::{AllowedMirroring, AllowedRotation};
+use crate::geometry::geo_traits::Shape;
 use crate::geometry::primitives::simple_polygon::SimplePolygon;
 use crate::geometry::transformation::Transformation;
 use crate::util::config::SPSurrogateConfig;
@@ -10,10 +14,15 @@ use crate::util::config::SPSurrogateConfig;
 #[derive(Clone, Debug)]
 pub struct Item {
     pub id: usize,
-    /// Contour of the item
+    /// Contour of the item, possibly with holes in which other items can be placed.
+    /// For items consisting of multiple disjoint polygons, this is the one with the largest area.
     pub shape: Arc<SimplePolygon>,
+    /// The other disjoint polygons of the item (if any), which move rigidly together with `shape`
+    pub extra_shapes: Vec<Arc<SimplePolygon>>,
     /// Possible rotations in which to place the item
     pub allowed_rotation: AllowedRotation,
+    /// Whether the item can be placed mirrored, not by default
+    pub allowed_mirroring: AllowedMirroring,
     /// The quality of the item, if `None` the item requires full quality
     pub base_quality: Option<usize>,
     pub value: u64,
@@ -26,20 +35,55 @@ pub struct Item {
 impl Item {
     pub fn new(
         id: usize,
-        mut shape: SimplePolygon,
+        shape: SimplePolygon,
+        value: u64,
+        allowed_rotation: AllowedRotation,
+        pretransform: Transformation,
+        base_quality: Option<usize>,
+        surrogate_config: SPSurrogateConfig,
+    ) -> Item {
+        Item::new_multi(
+            id,
+            vec![shape],
+            value,
+            allowed_rotation,
+            pretransform,
+            base_quality,
+            surrogate_config,
+        )
+    }
+
+    /// Creates an item consisting of multiple disjoint polygons, which are always placed together.
+    pub fn new_multi(
+        id: usize,
+        mut shapes: Vec<SimplePolygon>,
         value: u64,
         allowed_rotation: AllowedRotation,
         pretransform: Transformation,
         base_quality: Option<usize>,
         surrogate_config: SPSurrogateConfig,
     ) -> Item {
-        shape.generate_surrogate(surrogate_config);
-        let shape = Arc::new(shape);
+        assert!(!shapes.is_empty(), "item has no shape");
+        //the largest polygon is the main shape of the item
+        let largest = shapes
+            .iter()
+            .position_max_by(|a, b| a.area.partial_cmp(&b.area).unwrap())
+            .unwrap();
+        shapes.swap(0, largest);
+
+        let mut shapes = shapes.into_iter().map(|mut shape| {
+            shape.generate_surrogate(surrogate_config);
+            Arc::new(shape)
+        });
+        let shape = shapes.next().unwrap();
+        let extra_shapes = shapes.collect_vec();
         let hazard_filter = base_quality.map(QZHazardFilter);
         Item {
             id,
             shape,
+            extra_shapes,
             allowed_rotation,
+            allowed_mirroring: AllowedMirroring::None,
             base_quality,
             value,
             pretransform,
@@ -50,4 +94,13 @@ impl Item {
     pub fn clone_with_id(&self, id: usize) -> Item {
         Item { id, ..self.clone() }
     }
+
+    /// All polygons of the item, starting with the main [`Self::shape`]
+    pub fn shapes(&self) -> impl Iterator<Item = &Arc<SimplePolygon>> {
+        std::iter::once(&self.shape).chain(self.extra_shapes.iter())
+    }
+
+    pub fn area(&self) -> fsize {
+        self.shapes().map(|s| s.area()).sum()
+    }
 }
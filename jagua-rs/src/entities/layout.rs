@@ -1,13 +1,16 @@
 use crate::collision_detection::cd_engine::{CDESnapshot, CDEngine};
+#[cfg(feature = "cde-stats")]
+use crate::collision_detection::cde_stats::CDEStats;
 use crate::collision_detection::hazard::Hazard;
+use crate::collision_detection::hazard::HazardEntity;
 use crate::entities::bin::Bin;
 use crate::entities::item::Item;
 use crate::entities::placed_item::{PItemKey, PlacedItem};
 use crate::fsize;
 use crate::geometry::d_transformation::DTransformation;
-use crate::geometry::geo_traits::Shape;
 use crate::util::assertions;
 use slotmap::SlotMap;
+use std::sync::Arc;
 
 ///A Layout is made out of a [Bin] with a set of [Item]s positioned inside of it in a specific way.
 ///It is a mutable representation, and can be modified by placing or removing items.
@@ -24,6 +27,8 @@ pub struct Layout {
     pub placed_items: SlotMap<PItemKey, PlacedItem>,
     /// The collision detection engine for this layout
     cde: CDEngine,
+    /// State of the transaction in progress, if any
+    transaction: TransactionState,
 }
 
 impl Layout {
@@ -34,6 +39,7 @@ impl Layout {
             bin,
             placed_items: SlotMap::with_key(),
             cde,
+            transaction: TransactionState::Closed,
         }
     }
 
@@ -46,14 +52,35 @@ impl Layout {
     pub fn change_bin(&mut self, bin: Bin) {
         // swap the bin
         self.bin = bin;
-        // update the CDE
-        self.cde = self.bin.base_cde.as_ref().clone();
+        // update the CDE, carrying over the custom hazards
+        let old_cde = std::mem::replace(&mut self.cde, self.bin.base_cde.as_ref().clone());
+        for hazard in old_cde.static_hazards().iter().filter(|h| is_custom(h)) {
+            self.cde.register_static_hazard(hazard.clone());
+        }
+        for hazard in old_cde.dynamic_hazards().iter().filter(|h| is_custom(h)) {
+            self.cde.register_hazard(hazard.clone());
+        }
         for (pik, pi) in self.placed_items.iter() {
-            let hazard = Hazard::new(pik.into(), pi.shape.clone());
-            self.cde.register_hazard(hazard);
+            self.cde
+                .register_hazard_components(placed_item_hazards(pik, pi));
         }
     }
 
+    /// Replaces the bin by one which only differs in its exterior, such as a resized strip (see [Bin::resize_strip]).
+    /// If both bins share the bounding box of their [CDEngine], it is updated in place, otherwise it is rebuilt like in [Self::change_bin].
+    /// Placed items are not checked against the new bin and keep their keys.
+    pub fn change_bin_exterior(&mut self, bin: Bin) {
+        match self.cde.bbox() == bin.base_cde.bbox() {
+            true => {
+                self.cde.update_bin_exterior(bin.outer.clone());
+                self.bin = bin;
+            }
+            false => self.change_bin(bin),
+        }
+
+        debug_assert!(assertions::layout_qt_matches_fresh_qt(self));
+    }
+
     pub fn create_snapshot(&mut self) -> LayoutSnapshot {
         LayoutSnapshot {
             id: self.id,
@@ -79,11 +106,12 @@ impl Layout {
     }
 
     pub fn place_item(&mut self, item: &Item, d_transformation: DTransformation) -> PItemKey {
+        self.touch();
         let placed_item = PlacedItem::new(item, d_transformation);
         let pik = self.placed_items.insert(placed_item);
 
-        let hazard = Hazard::new(pik.into(), self.placed_items[pik].shape.clone());
-        self.cde.register_hazard(hazard);
+        let hazards = placed_item_hazards(pik, &self.placed_items[pik]);
+        self.cde.register_hazard_components(hazards);
 
         debug_assert!(assertions::layout_qt_matches_fresh_qt(self));
 
@@ -91,6 +119,7 @@ impl Layout {
     }
 
     pub fn remove_item(&mut self, key: PItemKey, commit_instant: bool) -> PlacedItem {
+        self.touch();
         let p_item = self
             .placed_items
             .remove(key)
@@ -104,6 +133,108 @@ impl Layout {
         p_item
     }
 
+    /// Starts a transaction, see [ProblemGeneric::begin](crate::entities::problems::problem_generic::ProblemGeneric::begin).
+    /// The placed items are saved right before the layout is modified for the first time.
+    pub fn begin(&mut self) {
+        assert!(
+            matches!(self.transaction, TransactionState::Closed),
+            "transaction already in progress"
+        );
+        self.transaction = TransactionState::Untouched;
+    }
+
+    /// Ends the transaction, keeping all changes.
+    /// Returns whether the layout was modified during the transaction.
+    pub fn commit(&mut self) -> bool {
+        match std::mem::replace(&mut self.transaction, TransactionState::Closed) {
+            TransactionState::Closed => panic!("no transaction in progress"),
+            TransactionState::Untouched => false,
+            TransactionState::Touched(_) => true,
+        }
+    }
+
+    /// Ends the transaction, reverting the layout to the items placed at its start.
+    /// Only the hazards of items placed or removed in the meantime are updated in the [CDEngine],
+    /// hazards of removed items which were not committed yet are simply reactivated.
+    pub fn rollback(&mut self) {
+        let state = std::mem::replace(&mut self.transaction, TransactionState::Closed);
+        let placed_items = match state {
+            TransactionState::Closed => panic!("no transaction in progress"),
+            TransactionState::Untouched => return,
+            TransactionState::Touched(placed_items) => placed_items,
+        };
+        let added_keys = self
+            .placed_items
+            .keys()
+            .filter(|k| !placed_items.contains_key(*k))
+            .collect::<Vec<_>>();
+        for key in added_keys {
+            self.cde.deregister_hazard(key.into(), true);
+        }
+        for (key, pi) in placed_items.iter() {
+            if !self.placed_items.contains_key(key) {
+                self.cde
+                    .register_hazard_components(placed_item_hazards(key, pi));
+            }
+        }
+        self.placed_items = placed_items;
+
+        debug_assert!(assertions::layout_qt_matches_fresh_qt(self));
+    }
+
+    /// Saves the placed items if the layout is about to be modified for the first time during a transaction
+    fn touch(&mut self) {
+        if let TransactionState::Untouched = self.transaction {
+            self.transaction = TransactionState::Touched(self.placed_items.clone());
+        }
+    }
+
+    /// Adds a hazard induced by a [HazardEntity::Custom] to the layout.
+    /// Static hazards are not part of [LayoutSnapshot]s and persist when restoring the layout,
+    /// while dynamic hazards are rolled back like placed items.
+    /// <br>
+    /// Items already placed in the layout are not checked against the new hazard.
+    /// If the shape of the hazard has no surrogate, one is generated using the config of the [CDEngine].
+    pub fn add_custom_hazard(&mut self, mut hazard: Hazard, is_static: bool) {
+        assert!(
+            is_custom(&hazard),
+            "not a custom hazard: {:?}",
+            hazard.entity
+        );
+        if hazard.shape.surrogate.is_none() {
+            let surrogate_config = self.cde.config().item_surrogate_config;
+            Arc::make_mut(&mut hazard.shape).generate_surrogate(surrogate_config);
+        }
+        match is_static {
+            true => self.cde.register_static_hazard(hazard),
+            false => self.cde.register_hazard(hazard),
+        }
+
+        debug_assert!(assertions::layout_qt_matches_fresh_qt(self));
+    }
+
+    /// Removes a hazard induced by a [HazardEntity::Custom] from the layout, regardless of whether it was added as static or dynamic.
+    pub fn remove_custom_hazard(&mut self, entity: HazardEntity, commit_instant: bool) {
+        assert!(
+            matches!(entity, HazardEntity::Custom { .. }),
+            "not a custom hazard: {:?}",
+            entity
+        );
+        match self.cde.static_hazards().iter().any(|h| h.entity == entity) {
+            true => {
+                self.cde.deregister_static_hazard(entity);
+            }
+            false => self.cde.deregister_hazard(entity, commit_instant),
+        }
+
+        debug_assert!(assertions::layout_qt_matches_fresh_qt(self));
+    }
+
+    /// Returns all hazards induced by [HazardEntity::Custom]s in the layout, both static and dynamic.
+    pub fn custom_hazards(&self) -> impl Iterator<Item = &Hazard> {
+        self.cde.all_hazards().filter(|h| is_custom(h))
+    }
+
     /// True if no items are placed
     pub fn is_empty(&self) -> bool {
         self.placed_items.is_empty()
@@ -118,13 +249,14 @@ impl Layout {
     }
 
     /// Returns the usage of the bin with the items placed.
-    /// It is the ratio of the area of the items placed to the area of the bin.
+    /// It is the ratio of the area of the items placed (including the fixed items of the bin) to the area of the bin.
     pub fn usage(&self) -> fsize {
         let bin_area = self.bin().area;
         let item_area = self
             .placed_items
-            .iter()
-            .map(|(_, pi)| pi.shape.area())
+            .values()
+            .chain(self.bin.fixed_items.iter())
+            .map(|pi| pi.area())
             .sum::<fsize>();
 
         item_area / bin_area
@@ -139,6 +271,15 @@ impl Layout {
         &self.cde
     }
 
+    /// Returns the statistics of the collision detection engine for this layout and resets them,
+    /// so queries are never counted twice when the engine outlives a harvest (e.g. when a strip is resized in place).
+    #[cfg(feature = "cde-stats")]
+    pub fn take_cde_stats(&mut self) -> CDEStats {
+        let stats = self.cde.stats();
+        self.cde.reset_stats();
+        stats
+    }
+
     /// Makes sure that the collision detection engine is completely updated with the changes made to the layout.
     pub fn flush_changes(&mut self) {
         self.cde.flush_haz_prox_grid();
@@ -160,3 +301,25 @@ pub struct LayoutSnapshot {
     /// The usage of the bin with the items placed
     pub usage: fsize,
 }
+
+/// Progress of a transaction in a [Layout]
+#[derive(Clone)]
+enum TransactionState {
+    /// No transaction in progress
+    Closed,
+    /// Transaction in progress, the layout has not been modified yet
+    Untouched,
+    /// Transaction in progress, contains the items placed before the first modification
+    Touched(SlotMap<PItemKey, PlacedItem>),
+}
+
+/// The hazards induced by a placed item, one for each of its polygons
+pub fn placed_item_hazards(pik: PItemKey, pi: &PlacedItem) -> Vec<Hazard> {
+    pi.shapes()
+        .map(|shape| Hazard::new(pik.into(), shape.clone()))
+        .collect()
+}
+
+fn is_custom(hazard: &Hazard) -> bool {
+    matches!(hazard.entity, HazardEntity::Custom { .. })
+}
@@ -1,7 +1,9 @@
 use crate::collision_detection::hazard_filter::QZHazardFilter;
 use crate::entities::item::Item;
+use crate::fsize;
 use crate::geometry::d_transformation::DTransformation;
-use crate::geometry::geo_traits::Transformable;
+use crate::geometry::geo_traits::{Shape, Transformable};
+use crate::geometry::primitives::aa_rectangle::AARectangle;
 use crate::geometry::primitives::simple_polygon::SimplePolygon;
 use slotmap::new_key_type;
 use std::sync::Arc;
@@ -22,19 +24,43 @@ pub struct PlacedItem {
     pub hazard_filter: Option<QZHazardFilter>,
     /// The shape of the `Item` after it has been transformed and placed in a `Layout`
     pub shape: Arc<SimplePolygon>,
+    /// The extra shapes of a multi-polygon `Item`, transformed and placed in a `Layout` together with `shape`
+    pub extra_shapes: Vec<Arc<SimplePolygon>>,
 }
 
 impl PlacedItem {
     pub fn new(item: &Item, d_transf: DTransformation) -> Self {
         let transf = d_transf.compose();
         let shape = Arc::new(item.shape.transform_clone(&transf));
+        let extra_shapes = item
+            .extra_shapes
+            .iter()
+            .map(|s| Arc::new(s.transform_clone(&transf)))
+            .collect();
         let qz_haz_filter = item.hazard_filter.clone();
 
         PlacedItem {
             item_id: item.id,
             d_transf,
             shape,
+            extra_shapes,
             hazard_filter: qz_haz_filter,
         }
     }
+
+    /// All placed polygons of the item, starting with the main [`Self::shape`]
+    pub fn shapes(&self) -> impl Iterator<Item = &Arc<SimplePolygon>> {
+        std::iter::once(&self.shape).chain(self.extra_shapes.iter())
+    }
+
+    pub fn area(&self) -> fsize {
+        self.shapes().map(|s| s.area()).sum()
+    }
+
+    /// Bounding box of all placed polygons of the item
+    pub fn bbox(&self) -> AARectangle {
+        self.extra_shapes.iter().fold(self.shape.bbox(), |acc, s| {
+            AARectangle::bounding_rectangle(&acc, &s.bbox())
+        })
+    }
 }
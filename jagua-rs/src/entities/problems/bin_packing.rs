@@ -1,13 +1,16 @@
 use itertools::Itertools;
 
+use crate::entities::bin::Bin;
 use crate::entities::instances::bin_packing::BPInstance;
 use crate::entities::instances::instance_generic::InstanceGeneric;
 use crate::entities::layout::Layout;
 use crate::entities::placed_item::PItemKey;
 use crate::entities::placing_option::PlacingOption;
 use crate::entities::problems::problem_generic::private::ProblemGenericPrivate;
+use crate::entities::problems::problem_generic::Transaction;
 use crate::entities::problems::problem_generic::{LayoutIndex, ProblemGeneric};
 use crate::entities::solution::Solution;
+use crate::io::compact_solution::CompactLayout;
 use crate::util::assertions;
 
 /// Bin Packing Problem
@@ -20,6 +23,7 @@ pub struct BPProblem {
     bin_qtys: Vec<usize>,
     layout_id_counter: usize,
     solution_id_counter: usize,
+    transaction: Option<Transaction>,
     unmodified_layout_ids: Vec<usize>,
     unmodified_layouts_ref_solution: Option<usize>,
     uncommitted_removed_layouts: Vec<Layout>,
@@ -53,6 +57,7 @@ impl BPProblem {
             bin_qtys,
             layout_id_counter,
             solution_id_counter: 0,
+            transaction: None,
             unmodified_layout_ids: unchanged_layouts,
             unmodified_layouts_ref_solution: unchanged_layouts_solution_id,
             uncommitted_removed_layouts,
@@ -68,6 +73,7 @@ impl BPProblem {
         layout
             .placed_items()
             .values()
+            .chain(layout.bin().fixed_items.iter())
             .for_each(|pi| self.register_included_item(pi.item_id));
         self.layouts.push(layout);
         LayoutIndex::Real(self.layouts.len() - 1)
@@ -82,6 +88,7 @@ impl BPProblem {
                 layout
                     .placed_items()
                     .values()
+                    .chain(layout.bin().fixed_items.iter())
                     .for_each(|pi| self.deregister_included_item(pi.item_id));
                 self.uncommitted_removed_layouts.push(layout);
             }
@@ -148,8 +155,8 @@ impl ProblemGeneric for BPProblem {
                 self.layout_has_changed(self.layouts[i].id());
                 let layout = &mut self.layouts[i];
                 let pi = layout.remove_item(pik, commit_instantly);
-                if layout.is_empty() {
-                    //if layout is empty, remove it
+                //if layout is empty, remove it (postponed until the end of a transaction, to keep the layout indices valid)
+                if layout.is_empty() && self.transaction.is_none() {
                     self.deregister_layout(layout_index);
                 }
                 self.deregister_included_item(pi.item_id);
@@ -337,4 +344,39 @@ impl ProblemGenericPrivate for BPProblem {
     fn missing_item_qtys_mut(&mut self) -> &mut [isize] {
         &mut self.missing_item_qtys
     }
+
+    fn transaction_mut(&mut self) -> &mut Option<Transaction> {
+        &mut self.transaction
+    }
+
+    fn import_bins(&self, layouts: &[CompactLayout]) -> Result<(Vec<Bin>, Vec<usize>), String> {
+        let mut bin_qtys = self.instance.bins.iter().map(|(_, qty)| *qty).collect_vec();
+        let mut bins = vec![];
+        for cl in layouts {
+            let Some((bin, _)) = self.instance.bins.get(cl.bin_id) else {
+                return Err(format!("bin {} does not exist", cl.bin_id));
+            };
+            match bin_qtys[cl.bin_id].checked_sub(1) {
+                Some(qty) => bin_qtys[cl.bin_id] = qty,
+                None => return Err(format!("bin {} is used more than its stock", cl.bin_id)),
+            }
+            bins.push(bin.clone());
+        }
+        Ok((bins, bin_qtys))
+    }
+
+    fn commit_layouts(&mut self, modified_layouts: &[usize]) {
+        //remove the layouts which were emptied during the transaction
+        for &i in modified_layouts.iter().rev() {
+            if self.layouts[i].is_empty() {
+                self.deregister_layout(LayoutIndex::Real(i));
+            }
+        }
+    }
+
+    fn rollback_layouts(&mut self, n_layouts: usize, bin_qtys: &[usize]) {
+        //discard the layouts which were opened during the transaction
+        self.layouts.truncate(n_layouts);
+        self.bin_qtys.copy_from_slice(bin_qtys);
+    }
 }
@@ -0,0 +1,224 @@
+use itertools::Itertools;
+
+use crate::entities::bin::Bin;
+use crate::entities::instances::instance_generic::InstanceGeneric;
+use crate::entities::instances::knapsack::KPInstance;
+use crate::entities::layout::Layout;
+use crate::entities::placed_item::PItemKey;
+use crate::entities::placing_option::PlacingOption;
+use crate::entities::problems::problem_generic::private::ProblemGenericPrivate;
+use crate::entities::problems::problem_generic::Transaction;
+use crate::entities::problems::problem_generic::{LayoutIndex, ProblemGeneric};
+use crate::entities::solution::Solution;
+use crate::io::compact_solution::CompactLayout;
+use crate::util::assertions;
+
+/// Knapsack Problem
+/// <br>
+/// Every bin of the instance has a layout from the start, which remains present even if it is empty.
+/// Items do not have to be placed, the objective is to maximize the total value of the placed items.
+#[derive(Clone)]
+pub struct KPProblem {
+    pub instance: KPInstance,
+    pub layouts: Vec<Layout>,
+    missing_item_qtys: Vec<isize>,
+    bin_qtys: Vec<usize>,
+    layout_id_counter: usize,
+    solution_id_counter: usize,
+    transaction: Option<Transaction>,
+}
+
+impl KPProblem {
+    pub fn new(instance: KPInstance) -> Self {
+        let mut missing_item_qtys = instance
+            .items
+            .iter()
+            .map(|(_, qty)| *qty as isize)
+            .collect_vec();
+        //all bins are in use from the start, so their fixed items count toward the demand
+        for (bin, qty) in instance.bins.iter() {
+            for fi in bin.fixed_items.iter() {
+                missing_item_qtys[fi.item_id] -= *qty as isize;
+            }
+        }
+        let bin_qtys = vec![0; instance.bins.len()];
+        let layouts = instance
+            .bins
+            .iter()
+            .flat_map(|(bin, qty)| (0..*qty).map(move |_| bin))
+            .enumerate()
+            .map(|(i, bin)| Layout::new(i, bin.clone()))
+            .collect_vec();
+        let layout_id_counter = layouts.len();
+
+        Self {
+            instance,
+            layouts,
+            missing_item_qtys,
+            bin_qtys,
+            layout_id_counter,
+            solution_id_counter: 0,
+            transaction: None,
+        }
+    }
+
+    /// Total value of all items currently placed
+    pub fn placed_value(&self) -> u64 {
+        self.layouts
+            .iter()
+            .flat_map(|l| l.placed_items().values())
+            .map(|pi| self.instance.item(pi.item_id).value)
+            .sum()
+    }
+}
+
+impl ProblemGeneric for KPProblem {
+    fn place_item(&mut self, p_opt: PlacingOption) -> (LayoutIndex, PItemKey) {
+        let i = match p_opt.layout_idx {
+            LayoutIndex::Real(i) => i,
+            LayoutIndex::Template(_) => {
+                unreachable!("knapsack problems do not have template layouts")
+            }
+        };
+        let item = self.instance.item(p_opt.item_id);
+        let pik = self.layouts[i].place_item(item, p_opt.d_transf);
+
+        self.register_included_item(p_opt.item_id);
+        (p_opt.layout_idx, pik)
+    }
+
+    fn remove_item(
+        &mut self,
+        layout_index: LayoutIndex,
+        pik: PItemKey,
+        commit_instantly: bool,
+    ) -> PlacingOption {
+        match layout_index {
+            LayoutIndex::Real(i) => {
+                //the layout is kept, even if it becomes empty
+                let pi = self.layouts[i].remove_item(pik, commit_instantly);
+                self.deregister_included_item(pi.item_id);
+                PlacingOption::from_placed_item(layout_index, &pi)
+            }
+            LayoutIndex::Template(_) => panic!("cannot remove item from template layout"),
+        }
+    }
+
+    fn create_solution(&mut self, _old_solution: Option<&Solution>) -> Solution {
+        let id = self.next_solution_id();
+        let included_item_qtys = self.placed_item_qtys().collect_vec();
+        let bin_qtys = self.bin_qtys().to_vec();
+        let layout_snapshots = self
+            .layouts
+            .iter_mut()
+            .map(|l| l.create_snapshot())
+            .collect();
+        let target_item_qtys = self
+            .instance
+            .items
+            .iter()
+            .map(|(_, qty)| *qty)
+            .collect_vec();
+
+        let solution = Solution::new(
+            id,
+            layout_snapshots,
+            self.usage(),
+            included_item_qtys,
+            target_item_qtys,
+            bin_qtys,
+        );
+        debug_assert!(assertions::problem_matches_solution(self, &solution));
+
+        solution
+    }
+
+    fn restore_to_solution(&mut self, solution: &Solution) {
+        debug_assert!(solution.layout_snapshots.len() == self.layouts.len());
+
+        //the set of layouts is fixed, so every layout has a snapshot at the same index
+        for (layout, sl) in self.layouts.iter_mut().zip(&solution.layout_snapshots) {
+            match layout.id() == sl.id {
+                true => layout.restore(sl),
+                false => *layout = Layout::from_snapshot(sl),
+            }
+        }
+
+        //restore the missing item quantities
+        self.missing_item_qtys
+            .iter_mut()
+            .enumerate()
+            .for_each(|(i, qty)| {
+                *qty = (self.instance.item_qty(i) - solution.placed_item_qtys[i]) as isize
+            });
+
+        debug_assert!(assertions::problem_matches_solution(self, solution));
+    }
+
+    fn layouts(&self) -> &[Layout] {
+        &self.layouts
+    }
+
+    fn layouts_mut(&mut self) -> &mut [Layout] {
+        &mut self.layouts
+    }
+
+    fn template_layouts(&self) -> &[Layout] {
+        &[]
+    }
+
+    fn missing_item_qtys(&self) -> &[isize] {
+        &self.missing_item_qtys
+    }
+
+    fn bin_qtys(&self) -> &[usize] {
+        &self.bin_qtys
+    }
+
+    fn instance(&self) -> &dyn InstanceGeneric {
+        &self.instance
+    }
+}
+
+impl ProblemGenericPrivate for KPProblem {
+    fn next_solution_id(&mut self) -> usize {
+        self.solution_id_counter += 1;
+        self.solution_id_counter
+    }
+
+    fn next_layout_id(&mut self) -> usize {
+        self.layout_id_counter += 1;
+        self.layout_id_counter
+    }
+
+    fn missing_item_qtys_mut(&mut self) -> &mut [isize] {
+        &mut self.missing_item_qtys
+    }
+
+    fn transaction_mut(&mut self) -> &mut Option<Transaction> {
+        &mut self.transaction
+    }
+
+    fn import_bins(&self, layouts: &[CompactLayout]) -> Result<(Vec<Bin>, Vec<usize>), String> {
+        //the set of layouts is fixed, so the solution should contain every one of them in the same order
+        if layouts.len() != self.layouts.len() {
+            return Err(format!(
+                "expected {} layouts, found {}",
+                self.layouts.len(),
+                layouts.len()
+            ));
+        }
+        let mut bins = vec![];
+        for (layout, cl) in self.layouts.iter().zip(layouts) {
+            if layout.bin().id != cl.bin_id {
+                return Err(format!(
+                    "expected bin {} for layout, found {}",
+                    layout.bin().id,
+                    cl.bin_id
+                ));
+            }
+            bins.push(layout.bin().clone());
+        }
+        Ok((bins, self.bin_qtys.clone()))
+    }
+}
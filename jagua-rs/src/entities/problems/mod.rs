@@ -1,4 +1,6 @@
 pub mod bin_packing;
+pub mod knapsack;
+pub mod open_dimension;
 pub mod problem;
 pub mod problem_generic;
 pub mod strip_packing;
@@ -0,0 +1,318 @@
+use std::{iter, slice};
+
+use itertools::Itertools;
+
+use crate::entities::bin::Bin;
+use crate::entities::instances::instance_generic::InstanceGeneric;
+use crate::entities::instances::open_dimension::{ODInstance, OpenDimension};
+use crate::entities::layout::Layout;
+use crate::entities::placed_item::PItemKey;
+use crate::entities::placing_option::PlacingOption;
+use crate::entities::problems::problem_generic::private::ProblemGenericPrivate;
+use crate::entities::problems::problem_generic::ProblemGeneric;
+use crate::entities::problems::problem_generic::Transaction;
+use crate::entities::problems::problem_generic::{LayoutIndex, STRIP_LAYOUT_IDX};
+use crate::entities::problems::strip_packing::resize_strip_layout;
+use crate::entities::solution::Solution;
+use crate::fsize;
+use crate::geometry::geo_traits::Shape;
+use crate::geometry::primitives::aa_rectangle::AARectangle;
+use crate::io::compact_solution::CompactLayout;
+use crate::util::assertions;
+use crate::util::config::CDEConfig;
+use crate::util::fpa::FPA;
+
+/// Open Dimension Problem
+/// <br>
+/// A single rectangular container, of which the open dimension(s) can be resized in both axes.
+/// Dimensions passed to the resizing methods are adjusted to the [OpenDimension] of the instance:
+/// a fixed width is kept and a fixed aspect ratio is restored by enlarging the smaller dimension.
+#[derive(Clone)]
+pub struct ODProblem {
+    pub instance: ODInstance,
+    pub layout: Layout,
+    missing_item_qtys: Vec<isize>,
+    layout_id_counter: usize,
+    solution_id_counter: usize,
+    transaction: Option<Transaction>,
+}
+
+impl ODProblem {
+    pub fn new(instance: ODInstance, width: fsize, height: fsize, cde_config: CDEConfig) -> Self {
+        let missing_item_qtys = instance
+            .items
+            .iter()
+            .map(|(_, qty)| *qty as isize)
+            .collect_vec();
+        let (width, height) = constrain_dims(instance.open_dim, width, height);
+        let rect = AARectangle::new(0.0, 0.0, width, height);
+        let layout_id_counter = 0;
+        let layout = Layout::new(layout_id_counter, Bin::from_strip(rect, cde_config));
+
+        Self {
+            instance,
+            layout,
+            missing_item_qtys,
+            layout_id_counter,
+            solution_id_counter: 0,
+            transaction: None,
+        }
+    }
+
+    /// Resizes the container, adding or removing space at the right and top.
+    pub fn modify_container(&mut self, width: fsize, height: fsize) {
+        let (width, height) = constrain_dims(self.instance.open_dim, width, height);
+        let bbox = self.layout.bin().bbox();
+        let new_rect = AARectangle::new(
+            bbox.x_min,
+            bbox.y_min,
+            bbox.x_min + width,
+            bbox.y_min + height,
+        );
+        self.modify_container_rect(new_rect);
+    }
+
+    /// Resizes the container, dividing the added or removed space equally around the current items.
+    pub fn modify_container_centered(&mut self, width: fsize, height: fsize) {
+        let (width, height) = constrain_dims(self.instance.open_dim, width, height);
+        let center = match self.occupied_bbox() {
+            Some(occupied) => occupied.centroid(),
+            None => self.layout.bin().bbox().centroid(),
+        };
+        let new_rect = AARectangle::new(
+            center.0 - width / 2.0,
+            center.1 - height / 2.0,
+            center.0 + width / 2.0,
+            center.1 + height / 2.0,
+        );
+        self.modify_container_rect(new_rect);
+    }
+
+    /// Shrinks the container to the minimum size that fits all items, respecting the [OpenDimension] of the instance.
+    pub fn fit_container(&mut self) {
+        let Some(occupied) = self.occupied_bbox() else {
+            return;
+        };
+        let n_items_in_old_container = self.layout.placed_items().len();
+
+        //add some tolerance to avoid rounding errors or false collision positives
+        let margin = 1.0 + FPA::tolerance();
+        self.modify_container_centered(occupied.width() * margin, occupied.height() * margin);
+
+        assert_eq!(
+            n_items_in_old_container,
+            self.layout.placed_items().len(),
+            "fitting the container should not remove any items"
+        );
+    }
+
+    /// Returns the bounding box of all placed items. If no items are placed, returns None.
+    pub fn occupied_bbox(&self) -> Option<AARectangle> {
+        self.layout
+            .placed_items()
+            .values()
+            .map(|pi| pi.bbox())
+            .reduce(|a, b| AARectangle::bounding_rectangle(&a, &b))
+    }
+
+    pub fn container_width(&self) -> fsize {
+        self.layout.bin().bbox().width()
+    }
+
+    pub fn container_height(&self) -> fsize {
+        self.layout.bin().bbox().height()
+    }
+
+    pub fn container_area(&self) -> fsize {
+        self.layout.bin().area
+    }
+
+    /// Replaces the container by a new rectangle.
+    /// All items that fit in the new container are kept, the rest are removed.
+    /// The container cannot be modified during a transaction, as the resize cannot be rolled back.
+    fn modify_container_rect(&mut self, rect: AARectangle) {
+        assert!(
+            self.transaction.is_none(),
+            "cannot modify the container during a transaction"
+        );
+        resize_strip_layout(&mut self.layout, rect);
+        //snapshots of the layout taken before the resize no longer match its bin
+        self.layout.id = self.next_layout_id();
+
+        //items which no longer fit are missing again
+        let placed_qtys = self
+            .layout
+            .placed_items()
+            .values()
+            .map(|pi| pi.item_id)
+            .counts();
+        self.missing_item_qtys
+            .iter_mut()
+            .enumerate()
+            .for_each(|(i, qty)| {
+                *qty = self.instance.item_qty(i) as isize
+                    - placed_qtys.get(&i).copied().unwrap_or(0) as isize
+            });
+    }
+}
+
+impl ProblemGeneric for ODProblem {
+    fn place_item(&mut self, p_opt: PlacingOption) -> (LayoutIndex, PItemKey) {
+        assert_eq!(
+            p_opt.layout_idx, STRIP_LAYOUT_IDX,
+            "open dimension problems only have a single layout"
+        );
+        let item_id = p_opt.item_id;
+        let item = self.instance.item(item_id);
+        let placed_item_key = self.layout.place_item(item, p_opt.d_transf);
+
+        self.register_included_item(item_id);
+        (STRIP_LAYOUT_IDX, placed_item_key)
+    }
+
+    fn remove_item(
+        &mut self,
+        layout_index: LayoutIndex,
+        pik: PItemKey,
+        commit_instantly: bool,
+    ) -> PlacingOption {
+        assert_eq!(
+            layout_index, STRIP_LAYOUT_IDX,
+            "open dimension problems only have a single layout"
+        );
+        let pi = self.layout.remove_item(pik, commit_instantly);
+        self.deregister_included_item(pi.item_id);
+
+        PlacingOption::from_placed_item(layout_index, &pi)
+    }
+
+    fn create_solution(&mut self, _old_solution: Option<&Solution>) -> Solution {
+        let id = self.next_solution_id();
+        let included_item_qtys = self.placed_item_qtys().collect_vec();
+        let bin_qtys = self.bin_qtys().to_vec();
+        let layout_snapshots = vec![self.layout.create_snapshot()];
+        let target_item_qtys = self
+            .instance
+            .items
+            .iter()
+            .map(|(_, qty)| *qty)
+            .collect_vec();
+
+        let solution = Solution::new(
+            id,
+            layout_snapshots,
+            self.usage(),
+            included_item_qtys,
+            target_item_qtys,
+            bin_qtys,
+        );
+
+        debug_assert!(assertions::problem_matches_solution(self, &solution));
+
+        solution
+    }
+
+    fn restore_to_solution(&mut self, solution: &Solution) {
+        debug_assert!(solution.layout_snapshots.len() == 1);
+
+        //restore the layout
+        let layout_snapshot = &solution.layout_snapshots[0];
+        match self.layout.id() == layout_snapshot.id {
+            true => self.layout.restore(layout_snapshot),
+            false => self.layout = Layout::from_snapshot(layout_snapshot),
+        }
+
+        //restore the missing item quantities
+        self.missing_item_qtys
+            .iter_mut()
+            .enumerate()
+            .for_each(|(i, qty)| {
+                *qty = (self.instance.item_qty(i) - solution.placed_item_qtys[i]) as isize
+            });
+
+        debug_assert!(assertions::problem_matches_solution(self, solution));
+    }
+
+    fn layouts(&self) -> &[Layout] {
+        slice::from_ref(&self.layout)
+    }
+
+    fn layouts_mut(&mut self) -> &mut [Layout] {
+        slice::from_mut(&mut self.layout)
+    }
+
+    fn template_layouts(&self) -> &[Layout] {
+        &[]
+    }
+
+    fn missing_item_qtys(&self) -> &[isize] {
+        &self.missing_item_qtys
+    }
+
+    fn template_layout_indices_with_stock(&self) -> impl Iterator<Item = LayoutIndex> {
+        iter::empty::<LayoutIndex>()
+    }
+
+    fn bin_qtys(&self) -> &[usize] {
+        &[0]
+    }
+
+    fn instance(&self) -> &dyn InstanceGeneric {
+        &self.instance
+    }
+}
+
+impl ProblemGenericPrivate for ODProblem {
+    fn next_solution_id(&mut self) -> usize {
+        self.solution_id_counter += 1;
+        self.solution_id_counter
+    }
+
+    fn next_layout_id(&mut self) -> usize {
+        self.layout_id_counter += 1;
+        self.layout_id_counter
+    }
+
+    fn missing_item_qtys_mut(&mut self) -> &mut [isize] {
+        &mut self.missing_item_qtys
+    }
+
+    fn transaction_mut(&mut self) -> &mut Option<Transaction> {
+        &mut self.transaction
+    }
+
+    fn import_bins(&self, layouts: &[CompactLayout]) -> Result<(Vec<Bin>, Vec<usize>), String> {
+        let [cl] = layouts else {
+            return Err(format!(
+                "expected exactly 1 layout, found {}",
+                layouts.len()
+            ));
+        };
+        let rect = &cl.bin_bbox;
+        if let OpenDimension::Height { width } = self.instance.open_dim {
+            if rect.width() != width {
+                return Err(format!(
+                    "container should have the fixed width of the instance ({width}), found {rect:?}"
+                ));
+            }
+        }
+        let bin = self.layout.bin().resize_strip(rect.clone());
+        Ok((vec![bin], self.bin_qtys().to_vec()))
+    }
+}
+
+/// Adjusts the dimensions of a container to the [OpenDimension]:
+/// a fixed width overrides the requested one,
+/// and for a fixed aspect ratio the smaller dimension is enlarged, so the container covers at least the requested size.
+fn constrain_dims(open_dim: OpenDimension, width: fsize, height: fsize) -> (fsize, fsize) {
+    match open_dim {
+        OpenDimension::Height { width: fixed_width } => (fixed_width, height),
+        OpenDimension::Area { aspect_ratio: None } => (width, height),
+        OpenDimension::Area {
+            aspect_ratio: Some(ratio),
+        } => {
+            let width = fsize::max(width, height * ratio);
+            (width, width / ratio)
+        }
+    }
+}
@@ -1,12 +1,16 @@
+use crate::entities::bin::Bin;
 use crate::entities::instances::instance_generic::InstanceGeneric;
 use crate::entities::layout::Layout;
 use crate::entities::placed_item::PItemKey;
 use crate::entities::placing_option::PlacingOption;
 use crate::entities::problems::bin_packing::BPProblem;
+use crate::entities::problems::knapsack::KPProblem;
+use crate::entities::problems::open_dimension::ODProblem;
 use crate::entities::problems::problem_generic::private::ProblemGenericPrivate;
-use crate::entities::problems::problem_generic::{LayoutIndex, ProblemGeneric};
+use crate::entities::problems::problem_generic::{LayoutIndex, ProblemGeneric, Transaction};
 use crate::entities::problems::strip_packing::SPProblem;
 use crate::entities::solution::Solution;
+use crate::io::compact_solution::CompactLayout;
 
 /// Enum which contains all the different problem types.
 /// A `Problem` represents a problem instance in a modifiable state.
@@ -15,12 +19,19 @@ use crate::entities::solution::Solution;
 /// <br>
 /// Also enables the use of match statements on the `Problem` enum when variant-specific behavior is required,
 /// When a new variant is added, compile errors will be generated everywhere specific behaviour is required.
+/// <br>
+/// Variants defined outside of this crate cannot be added to the enum,
+/// but can implement [ProblemGeneric] and [ProblemGenericPrivate] themselves and be used wherever a generic problem is accepted.
 #[derive(Clone)]
 pub enum Problem {
     /// Bin Packing Problem
     BP(BPProblem),
     /// Strip Packing Problem
     SP(SPProblem),
+    /// Knapsack Problem
+    KP(KPProblem),
+    /// Open Dimension Problem
+    OD(ODProblem),
 }
 
 impl ProblemGeneric for Problem {
@@ -28,6 +39,8 @@ impl ProblemGeneric for Problem {
         match self {
             Problem::BP(bp) => bp.place_item(p_opt),
             Problem::SP(sp) => sp.place_item(p_opt),
+            Problem::KP(kp) => kp.place_item(p_opt),
+            Problem::OD(od) => od.place_item(p_opt),
         }
     }
 
@@ -40,6 +53,8 @@ impl ProblemGeneric for Problem {
         match self {
             Problem::BP(bp) => bp.remove_item(layout_index, pik, commit_instantly),
             Problem::SP(sp) => sp.remove_item(layout_index, pik, commit_instantly),
+            Problem::KP(kp) => kp.remove_item(layout_index, pik, commit_instantly),
+            Problem::OD(od) => od.remove_item(layout_index, pik, commit_instantly),
         }
     }
 
@@ -47,6 +62,8 @@ impl ProblemGeneric for Problem {
         match self {
             Problem::BP(bp) => bp.create_solution(old_solution),
             Problem::SP(sp) => sp.create_solution(old_solution),
+            Problem::KP(kp) => kp.create_solution(old_solution),
+            Problem::OD(od) => od.create_solution(old_solution),
         }
     }
 
@@ -54,6 +71,8 @@ impl ProblemGeneric for Problem {
         match self {
             Problem::BP(bp) => bp.restore_to_solution(solution),
             Problem::SP(sp) => sp.restore_to_solution(solution),
+            Problem::KP(kp) => kp.restore_to_solution(solution),
+            Problem::OD(od) => od.restore_to_solution(solution),
         }
     }
 
@@ -61,6 +80,8 @@ impl ProblemGeneric for Problem {
         match self {
             Problem::BP(bp) => bp.layouts(),
             Problem::SP(sp) => sp.layouts(),
+            Problem::KP(kp) => kp.layouts(),
+            Problem::OD(od) => od.layouts(),
         }
     }
 
@@ -68,6 +89,8 @@ impl ProblemGeneric for Problem {
         match self {
             Problem::BP(bp) => bp.layouts_mut(),
             Problem::SP(sp) => sp.layouts_mut(),
+            Problem::KP(kp) => kp.layouts_mut(),
+            Problem::OD(od) => od.layouts_mut(),
         }
     }
 
@@ -75,6 +98,8 @@ impl ProblemGeneric for Problem {
         match self {
             Problem::BP(bp) => bp.template_layouts(),
             Problem::SP(sp) => sp.template_layouts(),
+            Problem::KP(kp) => kp.template_layouts(),
+            Problem::OD(od) => od.template_layouts(),
         }
     }
 
@@ -82,6 +107,8 @@ impl ProblemGeneric for Problem {
         match self {
             Problem::BP(bp) => bp.missing_item_qtys(),
             Problem::SP(sp) => sp.missing_item_qtys(),
+            Problem::KP(kp) => kp.missing_item_qtys(),
+            Problem::OD(od) => od.missing_item_qtys(),
         }
     }
 
@@ -89,6 +116,8 @@ impl ProblemGeneric for Problem {
         match self {
             Problem::BP(bp) => bp.bin_qtys(),
             Problem::SP(sp) => sp.bin_qtys(),
+            Problem::KP(kp) => kp.bin_qtys(),
+            Problem::OD(od) => od.bin_qtys(),
         }
     }
 
@@ -96,6 +125,8 @@ impl ProblemGeneric for Problem {
         match self {
             Problem::BP(bp) => bp.instance(),
             Problem::SP(sp) => sp.instance(),
+            Problem::KP(kp) => kp.instance(),
+            Problem::OD(od) => od.instance(),
         }
     }
 }
@@ -105,6 +136,8 @@ impl ProblemGenericPrivate for Problem {
         match self {
             Problem::BP(bp) => bp.next_solution_id(),
             Problem::SP(sp) => sp.next_solution_id(),
+            Problem::KP(kp) => kp.next_solution_id(),
+            Problem::OD(od) => od.next_solution_id(),
         }
     }
 
@@ -112,6 +145,8 @@ impl ProblemGenericPrivate for Problem {
         match self {
             Problem::BP(bp) => bp.next_layout_id(),
             Problem::SP(sp) => sp.next_layout_id(),
+            Problem::KP(kp) => kp.next_layout_id(),
+            Problem::OD(od) => od.next_layout_id(),
         }
     }
 
@@ -119,6 +154,44 @@ impl ProblemGenericPrivate for Problem {
         match self {
             Problem::BP(bp) => bp.missing_item_qtys_mut(),
             Problem::SP(sp) => sp.missing_item_qtys_mut(),
+            Problem::KP(kp) => kp.missing_item_qtys_mut(),
+            Problem::OD(od) => od.missing_item_qtys_mut(),
+        }
+    }
+
+    fn transaction_mut(&mut self) -> &mut Option<Transaction> {
+        match self {
+            Problem::BP(bp) => bp.transaction_mut(),
+            Problem::SP(sp) => sp.transaction_mut(),
+            Problem::KP(kp) => kp.transaction_mut(),
+            Problem::OD(od) => od.transaction_mut(),
+        }
+    }
+
+    fn commit_layouts(&mut self, modified_layouts: &[usize]) {
+        match self {
+            Problem::BP(bp) => bp.commit_layouts(modified_layouts),
+            Problem::SP(sp) => sp.commit_layouts(modified_layouts),
+            Problem::KP(kp) => kp.commit_layouts(modified_layouts),
+            Problem::OD(od) => od.commit_layouts(modified_layouts),
+        }
+    }
+
+    fn rollback_layouts(&mut self, n_layouts: usize, bin_qtys: &[usize]) {
+        match self {
+            Problem::BP(bp) => bp.rollback_layouts(n_layouts, bin_qtys),
+            Problem::SP(sp) => sp.rollback_layouts(n_layouts, bin_qtys),
+            Problem::KP(kp) => kp.rollback_layouts(n_layouts, bin_qtys),
+            Problem::OD(od) => od.rollback_layouts(n_layouts, bin_qtys),
+        }
+    }
+
+    fn import_bins(&self, layouts: &[CompactLayout]) -> Result<(Vec<Bin>, Vec<usize>), String> {
+        match self {
+            Problem::BP(bp) => bp.import_bins(layouts),
+            Problem::SP(sp) => sp.import_bins(layouts),
+            Problem::KP(kp) => kp.import_bins(layouts),
+            Problem::OD(od) => od.import_bins(layouts),
         }
     }
 }
@@ -134,3 +207,15 @@ impl From<SPProblem> for Problem {
         Problem::SP(sp)
     }
 }
+
+impl From<KPProblem> for Problem {
+    fn from(kp: KPProblem) -> Self {
+        Problem::KP(kp)
+    }
+}
+
+impl From<ODProblem> for Problem {
+    fn from(od: ODProblem) -> Self {
+        Problem::OD(od)
+    }
+}
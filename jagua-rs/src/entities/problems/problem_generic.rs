@@ -7,6 +7,9 @@ use crate::entities::placing_option::PlacingOption;
 use crate::entities::problems::problem_generic::private::ProblemGenericPrivate;
 use crate::entities::solution::Solution;
 use crate::fsize;
+use crate::geometry::geo_enums::AllowedMirroring;
+use crate::io::compact_solution::{CompactSolution, COMPACT_SOLUTION_VERSION};
+use crate::io::parse_error::ParseError;
 
 /// Trait for public shared functionality of all problem variants.
 pub trait ProblemGeneric: ProblemGenericPrivate {
@@ -97,10 +100,180 @@ pub trait ProblemGeneric: ProblemGenericPrivate {
     }
 
     fn instance(&self) -> &dyn InstanceGeneric;
+
+    /// Starts a transaction. All items placed and removed afterwards can be undone at once with [`Self::rollback`],
+    /// or accepted with [`Self::commit`].
+    /// Rolling back only updates the layouts and hazards modified during the transaction,
+    /// which is much cheaper than restoring a [`Solution`].
+    /// Removing items with `commit_instantly == false` makes it even cheaper, as their hazards are just reactivated.
+    /// <br>
+    /// Solutions should not be created or restored while a transaction is in progress.
+    fn begin(&mut self) {
+        assert!(
+            self.transaction_mut().is_none(),
+            "transaction already in progress"
+        );
+        self.layouts_mut().iter_mut().for_each(|l| l.begin());
+        let transaction = Transaction {
+            n_layouts: self.layouts().len(),
+            missing_item_qtys: self.missing_item_qtys().to_vec(),
+            bin_qtys: self.bin_qtys().to_vec(),
+        };
+        *self.transaction_mut() = Some(transaction);
+    }
+
+    /// Ends the transaction, keeping all changes made since [`Self::begin`].
+    fn commit(&mut self) {
+        let transaction = self
+            .transaction_mut()
+            .take()
+            .expect("no transaction in progress");
+        //layouts opened during the transaction count as modified
+        let modified_layouts = self
+            .layouts_mut()
+            .iter_mut()
+            .enumerate()
+            .filter_map(|(i, l)| {
+                let modified = i >= transaction.n_layouts || l.commit();
+                modified.then_some(i)
+            })
+            .collect::<Vec<_>>();
+        self.commit_layouts(&modified_layouts);
+    }
+
+    /// Ends the transaction, undoing all changes made since [`Self::begin`].
+    /// All items present at the start of the transaction keep their original [`PItemKey`].
+    fn rollback(&mut self) {
+        let transaction = self
+            .transaction_mut()
+            .take()
+            .expect("no transaction in progress");
+        self.rollback_layouts(transaction.n_layouts, &transaction.bin_qtys);
+        self.layouts_mut().iter_mut().for_each(|l| l.rollback());
+        self.missing_item_qtys_mut()
+            .copy_from_slice(&transaction.missing_item_qtys);
+    }
+
+    /// Rebuilds a [`Solution`] of this problem from a [`CompactSolution`], for example one saved by an earlier run.
+    /// The solution and its layouts receive new ids, so it can safely be passed to [`Self::restore_to_solution`].
+    /// Items are placed exactly as described, without checking for collisions,
+    /// but placements with a rotation or mirroring which the item does not allow are rejected.
+    fn import_solution(&mut self, compact: &CompactSolution) -> Result<Solution, ParseError> {
+        if compact.version != COMPACT_SOLUTION_VERSION {
+            return Err(ParseError::InvalidSolution(format!(
+                "unsupported version: {}, expected {}",
+                compact.version, COMPACT_SOLUTION_VERSION
+            )));
+        }
+        for (index, cl) in compact.layouts.iter().enumerate() {
+            let bbox = &cl.bin_bbox;
+            let valid_bbox = [bbox.x_min, bbox.y_min, bbox.x_max, bbox.y_max]
+                .iter()
+                .all(|v| v.is_finite())
+                && bbox.x_min < bbox.x_max
+                && bbox.y_min < bbox.y_max;
+            if !valid_bbox {
+                return Err(ParseError::InvalidLayout {
+                    index,
+                    reason: format!("invalid bin bounding box: {bbox:?}"),
+                });
+            }
+        }
+        let (bins, bin_qtys) = self
+            .import_bins(&compact.layouts)
+            .map_err(ParseError::InvalidSolution)?;
+
+        let n_items = self.instance().items().len();
+        let mut placed_item_qtys = vec![0; n_items];
+        let mut layout_snapshots = vec![];
+        for (index, (cl, bin)) in compact.layouts.iter().zip(bins).enumerate() {
+            let mut layout = Layout::new(self.next_layout_id(), bin);
+            for fi in layout.bin().fixed_items.iter() {
+                placed_item_qtys[fi.item_id] += 1;
+            }
+            for cpi in cl.placed_items.iter() {
+                if cpi.item_id >= n_items {
+                    return Err(ParseError::InvalidLayout {
+                        index,
+                        reason: format!("item {} does not exist", cpi.item_id),
+                    });
+                }
+                let item = self.instance().item(cpi.item_id);
+                let rotation = cpi.d_transf.rotation();
+                if !item.allowed_rotation.allows(rotation) {
+                    return Err(ParseError::InvalidLayout {
+                        index,
+                        reason: format!(
+                            "item {} is placed with a rotation of {:.3}°, which it does not allow",
+                            cpi.item_id,
+                            rotation.to_degrees()
+                        ),
+                    });
+                }
+                if cpi.d_transf.mirror() && item.allowed_mirroring == AllowedMirroring::None {
+                    return Err(ParseError::InvalidLayout {
+                        index,
+                        reason: format!(
+                            "item {} is mirrored, which it does not allow",
+                            cpi.item_id
+                        ),
+                    });
+                }
+                layout.place_item(item, cpi.d_transf);
+                placed_item_qtys[cpi.item_id] += 1;
+            }
+            layout_snapshots.push(layout.create_snapshot());
+        }
+        let target_item_qtys = (0..n_items)
+            .map(|i| self.instance().item_qty(i))
+            .collect::<Vec<_>>();
+        if let Some(i) = (0..n_items).find(|&i| placed_item_qtys[i] > target_item_qtys[i]) {
+            return Err(ParseError::InvalidSolution(format!(
+                "item {i} is placed {} times, but only {} are demanded",
+                placed_item_qtys[i], target_item_qtys[i]
+            )));
+        }
+
+        let (total_bin_area, total_used_area) =
+            layout_snapshots.iter().fold((0.0, 0.0), |acc, sl| {
+                let bin_area = sl.bin.area;
+                (acc.0 + bin_area, acc.1 + bin_area * sl.usage)
+            });
+        //a solution without any layouts does not use any area
+        let usage = match total_bin_area {
+            0.0 => 0.0,
+            _ => total_used_area / total_bin_area,
+        };
+        let mut solution = Solution::new(
+            self.next_solution_id(),
+            layout_snapshots,
+            usage,
+            placed_item_qtys,
+            target_item_qtys,
+            bin_qtys,
+        );
+        solution.time_stamp = compact.instant();
+        Ok(solution)
+    }
 }
 
-pub(super) mod private {
-    /// Trait for shared functionality of all problem variants, but not exposed to the public.
+/// State of a problem at the start of a transaction, see [`ProblemGeneric::begin`].
+#[derive(Clone, Debug)]
+pub struct Transaction {
+    /// Number of layouts at the start of the transaction
+    n_layouts: usize,
+    missing_item_qtys: Vec<isize>,
+    bin_qtys: Vec<usize>,
+}
+
+/// Contains the supertrait of [ProblemGeneric], which has to be implemented for custom problem variants as well.
+pub mod private {
+    use crate::entities::bin::Bin;
+    use crate::entities::problems::problem_generic::Transaction;
+    use crate::io::compact_solution::CompactLayout;
+
+    /// Trait for shared functionality of all problem variants, which is not meant to be used outside of the variants themselves.
+    /// Custom variants have to implement it alongside [ProblemGeneric](super::ProblemGeneric).
     pub trait ProblemGenericPrivate: Clone {
         fn next_solution_id(&mut self) -> usize;
 
@@ -108,6 +281,22 @@ pub(super) mod private {
 
         fn missing_item_qtys_mut(&mut self) -> &mut [isize];
 
+        /// The transaction in progress, if any
+        fn transaction_mut(&mut self) -> &mut Option<Transaction>;
+
+        /// Called when a transaction is committed, for variant-specific changes which are postponed until then.
+        /// `modified_layouts` contains the indices of the layouts which were modified or opened during the transaction, in increasing order.
+        fn commit_layouts(&mut self, _modified_layouts: &[usize]) {}
+
+        /// Called when a transaction is rolled back, before the modified layouts are restored.
+        /// Variants which can open new layouts should discard all layouts beyond `n_layouts` and restore the bin quantities.
+        fn rollback_layouts(&mut self, _n_layouts: usize, _bin_qtys: &[usize]) {}
+
+        /// Determines the bin of every layout in a [`CompactSolution`](crate::io::compact_solution::CompactSolution)
+        /// and the quantities of bins which remain available,
+        /// or describes why the layouts cannot belong to this problem.
+        fn import_bins(&self, layouts: &[CompactLayout]) -> Result<(Vec<Bin>, Vec<usize>), String>;
+
         fn register_included_item(&mut self, item_id: usize) {
             self.missing_item_qtys_mut()[item_id] -= 1;
         }
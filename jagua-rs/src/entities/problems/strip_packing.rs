@@ -9,11 +9,14 @@ use crate::entities::placed_item::PItemKey;
 use crate::entities::placing_option::PlacingOption;
 use crate::entities::problems::problem_generic::private::ProblemGenericPrivate;
 use crate::entities::problems::problem_generic::ProblemGeneric;
+use crate::entities::problems::problem_generic::Transaction;
 use crate::entities::problems::problem_generic::{LayoutIndex, STRIP_LAYOUT_IDX};
 use crate::entities::solution::Solution;
 use crate::fsize;
-use crate::geometry::geo_traits::{Shape, Transformable};
+use crate::geometry::geo_enums::GeoRelation;
+use crate::geometry::geo_traits::Shape;
 use crate::geometry::primitives::aa_rectangle::AARectangle;
+use crate::io::compact_solution::CompactLayout;
 use crate::util::assertions;
 use crate::util::config::CDEConfig;
 use crate::util::fpa::FPA;
@@ -28,18 +31,30 @@ pub struct SPProblem {
     missing_item_qtys: Vec<isize>,
     layout_id_counter: usize,
     solution_id_counter: usize,
+    transaction: Option<Transaction>,
 }
 
 impl SPProblem {
     pub fn new(instance: SPInstance, strip_width: fsize, cde_config: CDEConfig) -> Self {
         let strip_height = instance.strip_height;
-        let missing_item_qtys = instance
+        let mut missing_item_qtys = instance
             .items
             .iter()
             .map(|(_, qty)| *qty as isize)
             .collect_vec();
+        //fixed items count toward the demand from the start
+        for fi in instance.fixed_items.iter() {
+            missing_item_qtys[fi.item_id] -= 1;
+        }
+        //the strip should at least contain all fixed items
+        let strip_width = instance
+            .fixed_items
+            .iter()
+            .map(|fi| fi.bbox().x_max)
+            .fold(strip_width, fsize::max);
         let strip_rect = AARectangle::new(0.0, 0.0, strip_width, strip_height);
-        let strip_bin = Bin::from_strip(strip_rect, cde_config);
+        let strip_bin =
+            Bin::from_strip(strip_rect, cde_config).with_fixed_items(instance.fixed_items.clone());
         let layout_id_counter = 0;
         let layout = Layout::new(layout_id_counter, strip_bin);
 
@@ -49,6 +64,7 @@ impl SPProblem {
             missing_item_qtys,
             layout_id_counter,
             solution_id_counter: 0,
+            transaction: None,
         }
     }
 
@@ -90,57 +106,32 @@ impl SPProblem {
 
     /// Modifies the shape of the strip to a new rectangle.
     /// All items that fit in the new strip are kept, the rest are removed.
+    /// The new strip should still contain all fixed items.
+    /// The strip cannot be modified during a transaction, as the resize cannot be rolled back.
     pub fn modify_strip(&mut self, rect: AARectangle) {
-        let placed_items = self
+        assert!(
+            self.transaction.is_none(),
+            "cannot modify the strip during a transaction"
+        );
+        resize_strip_layout(&mut self.layout, rect);
+        //snapshots of the layout taken before the resize no longer match its bin
+        self.layout.id = self.next_layout_id();
+
+        //items which no longer fit are missing again
+        let placed_qtys = self
             .layout
             .placed_items()
-            .iter()
-            .map(|(_, pi)| (pi.item_id, pi.d_transf))
-            .collect_vec();
-
-        //reset the missing item quantities
+            .values()
+            .chain(self.layout.bin().fixed_items.iter())
+            .map(|pi| pi.item_id)
+            .counts();
         self.missing_item_qtys
             .iter_mut()
             .enumerate()
-            .for_each(|(i, qty)| *qty = self.instance.item_qty(i) as isize);
-
-        //Modifying the width causes the bin to change, so the layout must be replaced
-        self.layout = Layout::new(
-            self.next_layout_id(),
-            Bin::from_strip(rect, self.layout.bin().base_cde.config()),
-        );
-
-        //place the items back in the new layout
-        for (item_id, d_transf) in placed_items {
-            let item = self.instance.item(item_id);
-            let entities_to_ignore = self
-                .layout
-                .cde()
-                .all_hazards()
-                .filter(|h| h.entity != HazardEntity::BinExterior)
-                .map(|h| h.entity)
-                .collect_vec();
-            let shape = &item.shape;
-            let transform = d_transf.compose();
-            let transformed_shape = shape.transform_clone(&transform);
-            let cde = self.layout.cde();
-            if !cde.poly_collides(&transformed_shape, entities_to_ignore.as_ref()) {
-                let insert_opt = PlacingOption {
-                    layout_idx: STRIP_LAYOUT_IDX,
-                    item_id,
-                    d_transf,
-                };
-                self.place_item(insert_opt);
-            } else {
-                let mut collisions = vec![];
-                cde.collect_poly_collisions(
-                    &transformed_shape,
-                    entities_to_ignore.as_ref(),
-                    &mut collisions,
-                );
-                error!("Item {} could not be placed back in the strip after resizing. Collisions: {:?}", item_id, collisions);
-            }
-        }
+            .for_each(|(i, qty)| {
+                *qty = self.instance.item_qty(i) as isize
+                    - placed_qtys.get(&i).copied().unwrap_or(0) as isize
+            });
     }
 
     /// Shrinks the strip to the minimum width that fits all items.
@@ -296,19 +287,53 @@ impl ProblemGenericPrivate for SPProblem {
     fn missing_item_qtys_mut(&mut self) -> &mut [isize] {
         &mut self.missing_item_qtys
     }
+
+    fn transaction_mut(&mut self) -> &mut Option<Transaction> {
+        &mut self.transaction
+    }
+
+    fn import_bins(&self, layouts: &[CompactLayout]) -> Result<(Vec<Bin>, Vec<usize>), String> {
+        let [cl] = layouts else {
+            return Err(format!(
+                "expected exactly 1 layout, found {}",
+                layouts.len()
+            ));
+        };
+        let rect = &cl.bin_bbox;
+        if rect.y_min != 0.0 || rect.y_max != self.strip_height() {
+            return Err(format!(
+                "strip should span the height of the instance ({}), found {rect:?}",
+                self.strip_height()
+            ));
+        }
+        let contains_fixed_items = self
+            .instance
+            .fixed_items
+            .iter()
+            .all(|fi| rect.relation_to(&fi.bbox()) == GeoRelation::Surrounding);
+        if !contains_fixed_items {
+            return Err(format!("strip {rect:?} does not contain all fixed items"));
+        }
+        let bin = self.layout.bin().resize_strip(rect.clone());
+        Ok((vec![bin], self.bin_qtys().to_vec()))
+    }
 }
 
-/// Returns the horizontal range occupied by the placed items. If no items are placed, returns None.
+/// Returns the horizontal range occupied by the placed and fixed items. If there are none, returns None.
 pub fn occupied_range(layout: &Layout) -> Option<(fsize, fsize)> {
-    if layout.placed_items().is_empty() {
+    if layout.placed_items().is_empty() && layout.bin().fixed_items.is_empty() {
         return None;
     }
 
     let mut min_x = fsize::MAX;
     let mut max_x = fsize::MIN;
 
-    for pi in layout.placed_items().values() {
-        let bbox = pi.shape.bbox();
+    for pi in layout
+        .placed_items()
+        .values()
+        .chain(layout.bin().fixed_items.iter())
+    {
+        let bbox = pi.bbox();
         min_x = min_x.min(bbox.x_min);
         max_x = max_x.max(bbox.x_max);
     }
@@ -324,3 +349,37 @@ pub fn occupied_width(layout: &Layout) -> fsize {
         None => 0.0,
     }
 }
+
+/// Resizes the bin of `layout` to a rectangular strip, in place.
+/// Items which no longer fit in the new strip are removed, all others are left untouched and keep their keys.
+/// Custom hazards are not part of the bin, so they are unaffected.
+pub fn resize_strip_layout(layout: &mut Layout, rect: AARectangle) {
+    let new_bin = layout.bin().resize_strip(rect.clone());
+    layout.change_bin_exterior(new_bin);
+
+    //only the exterior of the bin changed, so it is the only hazard the items need to be checked against
+    let entities_to_ignore = layout
+        .cde()
+        .all_hazards()
+        .filter(|h| h.entity != HazardEntity::BinExterior)
+        .map(|h| h.entity)
+        .collect_vec();
+    let keys_to_remove = layout
+        .placed_items()
+        .iter()
+        .filter(|(_, pi)| rect.relation_to(&pi.bbox()) != GeoRelation::Surrounding)
+        .filter(|(_, pi)| {
+            pi.shapes()
+                .any(|shape| layout.cde().poly_collides(shape, &entities_to_ignore))
+        })
+        .map(|(pik, _)| pik)
+        .collect_vec();
+
+    for pik in keys_to_remove {
+        let pi = layout.remove_item(pik, true);
+        error!(
+            "Item {} no longer fits in the strip after resizing, it was removed",
+            pi.item_id
+        );
+    }
+}
@@ -10,7 +10,7 @@ pub const N_QUALITIES: usize = 10;
 pub struct InferiorQualityZone {
     /// Higher quality is better
     pub quality: usize,
-    /// The outer shapes of all zones of this quality
+    /// The shapes of all zones of this quality, possibly with holes of full quality material
     pub zones: Vec<Arc<SimplePolygon>>,
 }
 
@@ -6,7 +6,6 @@ use crate::entities::instances::instance::Instance;
 use crate::entities::instances::instance_generic::InstanceGeneric;
 use crate::entities::layout::LayoutSnapshot;
 use crate::fsize;
-use crate::geometry::geo_traits::Shape;
 
 /// Represents a snapshot of a `Problem` at a specific moment.
 /// Solutions can be used to restore the state of a `Problem` to a previous state.
@@ -63,7 +62,7 @@ impl Solution {
             .placed_item_qtys
             .iter()
             .enumerate()
-            .map(|(i, qty)| instance.item(i).shape.area() * *qty as fsize)
+            .map(|(i, qty)| instance.item(i).area() * *qty as fsize)
             .sum::<fsize>();
         included_item_area / total_item_area
     }
@@ -81,4 +80,13 @@ impl Solution {
     pub fn n_items_placed(&self) -> usize {
         self.placed_item_qtys.iter().sum()
     }
+
+    /// Total value of the placed items
+    pub fn placed_item_value(&self, instance: &dyn InstanceGeneric) -> u64 {
+        self.placed_item_qtys
+            .iter()
+            .enumerate()
+            .map(|(i, &qty)| instance.item(i).value * qty as u64)
+            .sum()
+    }
 }
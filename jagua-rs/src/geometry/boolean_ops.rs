@@ -0,0 +1,316 @@
+use std::collections::HashSet;
+
+use itertools::Itertools;
+
+use crate::fsize;
+use crate::geometry::geo_traits::{CollidesWith, DistanceFrom, Shape};
+use crate::geometry::primitives::aa_rectangle::AARectangle;
+use crate::geometry::primitives::edge::Edge;
+use crate::geometry::primitives::point::Point;
+use crate::geometry::primitives::simple_polygon::SimplePolygon;
+use crate::util::fpa::FPA;
+use crate::util::polygon_validation;
+
+/// Boolean operation between two sets of polygons
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BooleanOp {
+    /// Area covered by either operand
+    Union,
+    /// Area covered by both operands
+    Intersection,
+    /// Area covered by the first operand, but not by the second
+    Difference,
+}
+
+/// Part of a split edge, as the indices of its start and end vertex
+type EdgePart = (usize, usize);
+
+/// Position of a (split) edge of one operand relative to the other operand
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum EdgePosition {
+    Inside,
+    Outside,
+    /// The other operand contains the same edge, in the same direction
+    Shared,
+    /// The other operand contains the same edge, in the opposite direction
+    SharedOpposite,
+}
+
+pub fn union(a: &SimplePolygon, b: &SimplePolygon) -> Vec<SimplePolygon> {
+    boolean_op(
+        std::slice::from_ref(a),
+        std::slice::from_ref(b),
+        BooleanOp::Union,
+    )
+}
+
+pub fn intersection(a: &SimplePolygon, b: &SimplePolygon) -> Vec<SimplePolygon> {
+    boolean_op(
+        std::slice::from_ref(a),
+        std::slice::from_ref(b),
+        BooleanOp::Intersection,
+    )
+}
+
+pub fn difference(a: &SimplePolygon, b: &SimplePolygon) -> Vec<SimplePolygon> {
+    boolean_op(
+        std::slice::from_ref(a),
+        std::slice::from_ref(b),
+        BooleanOp::Difference,
+    )
+}
+
+/// Merges a set of possibly overlapping polygons into a set of disjoint polygons.
+/// Both halves of the set are merged recursively, which keeps the intermediate results small.
+pub fn union_all(polygons: &[SimplePolygon]) -> Vec<SimplePolygon> {
+    match polygons.len() {
+        0 | 1 => boolean_op(polygons, &[], BooleanOp::Union),
+        n => {
+            let (left, right) = polygons.split_at(n / 2);
+            boolean_op(&union_all(left), &union_all(right), BooleanOp::Union)
+        }
+    }
+}
+
+/// Performs a boolean operation between two sets of polygons, which can have holes.
+/// The polygons within a single operand should not overlap each other.
+/// Returns a set of disjoint polygons, with holes where the result requires them.
+///
+/// All edges are split at their intersections with the edges of the other operand,
+/// after which every part is classified as inside, outside or shared with the other operand.
+/// Depending on the operation, the relevant parts are linked back into closed contours.
+/// Points within [FPA::tolerance] (relative to the size of the operands) of each other are considered equal.
+pub fn boolean_op(a: &[SimplePolygon], b: &[SimplePolygon], op: BooleanOp) -> Vec<SimplePolygon> {
+    let eps = match a
+        .iter()
+        .chain(b.iter())
+        .map(|p| p.bbox())
+        .reduce(|r1, r2| AARectangle::bounding_rectangle(&r1, &r2))
+    {
+        Some(bbox) => FPA::tolerance() * (bbox.width().powi(2) + bbox.height().powi(2)).sqrt(),
+        None => return vec![],
+    };
+
+    let (edges_a, edges_b) = (region_edges(a), region_edges(b));
+    let mut vertices = vec![];
+    let (parts_a, parts_b) = split_edges(&edges_a, &edges_b, eps, &mut vertices);
+
+    let set_a: HashSet<EdgePart> = parts_a.iter().copied().collect();
+    let set_b: HashSet<EdgePart> = parts_b.iter().copied().collect();
+    let classify = |(u, v): EdgePart, other_set: &HashSet<EdgePart>, other: &[SimplePolygon]| {
+        if other_set.contains(&(u, v)) {
+            EdgePosition::Shared
+        } else if other_set.contains(&(v, u)) {
+            EdgePosition::SharedOpposite
+        } else {
+            let (p, q) = (vertices[u], vertices[v]);
+            let midpoint = Point((p.0 + q.0) / 2.0, (p.1 + q.1) / 2.0);
+            match other.iter().any(|poly| poly.collides_with(&midpoint)) {
+                true => EdgePosition::Inside,
+                false => EdgePosition::Outside,
+            }
+        }
+    };
+
+    //select the parts which make up the boundary of the result, with the result on their left side
+    let mut selected = vec![];
+    for &(u, v) in &parts_a {
+        let keep = match classify((u, v), &set_b, b) {
+            EdgePosition::Inside => op == BooleanOp::Intersection,
+            EdgePosition::Outside => op != BooleanOp::Intersection,
+            EdgePosition::Shared => op != BooleanOp::Difference,
+            EdgePosition::SharedOpposite => op == BooleanOp::Difference,
+        };
+        if keep {
+            selected.push((u, v));
+        }
+    }
+    for &(u, v) in &parts_b {
+        //shared parts were already handled from the side of the first operand
+        match (classify((u, v), &set_a, a), op) {
+            (EdgePosition::Inside, BooleanOp::Intersection) => selected.push((u, v)),
+            (EdgePosition::Inside, BooleanOp::Difference) => selected.push((v, u)),
+            (EdgePosition::Outside, BooleanOp::Union) => selected.push((u, v)),
+            _ => {}
+        }
+    }
+
+    let contours = link_contours(&selected, vertices.len())
+        .into_iter()
+        .map(|c| c.into_iter().map(|i| vertices[i]).collect_vec())
+        .flat_map(|c| polygon_validation::repair(&c, FPA::tolerance()))
+        .collect_vec();
+
+    assemble_polygons(contours)
+}
+
+/// All edges of a set of polygons, oriented such that the polygons are on their left side
+fn region_edges(polygons: &[SimplePolygon]) -> Vec<Edge> {
+    polygons
+        .iter()
+        .flat_map(|p| {
+            let holes = p
+                .holes
+                .iter()
+                .flat_map(|h| h.edge_iter().map(|e| e.reverse()));
+            p.edge_iter().chain(holes)
+        })
+        .collect_vec()
+}
+
+/// Splits the edges of both operands at every point where they touch or cross each other.
+/// Every intersection is computed once and shared by both edges, so they split at exactly the same point.
+/// Returns the parts as pairs of indices in `vertices`.
+fn split_edges(
+    edges_a: &[Edge],
+    edges_b: &[Edge],
+    eps: fsize,
+    vertices: &mut Vec<Point>,
+) -> (Vec<EdgePart>, Vec<EdgePart>) {
+    let initial_splits = |e: &Edge| vec![(0.0, e.start), (1.0, e.end)];
+    let mut splits_a = edges_a.iter().map(initial_splits).collect_vec();
+    let mut splits_b = edges_b.iter().map(initial_splits).collect_vec();
+
+    for (i, ea) in edges_a.iter().enumerate() {
+        for (j, eb) in edges_b.iter().enumerate() {
+            if ea.x_min() - eps > eb.x_max()
+                || ea.x_max() + eps < eb.x_min()
+                || ea.y_min() - eps > eb.y_max()
+                || ea.y_max() + eps < eb.y_min()
+            {
+                continue;
+            }
+            //endpoints of one edge which lie on the other (T-junctions and collinear overlaps)
+            let mut touching = false;
+            for p in [eb.start, eb.end] {
+                if ea.distance(&p) <= eps {
+                    splits_a[i].push((edge_param(ea, p), p));
+                    touching = true;
+                }
+            }
+            for p in [ea.start, ea.end] {
+                if eb.distance(&p) <= eps {
+                    splits_b[j].push((edge_param(eb, p), p));
+                    touching = true;
+                }
+            }
+            if !touching {
+                if let Some(p) = ea.collides_at(eb) {
+                    splits_a[i].push((edge_param(ea, p), p));
+                    splits_b[j].push((edge_param(eb, p), p));
+                }
+            }
+        }
+    }
+
+    let mut to_parts = |splits: Vec<Vec<(fsize, Point)>>| {
+        let mut parts = vec![];
+        for mut edge_splits in splits {
+            edge_splits.sort_by(|(t1, _), (t2, _)| t1.partial_cmp(t2).unwrap());
+            let ids = edge_splits
+                .into_iter()
+                .map(|(_, p)| vertex_id(vertices, p, eps))
+                .dedup()
+                .collect_vec();
+            parts.extend(ids.into_iter().tuple_windows().filter(|(u, v)| u != v));
+        }
+        parts
+    };
+    let parts_a = to_parts(splits_a);
+    let parts_b = to_parts(splits_b);
+    (parts_a, parts_b)
+}
+
+/// Position of the projection of `p` on the edge, 0.0 at the start and 1.0 at the end
+fn edge_param(edge: &Edge, p: Point) -> fsize {
+    let (dx, dy) = (edge.end.0 - edge.start.0, edge.end.1 - edge.start.1);
+    ((p.0 - edge.start.0) * dx + (p.1 - edge.start.1) * dy) / (dx * dx + dy * dy)
+}
+
+/// Index of the vertex within `eps` of `p`, or a new vertex if there is none
+fn vertex_id(vertices: &mut Vec<Point>, p: Point, eps: fsize) -> usize {
+    match vertices.iter().position(|v| v.distance(p) <= eps) {
+        Some(id) => id,
+        None => {
+            vertices.push(p);
+            vertices.len() - 1
+        }
+    }
+}
+
+/// Links directed edges into closed contours, which are split wherever they visit the same vertex twice
+fn link_contours(edges: &[EdgePart], n_vertices: usize) -> Vec<Vec<usize>> {
+    let mut outgoing = vec![vec![]; n_vertices];
+    for &(u, v) in edges.iter().rev() {
+        outgoing[u].push(v);
+    }
+
+    let mut contours = vec![];
+    for start in 0..n_vertices {
+        while let Some(mut next) = outgoing[start].pop() {
+            let mut path = vec![start];
+            while next != start {
+                path.push(next);
+                match outgoing[next].pop() {
+                    Some(n) => next = n,
+                    //the edges do not form closed contours, due to numerical issues
+                    None => break,
+                }
+            }
+            if next == start {
+                contours.extend(split_at_revisits(path));
+            }
+        }
+    }
+    contours
+}
+
+/// Splits a closed path into simple closed paths at every vertex which is visited more than once
+fn split_at_revisits(path: Vec<usize>) -> Vec<Vec<usize>> {
+    let mut loops = vec![];
+    let mut stack: Vec<usize> = vec![];
+    for v in path {
+        if let Some(pos) = stack.iter().position(|&s| s == v) {
+            loops.push(stack.split_off(pos));
+        }
+        stack.push(v);
+    }
+    loops.push(stack);
+    loops.into_iter().filter(|l| l.len() >= 3).collect_vec()
+}
+
+/// Turns counterclockwise contours into polygons and clockwise contours into their holes
+fn assemble_polygons(contours: Vec<Vec<Point>>) -> Vec<SimplePolygon> {
+    let (outer_contours, hole_contours): (Vec<_>, Vec<_>) = contours
+        .into_iter()
+        .partition(|c| SimplePolygon::calculate_area(c) > 0.0);
+
+    let outers = outer_contours
+        .into_iter()
+        .filter_map(|c| SimplePolygon::try_new(c).ok())
+        .collect_vec();
+    let mut holes = vec![vec![]; outers.len()];
+    for hole in hole_contours {
+        let Ok(hole) = SimplePolygon::try_new(hole) else {
+            continue;
+        };
+        //the smallest polygon containing the boundary of the hole
+        let (p, q) = (hole.points[0], hole.points[1]);
+        let probe = Point((p.0 + q.0) / 2.0, (p.1 + q.1) / 2.0);
+        let container = outers
+            .iter()
+            .enumerate()
+            .filter(|(_, o)| o.collides_with(&probe))
+            .min_by(|(_, o1), (_, o2)| o1.area().partial_cmp(&o2.area()).unwrap())
+            .map(|(i, _)| i);
+        if let Some(i) = container {
+            holes[i].push(hole);
+        }
+    }
+
+    outers
+        .into_iter()
+        .zip(holes)
+        .filter_map(|(outer, holes)| SimplePolygon::try_with_holes(outer.points, holes).ok())
+        .collect_vec()
+}
@@ -31,10 +31,13 @@ pub fn convex_hull_from_shapes<'a>(
 pub fn convex_hull_from_points(mut points: Vec<Point>) -> Vec<Point> {
     //https://en.wikibooks.org/wiki/Algorithm_Implementation/Geometry/Convex_hull/Monotone_chain
 
-    //sort the points by x coordinate
+    //sort the points by x coordinate, ties are broken by y coordinate
     points.sort_by(|a, b| {
         let (a_x, b_x) = (a.0, b.0);
-        a_x.partial_cmp(&b_x).unwrap()
+        let (a_y, b_y) = (a.1, b.1);
+        a_x.partial_cmp(&b_x)
+            .unwrap()
+            .then(a_y.partial_cmp(&b_y).unwrap())
     });
 
     let mut lower_hull = points
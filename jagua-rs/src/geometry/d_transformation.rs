@@ -2,17 +2,21 @@ use std::borrow::Borrow;
 use std::fmt::Display;
 
 use ordered_float::NotNan;
+use serde::{Deserialize, Serialize};
 
 use crate::fsize;
 use crate::geometry::transformation::Transformation;
 
-#[derive(Clone, Debug, PartialEq, Eq, Hash, Copy)]
-/// A proper rigid transformation, decomposed into a rotation followed by a translation.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Copy, Serialize, Deserialize)]
+/// A rigid transformation, decomposed into an optional mirroring across the x-axis, followed by a rotation and a translation.
+/// Without mirroring, it is a proper rigid transformation.
 pub struct DTransformation {
     /// The rotation in radians
     pub rotation: NotNan<fsize>,
     /// The translation in the x and y-axis
     pub translation: (NotNan<fsize>, NotNan<fsize>),
+    /// Whether the shape is mirrored across the x-axis (y ↦ -y) before being rotated
+    pub mirror: bool,
 }
 
 impl DTransformation {
@@ -23,14 +27,22 @@ impl DTransformation {
                 NotNan::new(translation.0).expect("translation.0 is NaN"),
                 NotNan::new(translation.1).expect("translation.1 is NaN"),
             ),
+            mirror: false,
         }
     }
 
+    /// Returns the same transformation, with or without mirroring before the rotation
+    pub fn with_mirror(mut self, mirror: bool) -> Self {
+        self.mirror = mirror;
+        self
+    }
+
     pub const fn empty() -> Self {
         const _0: NotNan<fsize> = unsafe { NotNan::new_unchecked(0.0) };
         Self {
             rotation: _0,
             translation: (_0, _0),
+            mirror: false,
         }
     }
 
@@ -42,6 +54,10 @@ impl DTransformation {
         (self.translation.0.into(), self.translation.1.into())
     }
 
+    pub fn mirror(&self) -> bool {
+        self.mirror
+    }
+
     pub fn compose(&self) -> Transformation {
         Transformation::from_dt(self)
     }
@@ -64,6 +80,10 @@ impl Display for DTransformation {
             self.rotation.to_degrees(),
             self.translation.0.into_inner(),
             self.translation.1.into_inner()
-        )
+        )?;
+        if self.mirror {
+            write!(f, ", mirrored")?;
+        }
+        Ok(())
     }
 }
@@ -0,0 +1,112 @@
+use std::ops::Range;
+
+use crate::fsize;
+use crate::geometry::primitives::aa_rectangle::AARectangle;
+use crate::geometry::primitives::simple_polygon::SimplePolygon;
+
+/// Polygons with fewer border edges than this do not get an [EdgeBVH], testing their edges one by one is cheap enough
+pub const EDGE_BVH_MIN_EDGES: usize = 32;
+
+/// Maximum number of edges in a leaf of an [EdgeBVH]
+const LEAF_SIZE: usize = 8;
+
+/// Bounding volume hierarchy over the border edges of a [SimplePolygon].
+/// Every node covers a chain of consecutive edges (in the order of [SimplePolygon::border_edge_iter]),
+/// which tend to be spatially coherent. This allows entire chains to be rejected at once during collision detection.
+/// <br>
+/// The structure of the tree only depends on the number of edges, so it survives transformations of the polygon.
+/// Only the bounding boxes have to be refitted, see [EdgeBVH::refit].
+#[derive(Clone, Debug)]
+pub struct EdgeBVH {
+    /// All nodes of the tree, the root is at index 0
+    pub nodes: Vec<EdgeBVHNode>,
+}
+
+#[derive(Clone, Debug)]
+pub struct EdgeBVHNode {
+    /// Bounding box of all edges in the chain
+    pub bbox: AARectangle,
+    /// Indices of the border edges in the chain
+    pub edges: Range<usize>,
+    /// Indices of the two nodes splitting the chain in half, `None` for leaves
+    pub children: Option<[usize; 2]>,
+}
+
+impl EdgeBVH {
+    /// Builds the hierarchy for a polygon, returns `None` if it has fewer than [EDGE_BVH_MIN_EDGES] border edges
+    pub fn new(shape: &SimplePolygon) -> Option<Self> {
+        let n_edges = shape.number_of_border_edges();
+        if n_edges < EDGE_BVH_MIN_EDGES {
+            return None;
+        }
+        let mut bvh = EdgeBVH { nodes: vec![] };
+        bvh.build(shape, 0..n_edges);
+        Some(bvh)
+    }
+
+    pub fn root(&self) -> &EdgeBVHNode {
+        &self.nodes[0]
+    }
+
+    /// Recomputes the bounding boxes of all nodes, after the polygon has been transformed
+    pub fn refit(&mut self, shape: &SimplePolygon) {
+        //children are always stored after their parent, so they are refitted first
+        for i in (0..self.nodes.len()).rev() {
+            let bbox = match self.nodes[i].children {
+                None => chain_bbox(shape, self.nodes[i].edges.clone()),
+                Some([l, r]) => union_bbox(&self.nodes[l].bbox, &self.nodes[r].bbox),
+            };
+            self.nodes[i].bbox = bbox;
+        }
+    }
+
+    fn build(&mut self, shape: &SimplePolygon, edges: Range<usize>) -> usize {
+        let index = self.nodes.len();
+        self.nodes.push(EdgeBVHNode {
+            bbox: chain_bbox(shape, edges.clone()),
+            edges: edges.clone(),
+            children: None,
+        });
+        if edges.len() > LEAF_SIZE {
+            let mid = edges.start + edges.len() / 2;
+            let left = self.build(shape, edges.start..mid);
+            let right = self.build(shape, mid..edges.end);
+            self.nodes[index].children = Some([left, right]);
+        }
+        index
+    }
+}
+
+/// Bounding box of a chain of edges, which can be degenerate (zero width or height) for axis-aligned chains
+fn chain_bbox(shape: &SimplePolygon, edges: Range<usize>) -> AARectangle {
+    let (x_min, y_min, x_max, y_max) = edges
+        .map(|i| shape.get_border_edge(i))
+        .flat_map(|e| [e.start, e.end])
+        .fold(
+            (fsize::MAX, fsize::MAX, fsize::MIN, fsize::MIN),
+            |(x_min, y_min, x_max, y_max), p| {
+                (
+                    x_min.min(p.0),
+                    y_min.min(p.1),
+                    x_max.max(p.0),
+                    y_max.max(p.1),
+                )
+            },
+        );
+    AARectangle {
+        x_min,
+        y_min,
+        x_max,
+        y_max,
+    }
+}
+
+/// Bounding box of two (possibly degenerate) bounding boxes
+fn union_bbox(a: &AARectangle, b: &AARectangle) -> AARectangle {
+    AARectangle {
+        x_min: a.x_min.min(b.x_min),
+        y_min: a.y_min.min(b.y_min),
+        x_max: a.x_max.max(b.x_max),
+        y_max: a.y_max.max(b.y_max),
+    }
+}
@@ -122,7 +122,7 @@ fn clip(shape: &SimplePolygon, ray: &Edge) -> Vec<Edge> {
 
     //collect all intersections of the ray with the shape, sorted by distance to the ray's start
     let intersections = shape
-        .edge_iter()
+        .border_edge_iter()
         .flat_map(|edge| edge.collides_at(ray))
         .sorted_by_key(|p| NotNan::new(ray.start.distance(*p)).unwrap())
         .collect_vec();
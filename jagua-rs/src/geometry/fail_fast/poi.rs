@@ -104,7 +104,9 @@ impl POINode {
             && poles.iter().all(|c| !c.collides_with(&bbox.centroid()));
 
         let distance = {
-            let distance_to_edges = poly.edge_iter().map(|e| e.distance(&bbox.centroid()));
+            let distance_to_edges = poly
+                .border_edge_iter()
+                .map(|e| e.distance(&bbox.centroid()));
 
             let distance_to_poles = poles
                 .iter()
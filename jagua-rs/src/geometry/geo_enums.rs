@@ -1,4 +1,4 @@
-use crate::fsize;
+use crate::{fsize, PI};
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum GeoPosition {
@@ -28,4 +28,39 @@ pub enum AllowedRotation {
     Continuous,
     /// Only a limited set of rotations is allowed
     Discrete(Vec<fsize>),
+    /// Only rotations within one of the (inclusive) ranges are allowed
+    Ranges(Vec<(fsize, fsize)>),
+}
+
+impl AllowedRotation {
+    /// Whether a rotation (in radians) is allowed, rotations which differ by a full turn are considered equal
+    pub fn allows(&self, rotation: fsize) -> bool {
+        //smallest absolute difference between two rotations, in [0, π]
+        let angular_distance =
+            |r1: fsize, r2: fsize| ((r1 - r2 + PI).rem_euclid(2.0 * PI) - PI).abs();
+        match self {
+            AllowedRotation::None => angular_distance(rotation, 0.0) <= ROTATION_TOLERANCE,
+            AllowedRotation::Continuous => true,
+            AllowedRotation::Discrete(rotations) => rotations
+                .iter()
+                .any(|&r| angular_distance(rotation, r) <= ROTATION_TOLERANCE),
+            AllowedRotation::Ranges(ranges) => ranges.iter().any(|&(min, max)| {
+                //bring the rotation within half a turn of the range's center
+                let center = (min + max) / 2.0;
+                let r = center + (rotation - center + PI).rem_euclid(2.0 * PI) - PI;
+                r >= min - ROTATION_TOLERANCE && r <= max + ROTATION_TOLERANCE
+            }),
+        }
+    }
+}
+
+/// Rotations (in radians) closer than this are considered equal
+const ROTATION_TOLERANCE: fsize = 1e-4;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AllowedMirroring {
+    /// The item can only be placed as it is defined
+    None,
+    /// The item can be placed both as it is defined and mirrored
+    Optional,
 }
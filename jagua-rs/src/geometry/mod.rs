@@ -1,8 +1,12 @@
+pub mod boolean_ops;
 pub mod convex_hull;
 
 pub mod d_transformation;
+pub mod edge_bvh;
 pub mod fail_fast;
 pub mod geo_enums;
 pub mod geo_traits;
+pub mod nfp;
+pub mod offset;
 pub mod primitives;
 pub mod transformation;
@@ -0,0 +1,422 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use itertools::Itertools;
+use ordered_float::NotNan;
+
+use crate::entities::bin::Bin;
+use crate::entities::item::Item;
+use crate::geometry::boolean_ops::{self, BooleanOp};
+use crate::geometry::convex_hull::convex_hull_from_points;
+use crate::geometry::geo_traits::{CollidesWith, Shape, Transformable};
+use crate::geometry::primitives::aa_rectangle::AARectangle;
+use crate::geometry::primitives::point::Point;
+use crate::geometry::primitives::simple_polygon::SimplePolygon;
+use crate::geometry::transformation::Transformation;
+use crate::util::predicates;
+use crate::{fsize, PI};
+
+/// Computes the [no-fit polygon](https://doi.org/10.1016/j.ejor.2006.03.011) (NFP) of two polygons at the given rotations.
+/// The NFP is the set of positions of the reference point of `orbiting` for which it overlaps with `fixed`,
+/// where `fixed` is rotated around its own reference point, without translation.
+/// Positions on the boundary of the NFP are those where both polygons touch.
+/// <br>
+/// Returns a set of disjoint polygons, with holes where `orbiting` fits in a hole or enclosed concavity of `fixed` (or vice versa).
+pub fn nfp(
+    fixed: &SimplePolygon,
+    fixed_rotation: fsize,
+    orbiting: &SimplePolygon,
+    orbiting_rotation: fsize,
+) -> Vec<SimplePolygon> {
+    nfp_of_sets(
+        &[rotate(fixed, fixed_rotation)],
+        &[rotate(orbiting, orbiting_rotation)],
+    )
+}
+
+/// Computes the inner-fit polygon (IFP) of a polygon in a bin at the given rotation.
+/// The IFP is the set of positions of the reference point of `item` for which it lies entirely inside the bin,
+/// without overlapping any of its holes. Quality zones are not taken into account.
+/// <br>
+/// Returns a set of disjoint polygons, which is empty if the polygon does not fit in the bin.
+pub fn ifp(bin: &Bin, item: &SimplePolygon, rotation: fsize) -> Vec<SimplePolygon> {
+    ifp_of_set(bin, &[rotate(item, rotation)])
+}
+
+/// Computes the [Minkowski sum](https://en.wikipedia.org/wiki/Minkowski_addition) of two sets of polygons, which can have holes.
+/// Both sets are decomposed into convex pieces, whose pairwise sums are merged with [boolean_ops::union_all].
+pub fn minkowski_sum(a: &[SimplePolygon], b: &[SimplePolygon]) -> Vec<SimplePolygon> {
+    let pieces_a = a.iter().flat_map(convex_decomposition).collect_vec();
+    let pieces_b = b.iter().flat_map(convex_decomposition).collect_vec();
+
+    let sums = pieces_a
+        .iter()
+        .cartesian_product(pieces_b.iter())
+        .filter_map(|(pa, pb)| {
+            let points = pa
+                .iter()
+                .cartesian_product(pb.iter())
+                .map(|(p, q)| Point(p.0 + q.0, p.1 + q.1))
+                .collect_vec();
+            SimplePolygon::try_new(convex_hull_from_points(points)).ok()
+        })
+        .collect_vec();
+
+    boolean_ops::union_all(&sums)
+}
+
+/// Key of a cached NFP: (fixed item id, rotation, orbiting item id, rotation)
+type NFPKey = (usize, NotNan<fsize>, usize, NotNan<fsize>);
+
+/// Caches NFPs between items and IFPs of items in bins, as they are expensive to compute.
+/// NFPs are stored per (fixed item, rotation, orbiting item, rotation), IFPs per (bin, item, rotation).
+/// Items consisting of multiple polygons are handled as a whole.
+#[derive(Clone, Debug, Default)]
+pub struct NFPCache {
+    nfps: HashMap<NFPKey, Arc<Vec<SimplePolygon>>>,
+    ifps: HashMap<(usize, usize, NotNan<fsize>), Arc<Vec<SimplePolygon>>>,
+}
+
+impl NFPCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The NFP of two items, relative to the reference point of `fixed`, see [nfp]
+    pub fn nfp(
+        &mut self,
+        fixed: &Item,
+        fixed_rotation: fsize,
+        orbiting: &Item,
+        orbiting_rotation: fsize,
+    ) -> Arc<Vec<SimplePolygon>> {
+        let key = (
+            fixed.id,
+            not_nan(fixed_rotation),
+            orbiting.id,
+            not_nan(orbiting_rotation),
+        );
+        self.nfps
+            .entry(key)
+            .or_insert_with(|| {
+                Arc::new(nfp_of_sets(
+                    &rotate_item(fixed, fixed_rotation),
+                    &rotate_item(orbiting, orbiting_rotation),
+                ))
+            })
+            .clone()
+    }
+
+    /// The IFP of an item in a bin, see [ifp]
+    pub fn ifp(&mut self, bin: &Bin, item: &Item, rotation: fsize) -> Arc<Vec<SimplePolygon>> {
+        let key = (bin.id, item.id, not_nan(rotation));
+        self.ifps
+            .entry(key)
+            .or_insert_with(|| Arc::new(ifp_of_set(bin, &rotate_item(item, rotation))))
+            .clone()
+    }
+
+    pub fn n_nfps(&self) -> usize {
+        self.nfps.len()
+    }
+
+    pub fn n_ifps(&self) -> usize {
+        self.ifps.len()
+    }
+
+    pub fn clear(&mut self) {
+        self.nfps.clear();
+        self.ifps.clear();
+    }
+}
+
+/// NFP of two sets of (already rotated) polygons, which move rigidly together
+fn nfp_of_sets(fixed: &[SimplePolygon], orbiting: &[SimplePolygon]) -> Vec<SimplePolygon> {
+    //the NFP is the Minkowski sum of the fixed polygons and the point reflection of the orbiting ones
+    let reflected = orbiting
+        .iter()
+        .map(|p| p.transform_clone(&Transformation::from_rotation(PI)))
+        .collect_vec();
+    minkowski_sum(fixed, &reflected)
+}
+
+/// IFP of a set of (already rotated) polygons, which move rigidly together
+fn ifp_of_set(bin: &Bin, item: &[SimplePolygon]) -> Vec<SimplePolygon> {
+    //maximum distance from the reference point of the item to any of its points
+    let reach = item
+        .iter()
+        .flat_map(|p| p.points.iter())
+        .map(|p| (p.0 * p.0 + p.1 * p.1).sqrt())
+        .fold(0.0, fsize::max);
+    let inflate = |bbox: AARectangle, margin: fsize| {
+        SimplePolygon::from(AARectangle::new(
+            bbox.x_min - margin,
+            bbox.y_min - margin,
+            bbox.x_max + margin,
+            bbox.y_max + margin,
+        ))
+    };
+
+    //every feasible position lies within `reach` of the bin, so it is inside the inner frame.
+    //the item overlaps the complement of the bin (within the outer frame) for every other position in the inner frame.
+    let bbox = bin.outer.bbox();
+    let inner_frame = inflate(bbox.clone(), 1.1 * reach);
+    let outer_frame = inflate(bbox, 3.0 * reach);
+    let bin_contour = SimplePolygon::new(bin.outer.points.clone());
+    let complement = std::iter::once(SimplePolygon::with_holes(
+        outer_frame.points,
+        vec![bin_contour],
+    ))
+    .chain(bin.holes.iter().map(|h| h.as_ref().clone()))
+    .collect_vec();
+
+    let forbidden = nfp_of_sets(&complement, item);
+    boolean_ops::boolean_op(&[inner_frame], &forbidden, BooleanOp::Difference)
+}
+
+/// Decomposes a polygon into convex pieces, as counterclockwise contours.
+/// The polygon is triangulated by ear clipping, after which adjacent pieces are merged
+/// as long as they remain convex (Hertel-Mehlhorn).
+fn convex_decomposition(shape: &SimplePolygon) -> Vec<Vec<Point>> {
+    if shape.holes.is_empty() && is_convex(&shape.points) {
+        return vec![shape.points.clone()];
+    }
+
+    let ring = bridge_holes(shape);
+    let mut pieces = triangulate(&ring)
+        .into_iter()
+        .map(|t| Some(t.to_vec()))
+        .collect_vec();
+
+    //the piece to the left of every directed edge
+    let mut owner = HashMap::new();
+    for (i, piece) in pieces.iter().enumerate() {
+        let piece = piece.as_ref().unwrap();
+        for (&u, &v) in piece.iter().circular_tuple_windows() {
+            owner.insert((u, v), i);
+        }
+    }
+
+    for i in 0..pieces.len() {
+        'grow: while let Some(piece) = pieces[i].as_ref() {
+            for k in 0..piece.len() {
+                let (u, v) = (piece[k], piece[(k + 1) % piece.len()]);
+                let Some(&j) = owner.get(&(v, u)) else {
+                    continue;
+                };
+                let other = pieces[j].as_ref().unwrap();
+                let l = other.iter().position(|&w| w == v).unwrap();
+
+                //walk around the piece starting at v, and continue on the other piece after u
+                let merged = (1..=piece.len())
+                    .map(|s| piece[(k + s) % piece.len()])
+                    .chain((2..other.len()).map(|s| other[(l + s) % other.len()]))
+                    .collect_vec();
+                if is_convex(&merged.iter().map(|&w| ring[w]).collect_vec()) {
+                    owner.remove(&(u, v));
+                    owner.remove(&(v, u));
+                    for (&w1, &w2) in merged.iter().circular_tuple_windows() {
+                        owner.insert((w1, w2), i);
+                    }
+                    pieces[j] = None;
+                    pieces[i] = Some(merged);
+                    continue 'grow;
+                }
+            }
+            break;
+        }
+    }
+
+    let pieces = pieces
+        .into_iter()
+        .flatten()
+        .map(|piece| piece.into_iter().map(|w| ring[w]).collect_vec())
+        .collect_vec();
+
+    debug_assert!({
+        let pieces_area = pieces
+            .iter()
+            .map(|p| SimplePolygon::calculate_area(p))
+            .sum::<fsize>();
+        (pieces_area - shape.area()).abs() <= 1e-3 * shape.area()
+    });
+
+    pieces
+}
+
+/// Connects the holes of a polygon to its outer contour with bridges (pairs of opposite edges),
+/// resulting in a single counterclockwise contour enclosing the same region.
+fn bridge_holes(shape: &SimplePolygon) -> Vec<Point> {
+    let mut ring = shape.points.clone();
+
+    //holes are traversed clockwise, starting from their rightmost point
+    let mut holes = shape
+        .holes
+        .iter()
+        .map(|h| {
+            let (start, _) = h
+                .points
+                .iter()
+                .enumerate()
+                .max_by(|(_, p), (_, q)| p.0.partial_cmp(&q.0).unwrap())
+                .unwrap();
+            let n = h.points.len();
+            (0..n).map(|s| h.points[(start + n - s) % n]).collect_vec()
+        })
+        .collect_vec();
+    holes.sort_by(|h1, h2| h2[0].0.partial_cmp(&h1[0].0).unwrap());
+
+    for k in 0..holes.len() {
+        let m = holes[k][0];
+        //edges which the bridge is not allowed to cross
+        let blocking = ring
+            .iter()
+            .circular_tuple_windows()
+            .chain(
+                holes[k..]
+                    .iter()
+                    .flat_map(|h| h.iter().circular_tuple_windows()),
+            )
+            .map(|(&p, &q)| (p, q))
+            .collect_vec();
+
+        let n = ring.len();
+        let visible = |i: usize| {
+            let (prev, p, next) = (ring[(i + n - 1) % n], ring[i], ring[(i + 1) % n]);
+            //the bridge has to leave the vertex towards the interior of the contour.
+            //this also selects the right copy of a vertex which was duplicated by an earlier bridge.
+            let inside_corner = match cross(prev, p, next) >= 0.0 {
+                true => cross(prev, p, m) > 0.0 && cross(p, next, m) > 0.0,
+                false => cross(prev, p, m) > 0.0 || cross(p, next, m) > 0.0,
+            };
+            let midpoint = Point((p.0 + m.0) / 2.0, (p.1 + m.1) / 2.0);
+            inside_corner
+                && shape.collides_with(&midpoint)
+                && blocking
+                    .iter()
+                    .all(|&(q1, q2)| !segments_cross((m, p), (q1, q2)))
+        };
+
+        //connect to the closest visible point of the contour
+        let bridge = (0..n)
+            .sorted_by(|&i, &j| {
+                let (di, dj) = (ring[i].sq_distance(m), ring[j].sq_distance(m));
+                di.partial_cmp(&dj).unwrap()
+            })
+            .find(|&i| visible(i))
+            .expect("no visible point on the contour to bridge the hole");
+
+        let hole = &holes[k];
+        ring = ring[..=bridge]
+            .iter()
+            .chain(hole.iter())
+            .chain(std::iter::once(&m))
+            .chain(ring[bridge..].iter())
+            .copied()
+            .collect_vec();
+    }
+    ring
+}
+
+/// Triangulates a counterclockwise contour by ear clipping, returns the triangles as indices in the contour
+fn triangulate(ring: &[Point]) -> Vec<[usize; 3]> {
+    let mut remaining = (0..ring.len()).collect_vec();
+    let mut triangles = vec![];
+
+    while remaining.len() >= 3 {
+        let n = remaining.len();
+        let corner = |i: usize| {
+            (
+                remaining[(i + n - 1) % n],
+                remaining[i],
+                remaining[(i + 1) % n],
+            )
+        };
+        let is_ear = |i: usize| {
+            let (a, b, c) = corner(i);
+            let (pa, pb, pc) = (ring[a], ring[b], ring[c]);
+            cross(pa, pb, pc) > 0.0
+                && remaining.iter().all(|&j| {
+                    let q = ring[j];
+                    q == pa || q == pb || q == pc || !in_triangle(q, pa, pb, pc)
+                })
+        };
+
+        match (0..n).find(|&i| is_ear(i)) {
+            Some(i) => {
+                let (a, b, c) = corner(i);
+                triangles.push([a, b, c]);
+                remaining.remove(i);
+            }
+            None => {
+                //no ear found due to numerical issues: drop a degenerate corner, or clip the most convex one
+                let corner_cross = |i: usize| {
+                    let (a, b, c) = corner(i);
+                    cross(ring[a], ring[b], ring[c])
+                };
+                let i = (0..n)
+                    .find(|&i| corner_cross(i) == 0.0)
+                    .or_else(|| {
+                        (0..n)
+                            .max_by(|&i, &j| corner_cross(i).partial_cmp(&corner_cross(j)).unwrap())
+                    })
+                    .unwrap();
+                if corner_cross(i) > 0.0 {
+                    let (a, b, c) = corner(i);
+                    triangles.push([a, b, c]);
+                }
+                remaining.remove(i);
+            }
+        }
+    }
+    triangles
+}
+
+/// Checks whether a counterclockwise contour is convex.
+/// Collinear corners are allowed, as long as the contour does not double back on itself.
+fn is_convex(points: &[Point]) -> bool {
+    points
+        .iter()
+        .circular_tuple_windows()
+        .all(|(&a, &b, &c)| match cross(a, b, c) {
+            0.0 => (b.0 - a.0) * (c.0 - b.0) + (b.1 - a.1) * (c.1 - b.1) > 0.0,
+            orientation => orientation > 0.0,
+        })
+}
+
+/// Checks whether `p` lies inside or on the boundary of the counterclockwise triangle `abc`
+fn in_triangle(p: Point, a: Point, b: Point, c: Point) -> bool {
+    cross(a, b, p) >= 0.0 && cross(b, c, p) >= 0.0 && cross(c, a, p) >= 0.0
+}
+
+/// Checks whether two segments cross, or whether an endpoint of the second lies strictly inside the first
+fn segments_cross((p1, p2): (Point, Point), (q1, q2): (Point, Point)) -> bool {
+    let shared = |q: Point| q == p1 || q == p2;
+    if shared(q1) || shared(q2) {
+        return false;
+    }
+    let (d1, d2) = (cross(p1, p2, q1), cross(p1, p2, q2));
+    let (d3, d4) = (cross(q1, q2, p1), cross(q1, q2, p2));
+    let on_segment = |q: Point, d: f64| {
+        d == 0.0 && (q.0 - p1.0) * (q.0 - p2.0) + (q.1 - p1.1) * (q.1 - p2.1) < 0.0
+    };
+    (d1 * d2 < 0.0 && d3 * d4 < 0.0) || on_segment(q1, d1) || on_segment(q2, d2)
+}
+
+/// Orientation of the corner `abc`, positive if counterclockwise.
+/// Its sign is exact, so nearly collinear vertices cannot make the decomposition inconsistent.
+fn cross(a: Point, b: Point, c: Point) -> f64 {
+    predicates::orient2d(a, b, c)
+}
+
+fn rotate(shape: &SimplePolygon, rotation: fsize) -> SimplePolygon {
+    shape.transform_clone(&Transformation::from_rotation(rotation))
+}
+
+fn rotate_item(item: &Item, rotation: fsize) -> Vec<SimplePolygon> {
+    item.shapes().map(|s| rotate(s, rotation)).collect_vec()
+}
+
+fn not_nan(rotation: fsize) -> NotNan<fsize> {
+    NotNan::new(rotation).expect("rotation is NaN")
+}
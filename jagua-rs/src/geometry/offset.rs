@@ -0,0 +1,562 @@
+use std::collections::HashMap;
+
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+
+use crate::fsize;
+use crate::geometry::geo_traits::{CollidesWith, DistanceFrom};
+use crate::geometry::primitives::point::Point;
+use crate::geometry::primitives::simple_polygon::SimplePolygon;
+
+/// How the offset edges are connected at the vertices where they separate.
+/// All join styles are conservative: the joins always enclose the circular arc of the true offset
+/// (their chords are tangent to it), so the offset polygon never undercuts the exact distance.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub enum JoinStyle {
+    /// Extends the offset edges until they intersect.
+    /// Falls back to [JoinStyle::Bevel] when the miter vertex would lie further than `limit` times the offset distance from the original vertex.
+    Miter { limit: fsize },
+    /// Approximates the arc with tangent segments, deviating at most `tolerance` from the true arc.
+    Round { tolerance: fsize },
+    /// Cuts the corner with a single segment tangent to the arc at its midpoint.
+    Bevel,
+}
+
+/// Offsets a polygon by `distance`: positive values inflate, negative values deflate.
+/// <br>
+/// The raw offset curve (offset edges connected by joins) is split at its self-intersections
+/// and only the regions with a positive winding number are retained.
+/// Inflation results in a single polygon which contains the true offset.
+/// Deflation can split the polygon into several components (or none at all), all of which are contained in the true offset.
+/// Offsets which cannot be represented as valid polygons (e.g. for a non-finite `distance`, or a curve
+/// degenerated by an extreme miter) are dropped, so inflation can exceptionally result in no polygon at all.
+/// <br>
+/// Holes are offset in the opposite direction. When deflating, components in which the grown holes
+/// cross the boundary (or each other) are discarded, as they cannot be represented without clipping.
+pub fn offset_polygon(
+    shape: &SimplePolygon,
+    distance: fsize,
+    join: JoinStyle,
+) -> Vec<SimplePolygon> {
+    if distance == 0.0 {
+        return vec![shape.clone()];
+    }
+    if shape.holes.is_empty() {
+        return offset_ring(shape, distance, join);
+    }
+
+    let outer = SimplePolygon::new(shape.points.clone());
+    let holes = shape
+        .holes
+        .iter()
+        .flat_map(|h| offset_ring(h, -distance, join))
+        .collect_vec();
+
+    offset_ring(&outer, distance, join)
+        .into_iter()
+        .filter_map(|component| {
+            let mut component_holes = vec![];
+            for hole in holes.iter() {
+                match ring_relation(hole, &component) {
+                    RingRelation::Disjoint => {}
+                    RingRelation::Inside => component_holes.push(hole.clone()),
+                    RingRelation::Other => return None,
+                }
+            }
+            let overlapping_holes = component_holes
+                .iter()
+                .tuple_combinations()
+                .any(|(a, b)| ring_relation(a, b) != RingRelation::Disjoint);
+            match overlapping_holes {
+                true => None,
+                false => Some(SimplePolygon::with_holes(component.points, component_holes)),
+            }
+        })
+        .collect()
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum RingRelation {
+    /// The ring lies entirely inside the other one
+    Inside,
+    /// The rings do not overlap at all
+    Disjoint,
+    /// The rings cross each other, or the other one lies inside the ring
+    Other,
+}
+
+/// Relation between the interiors of two polygons without holes
+fn ring_relation(ring: &SimplePolygon, other: &SimplePolygon) -> RingRelation {
+    if !ring.bbox.collides_with(&other.bbox) {
+        return RingRelation::Disjoint;
+    }
+    let crossing = ring
+        .edge_iter()
+        .any(|e| other.edge_iter().any(|oe| e.collides_with(&oe)));
+    match (crossing, other.collides_with(&ring.points[0])) {
+        (true, _) => RingRelation::Other,
+        (false, true) => RingRelation::Inside,
+        (false, false) => match ring.collides_with(&other.points[0]) {
+            true => RingRelation::Other,
+            false => RingRelation::Disjoint,
+        },
+    }
+}
+
+/// Offsets a polygon without holes, see [offset_polygon]
+fn offset_ring(shape: &SimplePolygon, distance: fsize, join: JoinStyle) -> Vec<SimplePolygon> {
+    let raw_curve = raw_offset_curve(&shape.points, distance, join);
+    let fragments = split_at_intersections(&raw_curve);
+
+    let fragments = merge_coincident_fragments(fragments);
+
+    //the boundary of the positive winding region consists of the fragments with a positive winding number on their left and a non-positive one on their right
+    let boundary = fragments
+        .iter()
+        .enumerate()
+        .filter_map(|(i, &((s, e), k))| {
+            let w_left = winding_number_left_of(&fragments, i);
+            let w_right = w_left - k;
+            match (w_left > 0, w_right > 0) {
+                (true, false) => Some((s, e)),
+                (false, true) => Some((e, s)),
+                _ => None,
+            }
+        })
+        .collect_vec();
+
+    let loops = trace_loops(&remove_dangling_fragments(boundary))
+        .into_iter()
+        .flat_map(split_at_repeated_points)
+        .map(remove_collinear_points)
+        .filter(|l| SimplePolygon::calculate_area(l) > 0.0)
+        .filter_map(|l| SimplePolygon::try_new(l).ok())
+        .collect_vec();
+
+    match distance > 0.0 {
+        true => {
+            //holes (if any) are dropped, the outer boundary is retained
+            loops
+                .into_iter()
+                .max_by(|a, b| a.area.total_cmp(&b.area))
+                .into_iter()
+                .collect()
+        }
+        false => {
+            //discard any spurious component which does not respect the offset distance
+            let min_distance = -distance * (1.0 - OFFSET_TOLERANCE);
+            loops
+                .into_iter()
+                .filter(|l| {
+                    l.points.iter().all(|p| {
+                        shape.collides_with(p) && shape.distance_from_border(p).1 >= min_distance
+                    })
+                })
+                .collect()
+        }
+    }
+}
+
+/// Relative tolerance on the offset distance used to validate deflated components
+const OFFSET_TOLERANCE: fsize = 1e-3;
+
+/// Number of tangent segments used to join two offset edges at a vertex, `sweep` being the angle between them
+fn n_join_segments(sweep: fsize, distance: fsize, join: JoinStyle) -> usize {
+    match join {
+        JoinStyle::Miter { limit } => match 1.0 / (sweep / 2.0).cos() <= limit {
+            true => 1,
+            false => 2,
+        },
+        JoinStyle::Bevel => 2,
+        JoinStyle::Round { tolerance } => {
+            //max sweep per segment for which the tangent vertices stay within tolerance of the arc
+            let max_segment_sweep = 2.0 * (distance / (distance + tolerance)).acos();
+            ((sweep / max_segment_sweep).ceil() as usize).max(1)
+        }
+    }
+}
+
+/// Constructs the (possibly self-intersecting) offset curve of a counterclockwise polygon.
+/// Vertices where the offset edges separate are connected by a join,
+/// vertices where they overlap are pivoted around the original vertex.
+/// At (almost) straight vertices, the offset edges are directly connected to avoid degenerate joins and pivots.
+fn raw_offset_curve(points: &[Point], distance: fsize, join: JoinStyle) -> Vec<Point> {
+    let n = points.len();
+    let r = distance.abs();
+
+    //lengths and outward normals of all edges
+    let (lengths, normals): (Vec<fsize>, Vec<(fsize, fsize)>) = (0..n)
+        .map(|i| {
+            let (p, q) = (points[i], points[(i + 1) % n]);
+            let (dx, dy) = (q.0 - p.0, q.1 - p.1);
+            let l = (dx * dx + dy * dy).sqrt();
+            (l, (dy / l, -dx / l))
+        })
+        .unzip();
+
+    let offset = |p: Point, (nx, ny): (fsize, fsize), d: fsize| Point(p.0 + nx * d, p.1 + ny * d);
+
+    let mut curve = vec![];
+    for i in 0..n {
+        //the vertex between edge i and edge j
+        let j = (i + 1) % n;
+        let (n_a, n_b) = (normals[i], normals[j]);
+        let vertex = points[j];
+
+        let cross = n_a.0 * n_b.1 - n_a.1 * n_b.0;
+        let dot = n_a.0 * n_b.0 + n_a.1 * n_b.1;
+
+        if dot > NEAR_STRAIGHT_COS {
+            //(almost) straight vertex: the offset edges are connected at their intersection (if it is well-defined)
+            let separating = cross * distance >= 0.0;
+            let retraction = r * cross.abs() / (1.0 + dot);
+            if separating || retraction <= 0.5 * fsize::min(lengths[i], lengths[j]) {
+                let miter = ((n_a.0 + n_b.0) / (1.0 + dot), (n_a.1 + n_b.1) / (1.0 + dot));
+                curve.push(offset(vertex, miter, distance));
+                continue;
+            }
+        }
+
+        curve.push(offset(vertex, n_a, distance));
+        let needs_join = match cross == 0.0 {
+            //a 180° turn is treated as an outward spike
+            true => distance > 0.0,
+            false => cross * distance > 0.0,
+        };
+        if needs_join {
+            //directions from the vertex towards the offset edges, the join sweeps from u_a to u_b
+            let sign = distance.signum();
+            let (u_a, u_b) = ((n_a.0 * sign, n_a.1 * sign), (n_b.0 * sign, n_b.1 * sign));
+            let sweep = match cross == 0.0 {
+                true => crate::PI,
+                false => (u_a.0 * u_b.1 - u_a.1 * u_b.0).atan2(u_a.0 * u_b.0 + u_a.1 * u_b.1),
+            };
+            let k = n_join_segments(sweep.abs(), r, join);
+            let step = sweep / k as fsize;
+            let radius = r / (step / 2.0).cos();
+            let start_angle = u_a.1.atan2(u_a.0);
+            for s in 0..k {
+                let angle = start_angle + (s as fsize + 0.5) * step;
+                curve.push(offset(vertex, (angle.cos(), angle.sin()), radius));
+            }
+        } else {
+            curve.push(vertex);
+        }
+        curve.push(offset(vertex, n_b, distance));
+    }
+
+    curve.dedup();
+    if curve.first() == curve.last() {
+        curve.pop();
+    }
+    curve
+}
+
+/// Vertices where the normals of both edges make an angle with a cosine above this value are considered (almost) straight
+const NEAR_STRAIGHT_COS: fsize = 0.99;
+
+type Fragment = (Point, Point);
+
+/// Splits all segments of a closed curve at their mutual intersections.
+/// All points are snapped together when they (almost) coincide, so fragments connect exactly.
+fn split_at_intersections(curve: &[Point]) -> Vec<Fragment> {
+    let bbox = SimplePolygon::generate_bounding_box(curve);
+    let tolerance = SNAP_TOLERANCE * (bbox.width() + bbox.height());
+    let mut snapper = PointSnapper::new(tolerance);
+
+    let mut curve = curve.iter().map(|p| snapper.snap(*p)).collect_vec();
+    curve.dedup();
+    if curve.first() == curve.last() {
+        curve.pop();
+    }
+
+    let n = curve.len();
+    let segments = (0..n).map(|i| (curve[i], curve[(i + 1) % n])).collect_vec();
+    let mut splits: Vec<Vec<(fsize, Point)>> = segments
+        .iter()
+        .map(|&(s, e)| vec![(0.0, s), (1.0, e)])
+        .collect();
+
+    for i in 0..n {
+        for j in (i + 1)..n {
+            let adjacent = j == i + 1 || (i == 0 && j == n - 1);
+            let (a, b) = segments[i];
+            let (c, d) = segments[j];
+            for (t, u, p) in segment_intersections(a, b, c, d, tolerance) {
+                let p = snapper.snap(p);
+                if adjacent && [a, b].contains(&p) && [c, d].contains(&p) {
+                    //shared vertex of consecutive segments
+                    continue;
+                }
+                splits[i].push((t, p));
+                splits[j].push((u, p));
+            }
+        }
+    }
+
+    splits
+        .into_iter()
+        .flat_map(|mut s| {
+            s.sort_by(|(t1, _), (t2, _)| t1.total_cmp(t2));
+            s.into_iter()
+                .map(|(_, p)| p)
+                .unique()
+                .tuple_windows()
+                .collect_vec()
+        })
+        .collect()
+}
+
+/// Maps points which lie within a tolerance of a previously encountered point onto that point.
+struct PointSnapper {
+    points: Vec<Point>,
+    sq_tolerance: fsize,
+}
+
+impl PointSnapper {
+    fn new(tolerance: fsize) -> Self {
+        Self {
+            points: vec![],
+            sq_tolerance: tolerance * tolerance,
+        }
+    }
+
+    fn snap(&mut self, p: Point) -> Point {
+        match self
+            .points
+            .iter()
+            .find(|q| q.sq_distance(p) <= self.sq_tolerance)
+        {
+            Some(q) => *q,
+            None => {
+                self.points.push(p);
+                p
+            }
+        }
+    }
+}
+
+/// Returns the parameters on both segments and the locations where they intersect.
+/// Endpoints lying within `tolerance` of the other segment are considered intersections at exactly that endpoint.
+fn segment_intersections(
+    a: Point,
+    b: Point,
+    c: Point,
+    d: Point,
+    tolerance: fsize,
+) -> Vec<(fsize, fsize, Point)> {
+    let (r_x, r_y) = (b.0 - a.0, b.1 - a.1);
+    let (s_x, s_y) = (d.0 - c.0, d.1 - c.1);
+    let (r_sq_len, s_sq_len) = (r_x * r_x + r_y * r_y, s_x * s_x + s_y * s_y);
+
+    //parameter of a point on the segment from p to q, if it lies within tolerance of it
+    let on_segment = |p: Point, q: Point, sq_len: fsize, x: Point| {
+        let (pq_x, pq_y) = (q.0 - p.0, q.1 - p.1);
+        let (px_x, px_y) = (x.0 - p.0, x.1 - p.1);
+        let t = ((px_x * pq_x + px_y * pq_y) / sq_len).clamp(0.0, 1.0);
+        let closest = Point(p.0 + t * pq_x, p.1 + t * pq_y);
+        match closest.sq_distance(x) <= tolerance * tolerance {
+            true => Some(t),
+            false => None,
+        }
+    };
+
+    let mut touching = vec![];
+    for (x, u) in [(c, 0.0), (d, 1.0)] {
+        if let Some(t) = on_segment(a, b, r_sq_len, x) {
+            touching.push((t, u, x));
+        }
+    }
+    for (x, t) in [(a, 0.0), (b, 1.0)] {
+        if let Some(u) = on_segment(c, d, s_sq_len, x) {
+            touching.push((t, u, x));
+        }
+    }
+    if !touching.is_empty() {
+        return touching;
+    }
+
+    let denom = r_x * s_y - r_y * s_x;
+    if denom == 0.0 {
+        //parallel segments
+        return vec![];
+    }
+    let (ac_x, ac_y) = (c.0 - a.0, c.1 - a.1);
+    let t = (ac_x * s_y - ac_y * s_x) / denom;
+    let u = (ac_x * r_y - ac_y * r_x) / denom;
+    if !(0.0..=1.0).contains(&t) || !(0.0..=1.0).contains(&u) {
+        return vec![];
+    }
+    let p = Point(a.0 + t * r_x, a.1 + t * r_y);
+    let q = Point(c.0 + u * s_x, c.1 + u * s_y);
+    match p.sq_distance(q) <= (CROSSING_TOLERANCE * tolerance).powi(2) {
+        true => vec![(t, u, p)],
+        //(nearly) parallel segments can produce nonsensical intersections
+        false => vec![],
+    }
+}
+
+/// Points closer than this fraction of the curve's size are considered to coincide
+const SNAP_TOLERANCE: fsize = 10.0 * fsize::EPSILON;
+
+/// Max disagreement (as a multiple of the snapping tolerance) between the intersection points computed on both segments
+const CROSSING_TOLERANCE: fsize = 10.0;
+
+/// Merges fragments which connect the same two points into a single fragment with a multiplicity.
+/// The multiplicity is the net number of times the curve traverses the fragment in its direction.
+fn merge_coincident_fragments(fragments: Vec<Fragment>) -> Vec<(Fragment, i32)> {
+    let mut merged: Vec<(Fragment, i32)> = vec![];
+    let mut index: HashMap<(Point, Point), usize> = HashMap::new();
+    for (s, e) in fragments {
+        if s == e {
+            continue;
+        }
+        if let Some(&i) = index.get(&(s, e)) {
+            merged[i].1 += 1;
+        } else if let Some(&i) = index.get(&(e, s)) {
+            merged[i].1 -= 1;
+        } else {
+            index.insert((s, e), merged.len());
+            merged.push(((s, e), 1));
+        }
+    }
+    merged
+}
+
+/// Winding number of the region directly to the left of a fragment, with respect to all (weighted) fragments.
+/// A ray is cast from the fragment's midpoint in the direction of its left normal, so it never crosses the fragment itself.
+fn winding_number_left_of(fragments: &[(Fragment, i32)], idx: usize) -> i32 {
+    let ((s, e), _) = fragments[idx];
+    let m = Point((s.0 + e.0) / 2.0, (s.1 + e.1) / 2.0);
+    let dir = (e.0 - s.0, e.1 - s.1);
+    let left = (-dir.1, dir.0);
+
+    //coordinates in a frame where the ray points along the positive x-axis (orientation is preserved)
+    let to_frame = |p: Point| {
+        let (dx, dy) = (p.0 - m.0, p.1 - m.1);
+        (dx * left.0 + dy * left.1, -(dx * dir.0 + dy * dir.1))
+    };
+
+    let mut winding = 0;
+    for (i, &((fs, fe), k)) in fragments.iter().enumerate() {
+        if i == idx {
+            continue;
+        }
+        let ((x0, y0), (x1, y1)) = (to_frame(fs), to_frame(fe));
+        let upward = y0 <= 0.0 && y1 > 0.0;
+        let downward = y1 <= 0.0 && y0 > 0.0;
+        if upward || downward {
+            let x_int = x0 + (0.0 - y0) * (x1 - x0) / (y1 - y0);
+            if x_int > 0.0 {
+                winding += if upward { k } else { -k };
+            }
+        }
+    }
+    winding
+}
+
+/// Iteratively removes fragments which cannot be part of a closed loop.
+/// These are artifacts of rounding errors in (nearly) degenerate configurations.
+fn remove_dangling_fragments(mut fragments: Vec<Fragment>) -> Vec<Fragment> {
+    loop {
+        let mut n_out: HashMap<Point, usize> = HashMap::new();
+        let mut n_in: HashMap<Point, usize> = HashMap::new();
+        for (s, e) in fragments.iter() {
+            *n_out.entry(*s).or_default() += 1;
+            *n_in.entry(*e).or_default() += 1;
+        }
+        let n_fragments = fragments.len();
+        fragments.retain(|(s, e)| n_in.contains_key(s) && n_out.contains_key(e));
+        if fragments.len() == n_fragments {
+            return fragments;
+        }
+    }
+}
+
+/// Chains directed fragments into closed loops.
+/// When multiple fragments leave the same point, the one making the sharpest left turn is followed.
+fn trace_loops(fragments: &[Fragment]) -> Vec<Vec<Point>> {
+    let mut outgoing: HashMap<Point, Vec<usize>> = HashMap::new();
+    for (i, (s, _)) in fragments.iter().enumerate() {
+        outgoing.entry(*s).or_default().push(i);
+    }
+
+    let mut used = vec![false; fragments.len()];
+    let mut loops = vec![];
+
+    for start in 0..fragments.len() {
+        if used[start] {
+            continue;
+        }
+        let mut points = vec![];
+        let mut current = start;
+        let closed = loop {
+            used[current] = true;
+            let (s, e) = fragments[current];
+            points.push(s);
+
+            //angle of the reversed incoming direction
+            let back_angle = (s.1 - e.1).atan2(s.0 - e.0);
+            let next = outgoing
+                .get(&e)
+                .into_iter()
+                .flatten()
+                .filter(|&&i| !used[i] || i == start)
+                .min_by(|&&i, &&j| {
+                    let cw_angle = |k: usize| {
+                        let (ks, ke) = fragments[k];
+                        let angle = (ke.1 - ks.1).atan2(ke.0 - ks.0);
+                        match (back_angle - angle).rem_euclid(2.0 * crate::PI) {
+                            0.0 => 2.0 * crate::PI,
+                            a => a,
+                        }
+                    };
+                    cw_angle(i).total_cmp(&cw_angle(j))
+                });
+            match next {
+                Some(&i) if i == start => break true,
+                Some(&i) => current = i,
+                None => break false,
+            }
+        };
+        if closed {
+            loops.push(points);
+        }
+    }
+    loops
+}
+
+/// Splits a loop which visits the same point multiple times into separate loops.
+fn split_at_repeated_points(points: Vec<Point>) -> Vec<Vec<Point>> {
+    let mut loops = vec![];
+    let mut stack: Vec<Point> = vec![];
+    for p in points {
+        if let Some(pos) = stack.iter().position(|q| *q == p) {
+            loops.push(stack.split_off(pos));
+        }
+        stack.push(p);
+    }
+    loops.push(stack);
+    loops
+}
+
+/// Removes vertices which (almost) lie on the segment connecting their neighbours.
+fn remove_collinear_points(mut points: Vec<Point>) -> Vec<Point> {
+    let mut i = 0;
+    while points.len() >= 3 && i < points.len() {
+        let n = points.len();
+        let (p, q, r) = (points[(i + n - 1) % n], points[i], points[(i + 1) % n]);
+        let (a, b) = ((q.0 - p.0, q.1 - p.1), (r.0 - q.0, r.1 - q.1));
+        let cross = a.0 * b.1 - a.1 * b.0;
+        let dot = a.0 * b.0 + a.1 * b.1;
+        let scale = (a.0 * a.0 + a.1 * a.1).sqrt() * (b.0 * b.0 + b.1 * b.1).sqrt();
+        if cross.abs() <= COLLINEAR_TOLERANCE * scale && dot > 0.0 {
+            points.remove(i);
+        } else {
+            i += 1;
+        }
+    }
+    points
+}
+
+const COLLINEAR_TOLERANCE: fsize = 1e-6;
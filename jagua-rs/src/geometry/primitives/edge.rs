@@ -6,6 +6,7 @@ use crate::geometry::geo_traits::{
 use crate::geometry::primitives::aa_rectangle::AARectangle;
 use crate::geometry::primitives::point::Point;
 use crate::geometry::transformation::Transformation;
+use crate::util::predicates;
 
 /// Geometric primitive representing a line segment
 #[derive(Clone, Debug, PartialEq)]
@@ -56,7 +57,7 @@ impl Edge {
     }
 
     pub fn collides_at(&self, other: &Edge) -> Option<Point> {
-        match edge_intersection(self, other, true) {
+        match edge_intersection(self, other, true, false) {
             Intersection::No => None,
             Intersection::Yes(point) => Some(
                 point.expect("Intersection::Yes, but returned no point when this was requested"),
@@ -64,6 +65,15 @@ impl Edge {
         }
     }
 
+    /// Checks whether the edge intersects with another edge.
+    /// With `robust` enabled, the decision is made using exact [predicates].
+    pub fn intersects(&self, other: &Edge, robust: bool) -> bool {
+        match edge_intersection(self, other, false, robust) {
+            Intersection::No => false,
+            Intersection::Yes(_) => true,
+        }
+    }
+
     /// Returns the closest point which lies on the edge to the given point
     pub fn closest_point_on_edge(&self, point: &Point) -> Point {
         //from https://stackoverflow.com/a/6853926
@@ -91,6 +101,27 @@ impl Edge {
         Point(xx, yy)
     }
 
+    /// Returns the closest pair of points between two edges, the first on `self` and the second on `other`
+    pub fn closest_points(&self, other: &Edge) -> (Point, Point) {
+        if let Some(p) = self.collides_at(other) {
+            return (p, p);
+        }
+        //if the edges do not cross, one of the closest points is an endpoint
+        [
+            (self.start, other.closest_point_on_edge(&self.start)),
+            (self.end, other.closest_point_on_edge(&self.end)),
+            (self.closest_point_on_edge(&other.start), other.start),
+            (self.closest_point_on_edge(&other.end), other.end),
+        ]
+        .into_iter()
+        .min_by(|(p1, q1), (p2, q2)| {
+            p1.sq_distance(*q1)
+                .partial_cmp(&p2.sq_distance(*q2))
+                .unwrap()
+        })
+        .unwrap()
+    }
+
     pub fn x_min(&self) -> fsize {
         fsize::min(self.start.0, self.end.0)
     }
@@ -173,10 +204,7 @@ impl DistanceFrom<Point> for Edge {
 
 impl CollidesWith<Edge> for Edge {
     fn collides_with(&self, other: &Edge) -> bool {
-        match edge_intersection(self, other, false) {
-            Intersection::No => false,
-            Intersection::Yes(_) => true,
-        }
+        self.intersects(other, false)
     }
 }
 
@@ -187,7 +215,7 @@ impl CollidesWith<AARectangle> for Edge {
 }
 
 #[inline(always)]
-fn edge_intersection(e1: &Edge, e2: &Edge, calculate_location: bool) -> Intersection {
+fn edge_intersection(e1: &Edge, e2: &Edge, calculate_location: bool, robust: bool) -> Intersection {
     if fsize::max(e1.x_min(), e2.x_min()) > fsize::min(e1.x_max(), e2.x_max())
         || fsize::max(e1.y_min(), e2.y_min()) > fsize::min(e1.y_max(), e2.y_max())
     {
@@ -206,6 +234,21 @@ fn edge_intersection(e1: &Edge, e2: &Edge, calculate_location: bool) -> Intersec
     let u_nom = (x2 - x4) * (y2 - y1) - (y2 - y4) * (x2 - x1);
     let u_denom = (x2 - x1) * (y4 - y3) - (y2 - y1) * (x4 - x3);
 
+    if robust {
+        //the existence of the intersection is decided exactly, only its location is approximate
+        if !predicates::segments_intersect((e1.start, e1.end), (e2.start, e2.end)) {
+            return Intersection::No;
+        }
+        let location = calculate_location.then(|| {
+            let t = match t_denom {
+                0.0 => 0.0,
+                _ => (t_nom / t_denom).clamp(0.0, 1.0),
+            };
+            Point(x2 + t * (x1 - x2), y2 + t * (y1 - y2))
+        });
+        return Intersection::Yes(location);
+    }
+
     if t_denom == 0.0 || u_denom == 0.0 {
         //parallel edges
         Intersection::No
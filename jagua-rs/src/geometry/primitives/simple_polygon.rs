@@ -1,4 +1,5 @@
 use std::borrow::Borrow;
+use std::fmt::{Display, Formatter};
 
 use itertools::Itertools;
 use num_integer::Integer;
@@ -6,12 +7,14 @@ use ordered_float::NotNan;
 
 use crate::fsize;
 use crate::geometry::convex_hull::convex_hull_from_points;
+use crate::geometry::edge_bvh::EdgeBVH;
 use crate::geometry::fail_fast::poi;
 use crate::geometry::fail_fast::sp_surrogate::SPSurrogate;
 use crate::geometry::geo_enums::GeoPosition;
 use crate::geometry::geo_traits::{
     CollidesWith, DistanceFrom, Shape, Transformable, TransformableFrom,
 };
+use crate::geometry::offset::{offset_polygon, JoinStyle};
 use crate::geometry::primitives::aa_rectangle::AARectangle;
 use crate::geometry::primitives::circle::Circle;
 use crate::geometry::primitives::edge::Edge;
@@ -19,12 +22,20 @@ use crate::geometry::primitives::point::Point;
 use crate::geometry::transformation::Transformation;
 use crate::util::config::SPSurrogateConfig;
 use crate::util::fpa::FPA;
+use crate::util::polygon_validation;
+use crate::util::polygon_validation::{PolygonDefect, DEFAULT_VALIDATION_TOLERANCE};
+use crate::util::predicates;
 
-/// Geometric primitive representing a simple polygon: <https://en.wikipedia.org/wiki/Simple_polygon>
+/// Geometric primitive representing a simple polygon: <https://en.wikipedia.org/wiki/Simple_polygon>,
+/// optionally with holes which are excluded from its interior.
 #[derive(Clone, Debug)]
 pub struct SimplePolygon {
-    /// Set of bounds describing the polygon
+    /// Vertices of the outer boundary only, the holes are stored separately in [Self::holes].
+    /// Methods based on them ([Self::get_point], [Self::get_edge], [Self::edge_iter] and [Self::number_of_points])
+    /// therefore ignore the holes, use [Self::border_edge_iter] and [Self::get_border_edge] to include them.
     pub points: Vec<Point>,
+    /// Holes in the polygon, simple polygons (without holes themselves) strictly inside the outer boundary
+    pub holes: Vec<SimplePolygon>,
     /// Bounding box
     pub bbox: AARectangle,
     pub area: fsize,
@@ -34,24 +45,45 @@ pub struct SimplePolygon {
     pub poi: Circle,
     /// Surrogate representation (subset of the simple polygon)
     pub surrogate: Option<SPSurrogate>,
+    /// Hierarchy over the border edges, only present for polygons with many edges
+    pub edge_bvh: Option<EdgeBVH>,
 }
 
 impl SimplePolygon {
     /// Create a new simple polygon from a set of points, expensive operations are performed here! Use [Self::clone()] or [Self::transform()] to avoid recomputation.
-    pub fn new(mut points: Vec<Point>) -> Self {
-        assert!(
-            points.len() >= 3,
-            "simple polygon must have at least 3 points"
-        );
-        assert_eq!(
-            points.iter().unique().count(),
-            points.len(),
-            "simple polygon should not contain duplicate points: {:?}",
-            points
-        );
+    /// Panics if the points do not form a valid polygon, see [`Self::try_new`] for a fallible alternative.
+    pub fn new(points: Vec<Point>) -> Self {
+        SimplePolygon::with_holes(points, vec![])
+    }
+
+    /// Create a new simple polygon from a set of points, with holes.
+    pub fn with_holes(points: Vec<Point>, holes: Vec<SimplePolygon>) -> Self {
+        SimplePolygon::try_with_holes(points, holes)
+            .unwrap_or_else(|e| panic!("invalid simple polygon: {e}"))
+    }
+
+    /// Same as [`Self::new`], but returns an error instead of panicking if the points do not form a valid polygon.
+    pub fn try_new(points: Vec<Point>) -> Result<Self, InvalidPolygon> {
+        SimplePolygon::try_with_holes(points, vec![])
+    }
+
+    /// Same as [`Self::with_holes`], but returns an error instead of panicking if the points do not form a valid polygon.
+    pub fn try_with_holes(
+        mut points: Vec<Point>,
+        holes: Vec<SimplePolygon>,
+    ) -> Result<Self, InvalidPolygon> {
+        if points.len() < 3 {
+            return Err(InvalidPolygon::TooFewPoints(points.len()));
+        }
+        if let Some(p) = points.iter().find(|p| !p.0.is_finite() || !p.1.is_finite()) {
+            return Err(InvalidPolygon::NonFinitePoint(*p));
+        }
+        if let Some(p) = points.iter().duplicates().next() {
+            return Err(InvalidPolygon::DuplicatePoint(*p));
+        }
 
         let area = match SimplePolygon::calculate_area(&points) {
-            area if area == 0.0 => panic!("simple polygon has no area: {:?}", points),
+            0.0 => return Err(InvalidPolygon::ZeroArea),
             area if area < 0.0 => {
                 //edges should always be ordered counterclockwise (positive area)
                 points.reverse();
@@ -59,42 +91,109 @@ impl SimplePolygon {
             }
             area => area,
         };
+        if holes.iter().any(|h| !h.holes.is_empty()) {
+            return Err(InvalidPolygon::NestedHoles);
+        }
+        let area = area - holes.iter().map(|h| h.area).sum::<fsize>();
+        if area <= 0.0 {
+            return Err(InvalidPolygon::HolesCoverPolygon);
+        }
 
         let diameter = SimplePolygon::calculate_diameter(points.clone());
         let bbox = SimplePolygon::generate_bounding_box(&points);
-        let poi = SimplePolygon::calculate_poi(&points, diameter);
+        let poi = SimplePolygon::calculate_poi(&points, &holes, diameter);
 
-        SimplePolygon {
+        let mut polygon = SimplePolygon {
             points,
+            holes,
             bbox,
             area,
             diameter,
             poi,
             surrogate: None,
-        }
+            edge_bvh: None,
+        };
+        polygon.edge_bvh = EdgeBVH::new(&polygon);
+        Ok(polygon)
     }
 
     pub fn generate_surrogate(&mut self, config: SPSurrogateConfig) {
         self.surrogate = Some(SPSurrogate::new(self, config));
     }
 
+    /// Returns the vertex at index `i` of the outer boundary
     pub fn get_point(&self, i: usize) -> Point {
         self.points[i]
     }
 
+    /// Returns the edge of the outer boundary starting at vertex `i`
     pub fn get_edge(&self, i: usize) -> Edge {
         let j = (i + 1) % self.number_of_points();
         Edge::new(self.points[i], self.points[j])
     }
 
+    /// Iterates over the edges of the outer boundary, ignoring any holes (see [Self::border_edge_iter])
     pub fn edge_iter(&self) -> impl Iterator<Item = Edge> + '_ {
         (0..self.number_of_points()).map(move |i| self.get_edge(i))
     }
 
+    /// Number of vertices of the outer boundary
     pub fn number_of_points(&self) -> usize {
         self.points.len()
     }
 
+    /// Iterates over all edges bounding the interior of the polygon: those of the outer boundary, followed by those of the holes
+    pub fn border_edge_iter(&self) -> impl Iterator<Item = Edge> + '_ {
+        self.edge_iter()
+            .chain(self.holes.iter().flat_map(|h| h.edge_iter()))
+    }
+
+    /// Returns the edge at index `i` in the order of [Self::border_edge_iter]
+    /// # Panics
+    /// If `i` is not smaller than [Self::number_of_border_edges]
+    pub fn get_border_edge(&self, mut i: usize) -> Edge {
+        debug_assert!(
+            i < self.number_of_border_edges(),
+            "border edge index {i} out of bounds for a polygon with {} border edges",
+            self.number_of_border_edges()
+        );
+        if i < self.number_of_points() {
+            return self.get_edge(i);
+        }
+        i -= self.number_of_points();
+        for hole in self.holes.iter() {
+            if i < hole.number_of_points() {
+                return hole.get_edge(i);
+            }
+            i -= hole.number_of_points();
+        }
+        panic!("border edge index out of bounds")
+    }
+
+    /// Number of edges bounding the interior of the polygon, including those of the holes
+    pub fn number_of_border_edges(&self) -> usize {
+        self.number_of_points()
+            + self
+                .holes
+                .iter()
+                .map(|h| h.number_of_points())
+                .sum::<usize>()
+    }
+
+    /// Whether the edge at index `i` in the order of [Self::border_edge_iter] belongs to a hole.
+    /// The interior of the polygon lies to the right of the (counterclockwise) edges of its holes, instead of to their left.
+    pub fn is_hole_edge(&self, i: usize) -> bool {
+        i >= self.number_of_points()
+    }
+
+    /// Refits the bounding boxes of the [EdgeBVH] (if any) to the current position of the edges
+    fn refit_edge_bvh(&mut self) {
+        if let Some(mut edge_bvh) = self.edge_bvh.take() {
+            edge_bvh.refit(self);
+            self.edge_bvh = Some(edge_bvh);
+        }
+    }
+
     pub fn surrogate(&self) -> &SPSurrogate {
         self.surrogate.as_ref().expect("surrogate not generated")
     }
@@ -144,7 +243,7 @@ impl SimplePolygon {
         0.5 * sigma
     }
 
-    pub fn calculate_poi(points: &[Point], diameter: fsize) -> Circle {
+    pub fn calculate_poi(points: &[Point], holes: &[SimplePolygon], diameter: fsize) -> Circle {
         //need to make a dummy simple polygon, because the pole generation algorithm
         //relies on many of the methods provided by the simple polygon struct
         let dummy_sp = {
@@ -154,17 +253,45 @@ impl SimplePolygon {
 
             SimplePolygon {
                 points: points.to_vec(),
+                holes: holes.to_vec(),
                 bbox,
                 area,
                 diameter,
                 poi: dummy_poi,
                 surrogate: None,
+                edge_bvh: None,
             }
         };
 
         poi::generate_next_pole(&dummy_sp, &[])
     }
 
+    /// Offsets the polygon by `distance`: positive values inflate, negative values deflate.
+    /// See [offset_polygon] for the guarantees on the result.
+    pub fn offset(&self, distance: fsize, join: JoinStyle) -> Vec<SimplePolygon> {
+        offset_polygon(self, distance, join)
+    }
+
+    /// Detects defects in the contour of the polygon and its holes, see [polygon_validation::validate].
+    /// Intersections between the contour and a hole, or between holes, are reported as self-intersections.
+    pub fn validate(&self) -> Vec<PolygonDefect> {
+        let contours = std::iter::once(self).chain(self.holes.iter()).collect_vec();
+        let mut defects = contours
+            .iter()
+            .flat_map(|c| polygon_validation::validate(&c.points, DEFAULT_VALIDATION_TOLERANCE))
+            .collect_vec();
+        for (c1, c2) in contours.iter().tuple_combinations() {
+            for e1 in c1.edge_iter() {
+                defects.extend(
+                    c2.edge_iter()
+                        .filter_map(|e2| e1.collides_at(&e2))
+                        .map(PolygonDefect::SelfIntersection),
+                );
+            }
+        }
+        defects
+    }
+
     pub fn center_around_centroid(mut self) -> (SimplePolygon, Transformation) {
         let Point(c_x, c_y) = self.centroid();
         let transformation = Transformation::from_translation((-c_x, -c_y));
@@ -173,26 +300,159 @@ impl SimplePolygon {
 
         (self, transformation)
     }
+
+    /// Returns the minimum distance between two polygons and a pair of closest points, the first on `self` and the second on `other`.
+    /// The distance is zero if the polygons overlap, in which case both points are equal and lie in the overlap.
+    pub fn closest_points(&self, other: &SimplePolygon) -> (fsize, Point, Point) {
+        self.closest_points_within(other, fsize::INFINITY)
+            .expect("polygons are always within an infinite distance")
+    }
+
+    /// Same as [`Self::closest_points`], but returns `None` if the polygons are further than `max_distance` apart.
+    /// Pairs of edges which are certainly further apart are skipped, polygons whose bounding boxes are further apart are rejected immediately.
+    pub fn closest_points_within(
+        &self,
+        other: &SimplePolygon,
+        max_distance: fsize,
+    ) -> Option<(fsize, Point, Point)> {
+        let max_sq_distance = max_distance * max_distance;
+        if bbox_sq_distance(&self.bbox, &other.bbox) > max_sq_distance {
+            return None;
+        }
+        if let Some(p) = self.overlap_point(other) {
+            return Some((0.0, p, p));
+        }
+        self.closest_border_points(other, max_sq_distance, false)
+            .map(|(sq_d, p, q)| (sq_d.sqrt(), p, q))
+    }
+
+    /// Checks whether two polygons are at most `distance` apart, stops as soon as a pair of points within the distance is found
+    pub fn within_distance(&self, other: &SimplePolygon, distance: fsize) -> bool {
+        let max_sq_distance = distance * distance;
+        if bbox_sq_distance(&self.bbox, &other.bbox) > max_sq_distance {
+            return false;
+        }
+        self.overlap_point(other).is_some()
+            || self
+                .closest_border_points(other, max_sq_distance, true)
+                .is_some()
+    }
+
+    /// A point which lies in both polygons, if one contains a vertex of the other.
+    /// Polygons can also overlap when their borders cross, which is detected by [`Self::closest_border_points`].
+    fn overlap_point(&self, other: &SimplePolygon) -> Option<Point> {
+        if !self.bbox.collides_with(&other.bbox) {
+            return None;
+        }
+        let (p, q) = (other.points[0], self.points[0]);
+        match (self.collides_with(&p), other.collides_with(&q)) {
+            (true, _) => Some(p),
+            (_, true) => Some(q),
+            _ => None,
+        }
+    }
+
+    /// Closest pair of points between the borders of two polygons, with their squared distance.
+    /// Returns `None` if none are within `max_sq_distance`.
+    /// If `first_within` is set, the first pair found within `max_sq_distance` is returned, instead of the closest one.
+    fn closest_border_points(
+        &self,
+        other: &SimplePolygon,
+        max_sq_distance: fsize,
+        first_within: bool,
+    ) -> Option<(fsize, Point, Point)> {
+        let mut closest: Option<(fsize, Point, Point)> = None;
+        let mut bound = max_sq_distance;
+
+        for e1 in self.border_edge_iter() {
+            let e1_bbox = edge_bbox(&e1);
+            if bbox_sq_distance(&e1_bbox, &other.bbox) > bound {
+                continue;
+            }
+            for e2 in other.border_edge_iter() {
+                if bbox_sq_distance(&e1_bbox, &edge_bbox(&e2)) > bound {
+                    continue;
+                }
+                let (p, q) = e1.closest_points(&e2);
+                let sq_distance = p.sq_distance(q);
+                if sq_distance <= bound {
+                    closest = Some((sq_distance, p, q));
+                    bound = sq_distance;
+                    if first_within || sq_distance == 0.0 {
+                        return closest;
+                    }
+                }
+            }
+        }
+        closest
+    }
+
+    /// Checks whether a point lies inside the polygon (and outside its holes).
+    /// With `robust` enabled, the decision is made using exact [predicates].
+    pub fn contains_point(&self, point: &Point, robust: bool) -> bool {
+        //based on the ray casting algorithm: https://en.wikipedia.org/wiki/Point_in_polygon#Ray_casting_algorithm
+        match self.bbox().collides_with(point) {
+            false => false,
+            true if robust => {
+                predicates::ring_contains(&self.points, *point)
+                    && self.holes.iter().all(|h| !h.contains_point(point, robust))
+            }
+            true => {
+                //horizontal ray shot to the right.
+                //Starting from the point to another point that is certainly outside the shape
+                let point_outside = Point(self.bbox.x_max + self.bbox.width(), point.1);
+                let ray = Edge::new(*point, point_outside);
+
+                let mut n_intersections = 0;
+                for edge in self.edge_iter() {
+                    //Check if the ray does not go through (or almost through) a vertex
+                    //This can result in funky behaviour, which could incorrect results
+                    //Therefore we handle this case
+                    let (s_x, s_y) = (FPA(edge.start.0), FPA(edge.start.1));
+                    let (e_x, e_y) = (FPA(edge.end.0), FPA(edge.end.1));
+                    let (p_x, p_y) = (FPA(point.0), FPA(point.1));
+
+                    if (s_y == p_y && s_x > p_x) || (e_y == p_y && e_x > p_x) {
+                        //in this case, the ray passes through (or dangerously close to) a vertex
+                        //We handle this case by only counting an intersection if the edge is below the ray
+                        if s_y < p_y || e_y < p_y {
+                            n_intersections += 1;
+                        }
+                    } else if ray.collides_with(&edge) {
+                        n_intersections += 1;
+                    }
+                }
+
+                //points inside a hole are not part of the polygon
+                n_intersections.is_odd()
+                    && self.holes.iter().all(|h| !h.contains_point(point, robust))
+            }
+        }
+    }
 }
 
 impl Shape for SimplePolygon {
     fn centroid(&self) -> Point {
         //based on: https://en.wikipedia.org/wiki/Centroid#Of_a_polygon
+        //the first moments of the holes are subtracted from the one of the outer boundary
+        let first_moment = |points: &[Point]| {
+            let (mut m_x, mut m_y) = (0.0, 0.0);
+            for i in 0..points.len() {
+                let j = (i + 1) % points.len();
+                let Point(x_i, y_i) = points[i];
+                let Point(x_j, y_j) = points[j];
+                m_x += (x_i + x_j) * (x_i * y_j - x_j * y_i);
+                m_y += (y_i + y_j) * (x_i * y_j - x_j * y_i);
+            }
+            (m_x, m_y)
+        };
 
         let area = self.area();
-        let mut c_x = 0.0;
-        let mut c_y = 0.0;
-
-        for i in 0..self.number_of_points() {
-            let j = if i == self.number_of_points() - 1 {
-                0
-            } else {
-                i + 1
-            };
-            let Point(x_i, y_i) = self.get_point(i);
-            let Point(x_j, y_j) = self.get_point(j);
-            c_x += (x_i + x_j) * (x_i * y_j - x_j * y_i);
-            c_y += (y_i + y_j) * (x_i * y_j - x_j * y_i);
+        let (mut c_x, mut c_y) = first_moment(&self.points);
+        for hole in self.holes.iter() {
+            let (h_x, h_y) = first_moment(&hole.points);
+            c_x -= h_x;
+            c_y -= h_y;
         }
 
         c_x /= 6.0 * area;
@@ -219,11 +479,13 @@ impl Transformable for SimplePolygon {
         //destructuring pattern to ensure that the code is updated when the struct changes
         let SimplePolygon {
             points,
+            holes,
             bbox,
             area: _,
             diameter: _,
             poi,
             surrogate,
+            edge_bvh: _,
         } = self;
 
         //transform all points of the simple poly
@@ -231,6 +493,10 @@ impl Transformable for SimplePolygon {
             p.transform(t);
         });
 
+        holes.iter_mut().for_each(|h| {
+            h.transform(t);
+        });
+
         poi.transform(t);
 
         //transform the surrogate
@@ -238,9 +504,22 @@ impl Transformable for SimplePolygon {
             surrogate.transform(t);
         }
 
+        //mirroring reverses the orientation of the points, reverse their order to keep them counterclockwise
+        if t.is_mirroring() {
+            let n = points.len();
+            points.reverse();
+            if let Some(surrogate) = surrogate.as_mut() {
+                let ch_indices = &mut surrogate.convex_hull_indices;
+                ch_indices.iter_mut().for_each(|i| *i = n - 1 - *i);
+                ch_indices.reverse();
+            }
+        }
+
         //regenerate bounding box
         *bbox = SimplePolygon::generate_bounding_box(points);
 
+        self.refit_edge_bvh();
+
         self
     }
 }
@@ -250,64 +529,67 @@ impl TransformableFrom for SimplePolygon {
         //destructuring pattern to ensure that the code is updated when the struct changes
         let SimplePolygon {
             points,
+            holes,
             bbox,
             area: _,
             diameter: _,
             poi,
             surrogate,
+            edge_bvh: _,
         } = self;
 
-        for (p, ref_p) in points.iter_mut().zip(&reference.points) {
-            p.transform_from(ref_p, t);
+        //mirroring reverses the orientation of the points, store them in reverse order to keep them counterclockwise
+        let mirror = t.is_mirroring();
+        match mirror {
+            false => {
+                for (p, ref_p) in points.iter_mut().zip(&reference.points) {
+                    p.transform_from(ref_p, t);
+                }
+            }
+            true => {
+                for (p, ref_p) in points.iter_mut().rev().zip(&reference.points) {
+                    p.transform_from(ref_p, t);
+                }
+            }
+        }
+
+        for (h, ref_h) in holes.iter_mut().zip(&reference.holes) {
+            h.transform_from(ref_h, t);
         }
 
         poi.transform_from(&reference.poi, t);
 
         //transform the surrogate
         if let Some(surrogate) = surrogate.as_mut() {
-            surrogate.transform_from(reference.surrogate(), t);
+            let ref_surrogate = reference.surrogate();
+            surrogate.transform_from(ref_surrogate, t);
+
+            //the indices of the convex hull follow the order of the points
+            let (n, ch_indices) = (points.len(), &mut surrogate.convex_hull_indices);
+            ch_indices.clear();
+            match mirror {
+                false => ch_indices.extend_from_slice(&ref_surrogate.convex_hull_indices),
+                true => ch_indices.extend(
+                    ref_surrogate
+                        .convex_hull_indices
+                        .iter()
+                        .rev()
+                        .map(|i| n - 1 - i),
+                ),
+            }
         }
         //regenerate bounding box
         *bbox = SimplePolygon::generate_bounding_box(points);
 
+        self.refit_edge_bvh();
+
         self
     }
 }
 
 impl CollidesWith<Point> for SimplePolygon {
     fn collides_with(&self, point: &Point) -> bool {
-        //based on the ray casting algorithm: https://en.wikipedia.org/wiki/Point_in_polygon#Ray_casting_algorithm
-        match self.bbox().collides_with(point) {
-            false => false,
-            true => {
-                //horizontal ray shot to the right.
-                //Starting from the point to another point that is certainly outside the shape
-                let point_outside = Point(self.bbox.x_max + self.bbox.width(), point.1);
-                let ray = Edge::new(*point, point_outside);
-
-                let mut n_intersections = 0;
-                for edge in self.edge_iter() {
-                    //Check if the ray does not go through (or almost through) a vertex
-                    //This can result in funky behaviour, which could incorrect results
-                    //Therefore we handle this case
-                    let (s_x, s_y) = (FPA(edge.start.0), FPA(edge.start.1));
-                    let (e_x, e_y) = (FPA(edge.end.0), FPA(edge.end.1));
-                    let (p_x, p_y) = (FPA(point.0), FPA(point.1));
-
-                    if (s_y == p_y && s_x > p_x) || (e_y == p_y && e_x > p_x) {
-                        //in this case, the ray passes through (or dangerously close to) a vertex
-                        //We handle this case by only counting an intersection if the edge is below the ray
-                        if s_y < p_y || e_y < p_y {
-                            n_intersections += 1;
-                        }
-                    } else if ray.collides_with(&edge) {
-                        n_intersections += 1;
-                    }
-                }
-
-                n_intersections.is_odd()
-            }
-        }
+        self.contains_point(point, false)
     }
 }
 
@@ -316,7 +598,7 @@ impl DistanceFrom<Point> for SimplePolygon {
         match self.collides_with(point) {
             true => 0.0,
             false => self
-                .edge_iter()
+                .border_edge_iter()
                 .map(|edge| edge.sq_distance(point))
                 .min_by(|a, b| a.partial_cmp(b).unwrap())
                 .unwrap(),
@@ -333,7 +615,7 @@ impl DistanceFrom<Point> for SimplePolygon {
 
     fn sq_distance_from_border(&self, point: &Point) -> (GeoPosition, fsize) {
         let distance_to_border = self
-            .edge_iter()
+            .border_edge_iter()
             .map(|edge| edge.sq_distance(point))
             .min_by(|a, b| a.partial_cmp(b).unwrap())
             .unwrap();
@@ -345,6 +627,57 @@ impl DistanceFrom<Point> for SimplePolygon {
     }
 }
 
+impl DistanceFrom<SimplePolygon> for SimplePolygon {
+    fn sq_distance(&self, other: &SimplePolygon) -> fsize {
+        match self.overlap_point(other) {
+            Some(_) => 0.0,
+            None => {
+                self.closest_border_points(other, fsize::INFINITY, false)
+                    .expect("borders are always within an infinite distance")
+                    .0
+            }
+        }
+    }
+
+    fn distance(&self, other: &SimplePolygon) -> fsize {
+        self.sq_distance(other).sqrt()
+    }
+
+    fn distance_from_border(&self, other: &SimplePolygon) -> (GeoPosition, fsize) {
+        let (position, sq_distance) = self.sq_distance_from_border(other);
+        (position, sq_distance.sqrt())
+    }
+
+    /// The position is [`GeoPosition::Interior`] if the polygons overlap
+    fn sq_distance_from_border(&self, other: &SimplePolygon) -> (GeoPosition, fsize) {
+        let (sq_distance, _, _) = self
+            .closest_border_points(other, fsize::INFINITY, false)
+            .expect("borders are always within an infinite distance");
+        let overlap = sq_distance == 0.0 || self.overlap_point(other).is_some();
+        match overlap {
+            true => (GeoPosition::Interior, sq_distance),
+            false => (GeoPosition::Exterior, sq_distance),
+        }
+    }
+}
+
+/// Edges can be axis-aligned, so their bounding box is not necessarily a valid [AARectangle]
+fn edge_bbox(edge: &Edge) -> AARectangle {
+    AARectangle {
+        x_min: edge.x_min(),
+        y_min: edge.y_min(),
+        x_max: edge.x_max(),
+        y_max: edge.y_max(),
+    }
+}
+
+/// Squared distance between two bounding boxes, zero if they overlap
+fn bbox_sq_distance(a: &AARectangle, b: &AARectangle) -> fsize {
+    let dx = fsize::max(0.0, fsize::max(a.x_min - b.x_max, b.x_min - a.x_max));
+    let dy = fsize::max(0.0, fsize::max(a.y_min - b.y_max, b.y_min - a.y_max));
+    dx * dx + dy * dy
+}
+
 impl<T> From<T> for SimplePolygon
 where
     T: Borrow<AARectangle>,
@@ -359,3 +692,43 @@ where
         ])
     }
 }
+
+/// Reasons why a set of points does not form a valid [SimplePolygon]
+#[derive(Debug, Clone, PartialEq)]
+pub enum InvalidPolygon {
+    /// A polygon requires at least 3 points
+    TooFewPoints(usize),
+    /// One of the coordinates is NaN or infinite
+    NonFinitePoint(Point),
+    /// The same point occurs more than once
+    DuplicatePoint(Point),
+    /// The points do not enclose any area
+    ZeroArea,
+    /// One of the holes has holes itself
+    NestedHoles,
+    /// The holes cover the entire area of the polygon
+    HolesCoverPolygon,
+}
+
+impl Display for InvalidPolygon {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InvalidPolygon::TooFewPoints(n) => {
+                write!(f, "polygon has {n} points, at least 3 are required")
+            }
+            InvalidPolygon::NonFinitePoint(Point(x, y)) => {
+                write!(f, "polygon contains a non-finite point ({x}, {y})")
+            }
+            InvalidPolygon::DuplicatePoint(Point(x, y)) => {
+                write!(f, "polygon contains duplicate point ({x}, {y})")
+            }
+            InvalidPolygon::ZeroArea => write!(f, "polygon has no area"),
+            InvalidPolygon::NestedHoles => write!(f, "holes cannot have holes themselves"),
+            InvalidPolygon::HolesCoverPolygon => {
+                write!(f, "holes cover the entire polygon")
+            }
+        }
+    }
+}
+
+impl std::error::Error for InvalidPolygon {}
@@ -9,7 +9,7 @@ use crate::geometry::d_transformation::DTransformation;
 //See https://pages.mtu.edu/~shene/COURSES/cs3621/NOTES/geometry/geo-tran.html#:~:text=A%20rotation%20matrix%20and%20a,rotations%20followed%20by%20a%20translation.
 
 #[derive(Clone, Debug)]
-///Proper rigid transformation in matrix form
+///Rigid transformation in matrix form, which is proper unless it includes a mirroring
 pub struct Transformation {
     matrix: [[NotNan<fsize>; 3]; 3],
 }
@@ -33,12 +33,29 @@ impl Transformation {
         }
     }
 
-    pub fn from_dt(dt: &DTransformation) -> Self {
+    /// Mirroring across the x-axis (y ↦ -y)
+    pub fn from_mirror() -> Self {
         Self {
-            matrix: rot_transl_m(dt.rotation(), dt.translation()),
+            matrix: MIRROR_MATRIX,
         }
     }
 
+    pub fn from_dt(dt: &DTransformation) -> Self {
+        let matrix = rot_transl_m(dt.rotation(), dt.translation());
+        match dt.mirror() {
+            false => Self { matrix },
+            true => Self {
+                matrix: dot_prod(&matrix, &MIRROR_MATRIX),
+            },
+        }
+    }
+
+    /// Mirrors across the x-axis, after the current transformation
+    pub fn mirror(mut self) -> Self {
+        self.matrix = dot_prod(&MIRROR_MATRIX, &self.matrix);
+        self
+    }
+
     pub fn rotate(mut self, angle: fsize) -> Self {
         self.matrix = dot_prod(&rot_m(angle), &self.matrix);
         self
@@ -65,7 +82,11 @@ impl Transformation {
     }
 
     pub fn transform_from_decomposed(self, other: &DTransformation) -> Self {
-        self.rotate_translate(other.rotation(), other.translation())
+        let t = match other.mirror() {
+            true => self.mirror(),
+            false => self,
+        };
+        t.rotate_translate(other.rotation(), other.translation())
     }
 
     pub fn inverse(mut self) -> Self {
@@ -77,15 +98,22 @@ impl Transformation {
         self.matrix == EMPTY_MATRIX
     }
 
+    /// True if the transformation reverses the orientation of shapes (an odd number of mirrorings)
+    pub fn is_mirroring(&self) -> bool {
+        let m = self.matrix();
+        (m[0][0] * m[1][1] - m[0][1] * m[1][0]).into_inner() < 0.0
+    }
+
     pub fn matrix(&self) -> &[[NotNan<fsize>; 3]; 3] {
         &self.matrix
     }
 
     pub fn decompose(&self) -> DTransformation {
         let m = self.matrix();
+        //mirroring across the x-axis only affects the second column of the matrix, so the angle can be derived from the first
         let angle = m[1][0].atan2(m[0][0].into_inner());
         let (tx, ty) = (m[0][2].into_inner(), m[1][2].into_inner());
-        DTransformation::new(angle, (tx, ty))
+        DTransformation::new(angle, (tx, ty)).with_mirror(self.is_mirroring())
     }
 }
 
@@ -103,6 +131,10 @@ const _1: NotNan<fsize> = unsafe { NotNan::new_unchecked(1.0) };
 
 const EMPTY_MATRIX: [[NotNan<fsize>; 3]; 3] = [[_1, _0, _0], [_0, _1, _0], [_0, _0, _1]];
 
+const _NEG_1: NotNan<fsize> = unsafe { NotNan::new_unchecked(-1.0) };
+
+const MIRROR_MATRIX: [[NotNan<fsize>; 3]; 3] = [[_1, _0, _0], [_0, _NEG_1, _0], [_0, _0, _1]];
+
 fn rot_m(angle: fsize) -> [[NotNan<fsize>; 3]; 3] {
     let (sin, cos) = angle.sin_cos();
     let cos = NotNan::new(cos).expect("cos is NaN");
@@ -0,0 +1,87 @@
+use std::time::{Duration, Instant, SystemTime};
+
+use serde::{Deserialize, Serialize};
+
+use crate::entities::solution::Solution;
+use crate::geometry::d_transformation::DTransformation;
+use crate::geometry::primitives::aa_rectangle::AARectangle;
+
+/// Version of the [CompactSolution] format, increased whenever it changes in an incompatible way
+pub const COMPACT_SOLUTION_VERSION: u32 = 1;
+
+/// Compact and serializable representation of a [Solution], to checkpoint solutions or to pass them between processes.
+/// In contrast to a [JsonSolution](crate::io::json_solution::JsonSolution), items are described by their internal [DTransformation],
+/// which allows a `Solution` to be restored without any loss of precision,
+/// as long as the serialization format round-trips floats exactly (e.g. `serde_json` with its `float_roundtrip` feature).
+/// Only the placements are stored, everything else is rebuilt from the instance by
+/// [ProblemGeneric::import_solution](crate::entities::problems::problem_generic::ProblemGeneric::import_solution).
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct CompactSolution {
+    /// Version of the format the solution was created with, see [COMPACT_SOLUTION_VERSION]
+    pub version: u32,
+    /// Layouts which compose the solution
+    pub layouts: Vec<CompactLayout>,
+    /// Moment the solution was created
+    pub time_stamp: SystemTime,
+}
+
+/// Compact representation of a [LayoutSnapshot](crate::entities::layout::LayoutSnapshot)
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct CompactLayout {
+    /// The id of the bin used for this layout
+    pub bin_id: usize,
+    /// The bounding box of the bin, which defines the container in strip packing and open dimension problems
+    pub bin_bbox: AARectangle,
+    /// The items placed in the layout, excluding the fixed items of the bin
+    pub placed_items: Vec<CompactPlacedItem>,
+}
+
+/// Compact representation of a [PlacedItem](crate::entities::placed_item::PlacedItem)
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct CompactPlacedItem {
+    /// The id of the item in the instance
+    pub item_id: usize,
+    /// The transformation applied to the internal shape of the item
+    pub d_transf: DTransformation,
+}
+
+impl CompactSolution {
+    /// The moment the solution was created, expressed as an [Instant] like [Solution::time_stamp]
+    pub fn instant(&self) -> Instant {
+        let age = SystemTime::now()
+            .duration_since(self.time_stamp)
+            .unwrap_or(Duration::ZERO);
+        Instant::now().checked_sub(age).unwrap_or_else(Instant::now)
+    }
+}
+
+impl From<&Solution> for CompactSolution {
+    fn from(solution: &Solution) -> Self {
+        let layouts = solution
+            .layout_snapshots
+            .iter()
+            .map(|sl| CompactLayout {
+                bin_id: sl.bin.id,
+                bin_bbox: sl.bin.bbox(),
+                placed_items: sl
+                    .placed_items
+                    .values()
+                    .map(|pi| CompactPlacedItem {
+                        item_id: pi.item_id,
+                        d_transf: pi.d_transf,
+                    })
+                    .collect(),
+            })
+            .collect();
+        let now = SystemTime::now();
+        let time_stamp = now
+            .checked_sub(solution.time_stamp.elapsed())
+            .unwrap_or(now);
+
+        CompactSolution {
+            version: COMPACT_SOLUTION_VERSION,
+            layouts,
+            time_stamp,
+        }
+    }
+}
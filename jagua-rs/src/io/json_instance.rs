@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
 
 use crate::fsize;
+use crate::io::json_solution::JsonTransformation;
 
 /// The JSON representation of a problem instance
 #[derive(Serialize, Deserialize, Clone)]
@@ -19,6 +20,23 @@ pub struct JsonInstance {
     #[serde(rename = "Strip")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub strip: Option<JsonStrip>,
+    /// Container for an Open Dimension Problem
+    #[serde(rename = "OpenDimension")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub open_dimension: Option<JsonOpenDimension>,
+    /// Objective for the containers in `Objects`, if not present the cost of the used bins is minimized
+    #[serde(rename = "Objective")]
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub objective: Option<JsonObjective>,
+}
+
+/// The objective of a problem with a set of bins
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum JsonObjective {
+    /// All items have to be packed, the total cost of the used bins is minimized (bin packing)
+    MinBinCost,
+    /// A subset of the items is packed in the available bins, their total value is maximized (knapsack)
+    MaxItemValue,
 }
 
 /// The JSON representation of a bin
@@ -34,6 +52,9 @@ pub struct JsonBin {
     /// A list of zones with different quality levels
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub zones: Vec<JsonQualityZone>,
+    /// A list of items already placed in the bin, which cannot be moved or removed
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub fixed_placements: Vec<JsonFixedPlacement>,
 }
 
 /// The JSON representation of a strip with fixed height and variable width
@@ -41,6 +62,33 @@ pub struct JsonBin {
 #[serde(rename_all = "PascalCase")]
 pub struct JsonStrip {
     pub height: fsize,
+    /// A list of items already placed in the strip, which cannot be moved or removed.
+    /// The strip starts at x = 0.
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub fixed_placements: Vec<JsonFixedPlacement>,
+}
+
+/// The JSON representation of an item fixed in place in a container
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct JsonFixedPlacement {
+    /// The index of the item in the instance
+    pub index: usize,
+    /// The transformation applied to the item to place it in the container
+    pub transformation: JsonTransformation,
+}
+
+/// The JSON representation of a rectangular container with one or two variable dimensions
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct JsonOpenDimension {
+    /// Fixed width of the container, of which the height is minimized.
+    /// If not present, the area of the container is minimized.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub width: Option<fsize>,
+    /// Fixed ratio of the width to the height of the container, only if the width is not fixed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub aspect_ratio: Option<fsize>,
 }
 
 /// The JSON representation of an item
@@ -49,9 +97,12 @@ pub struct JsonStrip {
 pub struct JsonItem {
     /// Number of times this item should be produced
     pub demand: u64,
-    /// List of allowed orientations angles (in degrees). If none any orientation is allowed
+    /// List of allowed orientations angles or ranges of angles (in degrees). If none any orientation is allowed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allowed_orientations: Option<Vec<JsonOrientation>>,
+    /// Whether the item can also be placed mirrored, if none it cannot
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub allowed_orientations: Option<Vec<fsize>>,
+    pub allowed_mirroring: Option<bool>,
     /// Polygon shape of the item
     pub shape: JsonShape,
     /// The value of the item (for knapsack problems)
@@ -60,6 +111,16 @@ pub struct JsonItem {
     pub base_quality: Option<usize>,
 }
 
+/// An allowed orientation of an item (in degrees)
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(untagged)]
+pub enum JsonOrientation {
+    /// A single angle, e.g. `90.0`
+    Angle(fsize),
+    /// An inclusive range of angles, e.g. `[-3.0, 3.0]`
+    Range(fsize, fsize),
+}
+
 /// Different ways to represent a shape
 #[derive(Serialize, Deserialize, Clone)]
 #[serde(tag = "Type", content = "Data")]
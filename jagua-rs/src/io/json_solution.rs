@@ -12,6 +12,9 @@ pub struct JsonSolution {
     pub run_time_sec: u64,
     /// Layouts which compose the solution
     pub layouts: Vec<JsonLayout>,
+    /// Total value of the placed items, only for knapsack problems
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub value: Option<u64>,
 }
 
 /// Representation how a set of items are placed in a certain container
@@ -34,9 +37,12 @@ pub struct JsonPlacedItem {
     pub index: usize,
     /// The transformation applied to the item to place it in the container
     pub transformation: JsonTransformation,
+    /// Whether the item is fixed in place by the instance, and thus cannot be removed
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub locked: bool,
 }
 
-/// Represents a proper rigid transformation defined as a rotation followed by translation
+/// Represents a rigid transformation defined as an optional mirroring, followed by a rotation and a translation
 #[derive(Serialize, Deserialize, Clone)]
 #[serde(rename_all = "PascalCase")]
 pub struct JsonTransformation {
@@ -44,6 +50,9 @@ pub struct JsonTransformation {
     pub rotation: fsize,
     /// The translation vector (x, y)
     pub translation: (fsize, fsize),
+    /// Whether the item is mirrored across the x-axis (y ↦ -y) before being rotated
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub mirror: bool,
 }
 
 /// Some statistics about the layout
@@ -72,4 +81,12 @@ pub enum JsonContainer {
         #[serde(rename = "Height")]
         height: fsize,
     },
+    OpenDimension {
+        /// The width of the container
+        #[serde(rename = "Width")]
+        width: fsize,
+        /// The height of the container
+        #[serde(rename = "Height")]
+        height: fsize,
+    },
 }
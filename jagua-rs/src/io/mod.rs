@@ -1,3 +1,5 @@
+pub mod compact_solution;
 pub mod json_instance;
 pub mod json_solution;
+pub mod parse_error;
 pub mod parser;
@@ -0,0 +1,41 @@
+use std::fmt::{Display, Formatter};
+
+use crate::fsize;
+
+/// Describes why a `JsonInstance` or a set of `JsonLayout`s could not be parsed.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParseError {
+    /// The instance should specify either bins or a strip, but not both
+    InvalidContainer(String),
+    /// The height of the strip is not strictly positive
+    InvalidStripHeight(fsize),
+    /// The item at this index in the instance is invalid
+    InvalidItem { index: usize, reason: String },
+    /// The bin at this index in the instance is invalid
+    InvalidBin { index: usize, reason: String },
+    /// The layout at this index in the solution is invalid
+    InvalidLayout { index: usize, reason: String },
+    /// The solution does not match the instance as a whole
+    InvalidSolution(String),
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseError::InvalidContainer(reason) => write!(f, "invalid container: {reason}"),
+            ParseError::InvalidStripHeight(height) => {
+                write!(f, "invalid strip height: {height}, must be positive")
+            }
+            ParseError::InvalidItem { index, reason } => {
+                write!(f, "invalid item {index}: {reason}")
+            }
+            ParseError::InvalidBin { index, reason } => write!(f, "invalid bin {index}: {reason}"),
+            ParseError::InvalidLayout { index, reason } => {
+                write!(f, "invalid layout {index}: {reason}")
+            }
+            ParseError::InvalidSolution(reason) => write!(f, "invalid solution: {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
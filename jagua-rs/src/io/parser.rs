@@ -7,43 +7,63 @@ use rayon::iter::IndexedParallelIterator;
 use rayon::iter::ParallelIterator;
 use rayon::prelude::IntoParallelRefIterator;
 
+use crate::collision_detection::hazard::HazardEntity;
+use crate::collision_detection::hazard_filter;
 use crate::entities::bin::Bin;
 use crate::entities::instances::bin_packing::BPInstance;
 use crate::entities::instances::instance::Instance;
 use crate::entities::instances::instance_generic::InstanceGeneric;
+use crate::entities::instances::knapsack::KPInstance;
+use crate::entities::instances::open_dimension::{ODInstance, OpenDimension};
 use crate::entities::instances::strip_packing::SPInstance;
 use crate::entities::item::Item;
+use crate::entities::layout::LayoutSnapshot;
+use crate::entities::placed_item::PlacedItem;
 use crate::entities::placing_option::PlacingOption;
 use crate::entities::problems::bin_packing::BPProblem;
+use crate::entities::problems::knapsack::KPProblem;
+use crate::entities::problems::open_dimension::ODProblem;
 use crate::entities::problems::problem_generic::{LayoutIndex, ProblemGeneric, STRIP_LAYOUT_IDX};
 use crate::entities::problems::strip_packing::SPProblem;
 use crate::entities::quality_zone::InferiorQualityZone;
 use crate::entities::quality_zone::N_QUALITIES;
 use crate::entities::solution::Solution;
 use crate::fsize;
+use crate::geometry::boolean_ops::{self, BooleanOp};
 use crate::geometry::d_transformation::DTransformation;
-use crate::geometry::geo_enums::AllowedRotation;
-use crate::geometry::geo_traits::{Shape, Transformable};
+use crate::geometry::geo_enums::{AllowedMirroring, AllowedRotation};
+use crate::geometry::geo_traits::{CollidesWith, Shape, Transformable};
 use crate::geometry::primitives::aa_rectangle::AARectangle;
 use crate::geometry::primitives::point::Point;
 use crate::geometry::primitives::simple_polygon::SimplePolygon;
 use crate::geometry::transformation::Transformation;
-use crate::io::json_instance::{JsonBin, JsonInstance, JsonItem, JsonShape, JsonSimplePoly};
+use crate::io::json_instance::{
+    JsonBin, JsonFixedPlacement, JsonInstance, JsonItem, JsonObjective, JsonOpenDimension,
+    JsonOrientation, JsonPoly, JsonShape, JsonSimplePoly,
+};
 use crate::io::json_solution::{
     JsonContainer, JsonLayout, JsonLayoutStats, JsonPlacedItem, JsonSolution, JsonTransformation,
 };
+use crate::io::parse_error::ParseError;
 use crate::util::config::CDEConfig;
 use crate::util::polygon_simplification;
 use crate::util::polygon_simplification::{PolySimplConfig, PolySimplMode};
+use crate::util::polygon_validation;
+use crate::util::polygon_validation::{PolyValidationPolicy, DEFAULT_VALIDATION_TOLERANCE};
+
+/// Scale factor of the rectangle enclosing the sheets of a bin consisting of multiple sheets
+const MULTI_SHEET_MARGIN: fsize = 1.1;
 
 /// Parses a `JsonInstance` into an `Instance`.
 pub struct Parser {
     poly_simpl_config: PolySimplConfig,
+    poly_validation: PolyValidationPolicy,
     cde_config: CDEConfig,
     center_polygons: bool,
 }
 
 impl Parser {
+    /// Creates a parser which does not validate the polygons, see [`Parser::with_validation_policy`].
     pub fn new(
         poly_simpl_config: PolySimplConfig,
         cde_config: CDEConfig,
@@ -51,34 +71,100 @@ impl Parser {
     ) -> Parser {
         Parser {
             poly_simpl_config,
+            poly_validation: PolyValidationPolicy::default(),
             cde_config,
             center_polygons,
         }
     }
 
+    /// Sets how the parser handles polygons with defects.
+    pub fn with_validation_policy(mut self, poly_validation: PolyValidationPolicy) -> Parser {
+        self.poly_validation = poly_validation;
+        self
+    }
+
     /// Parses a `JsonInstance` into an `Instance`.
+    /// Panics if the instance is invalid, see [`Parser::try_parse`] for a fallible alternative.
     pub fn parse(&self, json_instance: &JsonInstance) -> Instance {
-        let items = json_instance
-            .items
-            .par_iter()
-            .enumerate()
-            .map(|(item_id, json_item)| self.parse_item(json_item, item_id))
-            .collect();
-
-        let instance: Instance = match (json_instance.bins.as_ref(), json_instance.strip.as_ref()) {
-            (Some(json_bins), None) => {
-                let bins: Vec<(Bin, usize)> = json_bins
-                    .par_iter()
-                    .enumerate()
-                    .map(|(bin_id, json_bin)| self.parse_bin(json_bin, bin_id))
-                    .collect();
-                BPInstance::new(items, bins).into()
+        self.try_parse(json_instance)
+            .unwrap_or_else(|e| panic!("failed to parse instance: {e}"))
+    }
+
+    /// Parses a `JsonInstance` into an `Instance`, or returns a [`ParseError`] describing why the instance is invalid.
+    pub fn try_parse(&self, json_instance: &JsonInstance) -> Result<Instance, ParseError> {
+        let items = self.try_parse_items(&json_instance.items)?;
+
+        let instance: Instance = match (
+            json_instance.bins.as_ref(),
+            json_instance.strip.as_ref(),
+            json_instance.open_dimension.as_ref(),
+        ) {
+            (Some(json_bins), None, None) => {
+                let bins = self.try_parse_bins(json_bins, &items)?;
+                match json_instance.objective {
+                    None | Some(JsonObjective::MinBinCost) => BPInstance::new(items, bins).into(),
+                    Some(JsonObjective::MaxItemValue) => {
+                        //all bins are available from the start, so their stock has to be limited
+                        if let Some(bin_id) = json_bins.iter().position(|jb| jb.stock.is_none()) {
+                            return Err(ParseError::InvalidBin {
+                                index: bin_id,
+                                reason: "knapsack problems require a stock for every bin"
+                                    .to_string(),
+                            });
+                        }
+                        KPInstance::new(items, bins).into()
+                    }
+                }
+            }
+            (None, _, _) if json_instance.objective.is_some() => {
+                return Err(ParseError::InvalidContainer(
+                    "an objective can only be specified for bins".to_string(),
+                ))
+            }
+            (None, Some(json_strip), None) => {
+                let height = json_strip.height;
+                if !(height.is_finite() && height > 0.0) {
+                    return Err(ParseError::InvalidStripHeight(height));
+                }
+                let fixed_items = parse_fixed_items(
+                    &items,
+                    &json_strip.fixed_placements,
+                    &Transformation::empty(),
+                )
+                .and_then(|fixed_items| {
+                    //check the fixed items in a strip wide enough to contain all of them
+                    let width = fixed_items
+                        .iter()
+                        .map(|fi| fi.bbox().x_max)
+                        .fold(0.0, fsize::max)
+                        + height;
+                    let strip = Bin::from_strip(
+                        AARectangle::new(0.0, 0.0, width, height),
+                        self.cde_config,
+                    )
+                    .with_fixed_items(fixed_items);
+                    check_fixed_items(&strip)?;
+                    Ok(strip.fixed_items)
+                })
+                .map_err(ParseError::InvalidContainer)?;
+                SPInstance::new(items, height, fixed_items).into()
+            }
+            (None, None, Some(json_open_dim)) => {
+                let open_dim = parse_open_dimension(json_open_dim)?;
+                ODInstance::new(items, open_dim).into()
+            }
+            (None, None, None) => {
+                return Err(ParseError::InvalidContainer(
+                    "no container specified, has to be bins, a strip or an open dimension"
+                        .to_string(),
+                ))
             }
-            (None, Some(json_strip)) => SPInstance::new(items, json_strip.height).into(),
-            (Some(_), Some(_)) => {
-                panic!("Both bins and strip packing specified, has to be one or the other")
+            _ => {
+                return Err(ParseError::InvalidContainer(
+                    "multiple containers specified, has to be one of bins, a strip or an open dimension"
+                        .to_string(),
+                ))
             }
-            (None, None) => panic!("Neither bins or strips specified"),
         };
 
         match &instance {
@@ -103,9 +189,69 @@ impl Parser {
                     bpi.bins.len()
                 );
             }
+            Instance::KP(kpi) => {
+                log!(
+                    Level::Info,
+                    "[PARSE] knapsack instance \"{}\": {} items ({} unique) with a total value of {}, {} bins ({} unique)",
+                    json_instance.name,
+                    kpi.total_item_qty(),
+                    kpi.items.len(),
+                    kpi.item_value(),
+                    kpi.bins.iter().map(|(_, qty)| *qty).sum::<usize>(),
+                    kpi.bins.len()
+                );
+            }
+            Instance::OD(odi) => {
+                log!(
+                    Level::Info,
+                    "[PARSE] open dimension instance \"{}\": {} items ({} unique), {:?}",
+                    json_instance.name,
+                    odi.total_item_qty(),
+                    odi.items.len(),
+                    odi.open_dim
+                );
+            }
         }
 
-        instance
+        Ok(instance)
+    }
+
+    /// Parses the items of a `JsonInstance`.
+    /// Together with [`Self::try_parse_bins`], this allows custom instance variants to be built from a `JsonInstance`.
+    pub fn try_parse_items(
+        &self,
+        json_items: &[JsonItem],
+    ) -> Result<Vec<(Item, usize)>, ParseError> {
+        json_items
+            .par_iter()
+            .enumerate()
+            .map(|(item_id, json_item)| {
+                self.parse_item(json_item, item_id)
+                    .map_err(|reason| ParseError::InvalidItem {
+                        index: item_id,
+                        reason,
+                    })
+            })
+            .collect()
+    }
+
+    /// Parses the bins of a `JsonInstance`, including the fixed placements of the `items` in them.
+    pub fn try_parse_bins(
+        &self,
+        json_bins: &[JsonBin],
+        items: &[(Item, usize)],
+    ) -> Result<Vec<(Bin, usize)>, ParseError> {
+        json_bins
+            .par_iter()
+            .enumerate()
+            .map(|(bin_id, json_bin)| {
+                self.parse_bin(json_bin, bin_id, items)
+                    .map_err(|reason| ParseError::InvalidBin {
+                        index: bin_id,
+                        reason,
+                    })
+            })
+            .collect()
     }
 
     /// Parses a `JsonInstance` and accompanying `JsonLayout`s into an `Instance` and `Solution`.
@@ -114,111 +260,144 @@ impl Parser {
         json_instance: &JsonInstance,
         json_layouts: &[JsonLayout],
     ) -> (Instance, Solution) {
-        let instance = Arc::new(self.parse(json_instance));
-        let solution = build_solution_from_json(instance.as_ref(), json_layouts, self.cde_config);
+        self.try_parse_and_build_solution(json_instance, json_layouts)
+            .unwrap_or_else(|e| panic!("failed to parse instance and solution: {e}"))
+    }
+
+    /// Fallible version of [`Parser::parse_and_build_solution`].
+    pub fn try_parse_and_build_solution(
+        &self,
+        json_instance: &JsonInstance,
+        json_layouts: &[JsonLayout],
+    ) -> Result<(Instance, Solution), ParseError> {
+        let instance = Arc::new(self.try_parse(json_instance)?);
+        let solution =
+            try_build_solution_from_json(instance.as_ref(), json_layouts, self.cde_config)?;
         let instance =
             Arc::try_unwrap(instance).expect("Cannot unwrap instance, strong references present");
-        (instance, solution)
+        Ok((instance, solution))
     }
 
-    fn parse_item(&self, json_item: &JsonItem, item_id: usize) -> (Item, usize) {
-        let (shape, centering_transf) = match &json_item.shape {
-            JsonShape::Rectangle { width, height } => {
-                let shape = SimplePolygon::from(AARectangle::new(0.0, 0.0, *width, *height));
-                (shape, Transformation::empty())
-            }
-            JsonShape::SimplePolygon(sp) => convert_json_simple_poly(
-                sp,
-                self.center_polygons,
-                self.poly_simpl_config,
-                PolySimplMode::Inflate,
-            ),
-            JsonShape::Polygon(_) => {
-                unimplemented!("No support for polygon shapes yet")
-            }
-            JsonShape::MultiPolygon(_) => {
-                unimplemented!("No support for multipolygon shapes yet")
-            }
+    /// Parses a single item, returns the reason why it is invalid on failure.
+    fn parse_item(&self, json_item: &JsonItem, item_id: usize) -> Result<(Item, usize), String> {
+        let shapes = self.convert_json_shape(&json_item.shape, PolySimplMode::Inflate)?;
+        let (shapes, centering_transf) = match (&json_item.shape, self.center_polygons) {
+            (JsonShape::Rectangle { .. }, _) | (_, false) => (shapes, Transformation::empty()),
+            (_, true) => center_shapes(shapes),
         };
 
         let item_value = json_item.value.unwrap_or(0);
         let base_quality = json_item.base_quality;
+        if let Some(quality) = base_quality.filter(|q| *q >= N_QUALITIES) {
+            return Err(format!(
+                "base quality {quality} must be less than {N_QUALITIES}"
+            ));
+        }
 
         let allowed_orientations = match json_item.allowed_orientations.as_ref() {
             Some(a_o) => {
-                if a_o.is_empty() || (a_o.len() == 1 && a_o[0] == 0.0) {
-                    AllowedRotation::None
-                } else {
-                    AllowedRotation::Discrete(a_o.iter().map(|angle| angle.to_radians()).collect())
+                if let Some(JsonOrientation::Range(min, max)) = a_o
+                    .iter()
+                    .find(|o| matches!(o, JsonOrientation::Range(min, max) if min > max))
+                {
+                    return Err(format!("invalid orientation range [{min}, {max}]"));
+                }
+                let angles = a_o
+                    .iter()
+                    .map(|o| match o {
+                        JsonOrientation::Angle(angle) => Some(*angle),
+                        JsonOrientation::Range(..) => None,
+                    })
+                    .collect::<Option<Vec<fsize>>>();
+                match angles {
+                    Some(angles) if angles.is_empty() || angles == [0.0] => AllowedRotation::None,
+                    Some(angles) => {
+                        AllowedRotation::Discrete(angles.iter().map(|a| a.to_radians()).collect())
+                    }
+                    None => {
+                        //single angles are treated as degenerate ranges
+                        let ranges = a_o
+                            .iter()
+                            .map(|o| match o {
+                                JsonOrientation::Angle(angle) => (*angle, *angle),
+                                JsonOrientation::Range(min, max) => (*min, *max),
+                            })
+                            .map(|(min, max)| (min.to_radians(), max.to_radians()))
+                            .collect();
+                        AllowedRotation::Ranges(ranges)
+                    }
                 }
             }
             None => AllowedRotation::Continuous,
         };
 
-        (
-            Item::new(
-                item_id,
-                shape,
-                item_value,
-                allowed_orientations,
-                centering_transf,
-                base_quality,
-                self.cde_config.item_surrogate_config,
-            ),
+        let allowed_mirroring = match json_item.allowed_mirroring {
+            Some(true) => AllowedMirroring::Optional,
+            Some(false) | None => AllowedMirroring::None,
+        };
+
+        let item = Item::new_multi(
+            item_id,
+            shapes,
+            item_value,
+            allowed_orientations,
+            centering_transf,
+            base_quality,
+            self.cde_config.item_surrogate_config,
+        );
+
+        Ok((
+            Item {
+                allowed_mirroring,
+                ..item
+            },
             json_item.demand as usize,
-        )
+        ))
     }
 
-    fn parse_bin(&self, json_bin: &JsonBin, bin_id: usize) -> (Bin, usize) {
-        let (bin_outer, centering_transf) = match &json_bin.shape {
+    /// Parses a single bin, returns the reason why it is invalid on failure.
+    fn parse_bin(
+        &self,
+        json_bin: &JsonBin,
+        bin_id: usize,
+        items: &[(Item, usize)],
+    ) -> Result<(Bin, usize), String> {
+        let (bin_outer, bin_holes) = match &json_bin.shape {
             JsonShape::Rectangle { width, height } => {
-                let shape = SimplePolygon::from(AARectangle::new(0.0, 0.0, *width, *height));
-                (shape, Transformation::empty())
+                (convert_json_rectangle(*width, *height)?, vec![])
             }
-            JsonShape::SimplePolygon(jsp) => convert_json_simple_poly(
-                jsp,
-                self.center_polygons,
-                self.poly_simpl_config,
-                PolySimplMode::Deflate,
-            ),
-            JsonShape::Polygon(jp) => convert_json_simple_poly(
-                &jp.outer,
-                self.center_polygons,
-                self.poly_simpl_config,
-                PolySimplMode::Deflate,
-            ),
-            JsonShape::MultiPolygon(_) => {
-                unimplemented!("No support for multipolygon shapes yet")
+            JsonShape::SimplePolygon(jsp) => self.convert_json_sheets(&[(jsp, &[])])?,
+            JsonShape::Polygon(jp) => self.convert_json_sheets(&[(&jp.outer, &jp.inner)])?,
+            JsonShape::MultiPolygon(jps) => {
+                let sheets = jps
+                    .iter()
+                    .map(|jp| (&jp.outer, jp.inner.as_slice()))
+                    .collect_vec();
+                self.convert_json_sheets(&sheets)?
             }
         };
 
-        let bin_holes = match &json_bin.shape {
-            JsonShape::SimplePolygon(_) | JsonShape::Rectangle { .. } => vec![],
-            JsonShape::Polygon(jp) => jp
-                .inner
-                .iter()
-                .map(|jsp| {
-                    let (hole, _) = convert_json_simple_poly(
-                        jsp,
-                        false,
-                        self.poly_simpl_config,
-                        PolySimplMode::Inflate,
-                    );
-                    hole.transform_clone(&centering_transf)
-                })
-                .collect_vec(),
-            JsonShape::MultiPolygon(_) => {
-                unimplemented!("No support for multipolygon shapes yet")
-            }
+        let (bin_outer, centering_transf) = match &json_bin.shape {
+            JsonShape::Rectangle { .. } => (bin_outer, Transformation::empty()),
+            _ => match self.center_polygons {
+                true => bin_outer.center_around_centroid(),
+                false => (bin_outer, Transformation::empty()),
+            },
         };
+        let bin_holes = bin_holes
+            .into_iter()
+            .map(|hole| hole.transform_clone(&centering_transf))
+            .collect_vec();
 
         let material_value =
             (bin_outer.area() - bin_holes.iter().map(|hole| hole.area()).sum::<fsize>()) as u64;
 
-        assert!(
-            json_bin.zones.iter().all(|zone| zone.quality < N_QUALITIES),
-            "Quality must be less than N_QUALITIES"
-        );
+        if let Some(zone) = json_bin.zones.iter().find(|z| z.quality >= N_QUALITIES) {
+            return Err(format!(
+                "zone quality {} must be less than {N_QUALITIES}",
+                zone.quality
+            ));
+        }
 
         let quality_zones = (0..N_QUALITIES)
             .map(|quality| {
@@ -227,35 +406,20 @@ impl Parser {
                     .iter()
                     .filter(|zone| zone.quality == quality)
                     .map(|zone| {
-                        let (zone_shape, _) = match &zone.shape {
-                            JsonShape::Rectangle { width, height } => {
-                                let shape = SimplePolygon::from(AARectangle::new(
-                                    0.0, 0.0, *width, *height,
-                                ));
-                                (shape, Transformation::empty())
-                            }
-                            JsonShape::SimplePolygon(jsp) => convert_json_simple_poly(
-                                jsp,
-                                false,
-                                self.poly_simpl_config,
-                                PolySimplMode::Inflate,
-                            ),
-                            JsonShape::Polygon(_) => {
-                                unimplemented!(
-                                    "No support for polygon to simplepolygon conversion yet"
-                                )
-                            }
-                            JsonShape::MultiPolygon(_) => {
-                                unimplemented!("No support for multipolygon shapes yet")
-                            }
-                        };
-                        zone_shape.transform_clone(&centering_transf)
+                        //zones consisting of multiple polygons are split into separate zones
+                        let zone_shapes =
+                            self.convert_json_shape(&zone.shape, PolySimplMode::Inflate)?;
+                        Ok(zone_shapes
+                            .into_iter()
+                            .map(|zone_shape| zone_shape.transform_clone(&centering_transf))
+                            .collect_vec())
                     })
-                    .collect_vec();
+                    .flatten_ok()
+                    .collect::<Result<Vec<_>, String>>()?;
 
-                InferiorQualityZone::new(quality, zones)
+                Ok(InferiorQualityZone::new(quality, zones))
             })
-            .collect_vec();
+            .collect::<Result<Vec<_>, String>>()?;
 
         let bin = Bin::new(
             bin_id,
@@ -266,24 +430,262 @@ impl Parser {
             quality_zones,
             self.cde_config,
         );
+        let bin = match json_bin.fixed_placements.is_empty() {
+            true => bin,
+            false => {
+                let fixed_items =
+                    parse_fixed_items(items, &json_bin.fixed_placements, &bin.pretransform)?;
+                let bin = bin.with_fixed_items(fixed_items);
+                check_fixed_items(&bin)?;
+                bin
+            }
+        };
         let stock = json_bin.stock.unwrap_or(u64::MAX) as usize;
 
-        (bin, stock)
+        Ok((bin, stock))
+    }
+
+    /// Converts a shape into one or more polygons, applying the validation policy and simplification.
+    /// Rectangles are not validated or simplified.
+    fn convert_json_shape(
+        &self,
+        json_shape: &JsonShape,
+        simpl_mode: PolySimplMode,
+    ) -> Result<Vec<SimplePolygon>, String> {
+        match json_shape {
+            JsonShape::Rectangle { width, height } => {
+                Ok(vec![convert_json_rectangle(*width, *height)?])
+            }
+            JsonShape::SimplePolygon(jsp) => self.convert_json_simple_poly(jsp, simpl_mode),
+            JsonShape::Polygon(jp) => self.convert_json_poly(jp, simpl_mode),
+            JsonShape::MultiPolygon(jps) => {
+                if jps.is_empty() {
+                    return Err("multipolygon contains no polygons".to_string());
+                }
+                jps.iter()
+                    .map(|jp| self.convert_json_poly(jp, simpl_mode))
+                    .flatten_ok()
+                    .collect()
+            }
+        }
+    }
+
+    /// Converts a simple polygon, which is split into multiple polygons if it is repaired at a self-intersection.
+    fn convert_json_simple_poly(
+        &self,
+        s_json_shape: &JsonSimplePoly,
+        simpl_mode: PolySimplMode,
+    ) -> Result<Vec<SimplePolygon>, String> {
+        let points = json_simple_poly_to_points(s_json_shape);
+
+        let contours = match self.poly_validation {
+            PolyValidationPolicy::Disabled => vec![points],
+            policy => {
+                let defects = polygon_validation::validate(&points, DEFAULT_VALIDATION_TOLERANCE);
+                match (policy, defects.first()) {
+                    (_, None) => vec![points],
+                    (PolyValidationPolicy::Warn, Some(defect)) => {
+                        log!(
+                            Level::Warn,
+                            "[PARSE] polygon has {} defect(s), first: {}",
+                            defects.len(),
+                            defect
+                        );
+                        vec![points]
+                    }
+                    (PolyValidationPolicy::Repair, Some(_)) => {
+                        let repaired =
+                            polygon_validation::repair(&points, DEFAULT_VALIDATION_TOLERANCE);
+                        if repaired.is_empty() {
+                            return Err("polygon collapsed entirely during repair".to_string());
+                        }
+                        log!(
+                            Level::Debug,
+                            "[PARSE] repaired polygon with {} defect(s) into {} polygon(s)",
+                            defects.len(),
+                            repaired.len()
+                        );
+                        repaired
+                    }
+                    (_, Some(defect)) => {
+                        return Err(format!(
+                            "polygon has {} defect(s), first: {}",
+                            defects.len(),
+                            defect
+                        ))
+                    }
+                }
+            }
+        };
+
+        contours
+            .into_iter()
+            .map(|points| {
+                let shape = SimplePolygon::try_new(points).map_err(|e| e.to_string())?;
+                Ok(match self.poly_simpl_config {
+                    PolySimplConfig::Enabled { tolerance } => {
+                        polygon_simplification::simplify_shape(&shape, simpl_mode, tolerance)
+                    }
+                    PolySimplConfig::Disabled => shape,
+                })
+            })
+            .collect()
+    }
+
+    /// Converts a polygon with holes, the holes are simplified in the opposite direction of the outer boundary.
+    fn convert_json_poly(
+        &self,
+        json_poly: &JsonPoly,
+        simpl_mode: PolySimplMode,
+    ) -> Result<Vec<SimplePolygon>, String> {
+        let (outers, holes) =
+            self.convert_json_contours(&json_poly.outer, &json_poly.inner, simpl_mode)?;
+        let holes = assign_holes(&outers, holes)?;
+
+        outers
+            .into_iter()
+            .zip(holes)
+            .map(|(outer, holes)| {
+                SimplePolygon::try_with_holes(outer.points, holes).map_err(|e| e.to_string())
+            })
+            .collect()
+    }
+
+    /// Converts the outer boundary and the holes of a polygon separately.
+    fn convert_json_contours(
+        &self,
+        outer: &JsonSimplePoly,
+        inner: &[JsonSimplePoly],
+        simpl_mode: PolySimplMode,
+    ) -> Result<(Vec<SimplePolygon>, Vec<SimplePolygon>), String> {
+        let outers = self.convert_json_simple_poly(outer, simpl_mode)?;
+        let holes = inner
+            .iter()
+            .map(|jsp| self.convert_json_simple_poly(jsp, simpl_mode.flip()))
+            .flatten_ok()
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok((outers, holes))
+    }
+
+    /// Converts sheets of material into the contour of a bin and its holes.
+    /// The contours of the sheets are deflated, while their holes are inflated.
+    /// Multiple sheets, or a single sheet which was split during repair, are enclosed with [enclose_sheets].
+    fn convert_json_sheets(
+        &self,
+        json_sheets: &[(&JsonSimplePoly, &[JsonSimplePoly])],
+    ) -> Result<(SimplePolygon, Vec<SimplePolygon>), String> {
+        let mut sheets = vec![];
+        let mut sheet_holes = vec![];
+        for (outer, inner) in json_sheets {
+            let (outers, holes) =
+                self.convert_json_contours(outer, inner, PolySimplMode::Deflate)?;
+            sheets.extend(outers);
+            sheet_holes.extend(holes);
+        }
+
+        match (json_sheets.len(), sheets.len()) {
+            (1, 1) => Ok((sheets.remove(0), sheet_holes)),
+            _ => enclose_sheets(sheets, sheet_holes),
+        }
     }
 }
 
+/// Converts multiple disjoint sheets of material into the contour of a single bin and its holes.
+/// The contour is a rectangle enclosing all sheets, and the space between the sheets is modeled as additional holes.
+/// To avoid holes which have holes themselves, this space is split into vertical bands,
+/// with a cut through the interior of every sheet, so no sheet lies isolated within a single band.
+fn enclose_sheets(
+    sheets: Vec<SimplePolygon>,
+    sheet_holes: Vec<SimplePolygon>,
+) -> Result<(SimplePolygon, Vec<SimplePolygon>), String> {
+    //enclose the sheets with a margin, so they are strictly inside the contour
+    let bbox = sheets
+        .iter()
+        .map(|s| s.bbox())
+        .reduce(|a, b| AARectangle::bounding_rectangle(&a, &b))
+        .ok_or("bin has no sheets")?
+        .scale(MULTI_SHEET_MARGIN);
+
+    let cuts = sheets
+        .iter()
+        .map(|s| s.poi.center.0)
+        .sorted_by(|a, b| a.total_cmp(b))
+        .dedup();
+    let bounds = std::iter::once(bbox.x_min)
+        .chain(cuts)
+        .chain(std::iter::once(bbox.x_max));
+
+    let mut holes = vec![];
+    for (x_min, x_max) in bounds.tuple_windows() {
+        let band = SimplePolygon::from(AARectangle::new(x_min, bbox.y_min, x_max, bbox.y_max));
+        for gap in boolean_ops::boolean_op(&[band], &sheets, BooleanOp::Difference) {
+            if !gap.holes.is_empty() {
+                return Err(
+                    "space between the sheets cannot be modeled without nested holes".into(),
+                );
+            }
+            holes.push(gap);
+        }
+    }
+    holes.extend(sheet_holes);
+    Ok((SimplePolygon::from(bbox), holes))
+}
+
+/// Assigns each hole to the polygon which contains it
+fn assign_holes(
+    outers: &[SimplePolygon],
+    holes: Vec<SimplePolygon>,
+) -> Result<Vec<Vec<SimplePolygon>>, String> {
+    let mut assigned = vec![vec![]; outers.len()];
+    for hole in holes {
+        let index = match outers.len() {
+            1 => Some(0),
+            _ => outers
+                .iter()
+                .position(|o| o.collides_with(&hole.poi.center)),
+        };
+        match index {
+            Some(index) => assigned[index].push(hole),
+            None => return Err("hole is not contained in the polygon".to_string()),
+        }
+    }
+    Ok(assigned)
+}
+
 /// Builds a `Solution` from a set of `JsonLayout`s and an `Instance`.
+/// Panics if the layouts are invalid, see [`try_build_solution_from_json`] for a fallible alternative.
 pub fn build_solution_from_json(
     instance: &Instance,
     json_layouts: &[JsonLayout],
     cde_config: CDEConfig,
 ) -> Solution {
+    try_build_solution_from_json(instance, json_layouts, cde_config)
+        .unwrap_or_else(|e| panic!("failed to build solution: {e}"))
+}
+
+/// Builds a `Solution` from a set of `JsonLayout`s and an `Instance`, or returns a [`ParseError`] describing why the layouts are invalid.
+pub fn try_build_solution_from_json(
+    instance: &Instance,
+    json_layouts: &[JsonLayout],
+    cde_config: CDEConfig,
+) -> Result<Solution, ParseError> {
     match instance {
         Instance::BP(bp_i) => build_bin_packing_solution(bp_i, json_layouts),
-        Instance::SP(sp_i) => {
-            assert_eq!(json_layouts.len(), 1);
-            build_strip_packing_solution(sp_i, &json_layouts[0], cde_config)
-        }
+        Instance::KP(kp_i) => build_knapsack_solution(kp_i, json_layouts),
+        Instance::OD(od_i) => match json_layouts {
+            [json_layout] => build_open_dimension_solution(od_i, json_layout, cde_config),
+            _ => Err(ParseError::InvalidSolution(format!(
+                "open dimension solution should contain exactly 1 layout, found {}",
+                json_layouts.len()
+            ))),
+        },
+        Instance::SP(sp_i) => match json_layouts {
+            [json_layout] => build_strip_packing_solution(sp_i, json_layout, cde_config),
+            _ => Err(ParseError::InvalidSolution(format!(
+                "strip packing solution should contain exactly 1 layout, found {}",
+                json_layouts.len()
+            ))),
+        },
     }
 }
 
@@ -291,22 +693,57 @@ pub fn build_strip_packing_solution(
     instance: &SPInstance,
     json_layout: &JsonLayout,
     cde_config: CDEConfig,
-) -> Solution {
+) -> Result<Solution, ParseError> {
+    let invalid = |reason: String| ParseError::InvalidLayout { index: 0, reason };
+
     let mut problem = match json_layout.container {
         JsonContainer::Bin { .. } => {
-            panic!("Strip packing solution should not contain layouts with references to an Object")
+            return Err(invalid(
+                "strip packing solution should not contain layouts with references to a bin"
+                    .to_string(),
+            ))
+        }
+        JsonContainer::OpenDimension { .. } => {
+            return Err(invalid(
+                "strip packing solution should not contain an open dimension container".to_string(),
+            ))
         }
         JsonContainer::Strip { width, height: _ } => {
+            if !(width.is_finite() && width > 0.0) {
+                return Err(invalid(format!(
+                    "invalid strip width: {width}, must be positive"
+                )));
+            }
             SPProblem::new(instance.clone(), width, cde_config)
         }
     };
 
-    for json_item in json_layout.placed_items.iter() {
+    check_placed_items(instance, json_layout).map_err(invalid)?;
+
+    //the strip of the solution can be shifted with respect to the fixed items, which are positioned relative to x = 0
+    let first_locked = json_layout.placed_items.iter().find(|jpi| jpi.locked);
+    if let (Some(jpi), Some(fi), JsonContainer::Strip { width, height }) = (
+        first_locked,
+        instance.fixed_items.first(),
+        &json_layout.container,
+    ) {
+        let fi_abs_transf = internal_to_absolute_transform(
+            &fi.d_transf,
+            &instance.item(fi.item_id).pretransform,
+            &Transformation::empty(),
+        )
+        .decompose();
+        let shift = fi_abs_transf.translation().0 - jpi.transformation.translation.0;
+        problem.modify_strip(AARectangle::new(shift, 0.0, shift + width, *height));
+    }
+
+    for json_item in json_layout.placed_items.iter().filter(|jpi| !jpi.locked) {
         let item = instance.item(json_item.index);
         let json_rotation = json_item.transformation.rotation;
         let json_translation = json_item.transformation.translation;
 
-        let abs_transform = DTransformation::new(json_rotation, json_translation);
+        let abs_transform = DTransformation::new(json_rotation, json_translation)
+            .with_mirror(json_item.transformation.mirror);
         let transform = absolute_to_internal_transform(
             &abs_transform,
             &item.pretransform,
@@ -325,19 +762,96 @@ pub fn build_strip_packing_solution(
         problem.flush_changes();
     }
 
-    problem.create_solution(None)
+    Ok(problem.create_solution(None))
 }
 
-pub fn build_bin_packing_solution(instance: &BPInstance, json_layouts: &[JsonLayout]) -> Solution {
+pub fn build_open_dimension_solution(
+    instance: &ODInstance,
+    json_layout: &JsonLayout,
+    cde_config: CDEConfig,
+) -> Result<Solution, ParseError> {
+    let invalid = |reason: String| ParseError::InvalidLayout { index: 0, reason };
+
+    let mut problem = match json_layout.container {
+        JsonContainer::OpenDimension { width, height } => {
+            if !(width.is_finite() && width > 0.0 && height.is_finite() && height > 0.0) {
+                return Err(invalid(format!(
+                    "invalid container dimensions: {width}x{height}, must be positive"
+                )));
+            }
+            ODProblem::new(instance.clone(), width, height, cde_config)
+        }
+        JsonContainer::Bin { .. } | JsonContainer::Strip { .. } => {
+            return Err(invalid(
+                "open dimension solution should only contain an open dimension container"
+                    .to_string(),
+            ))
+        }
+    };
+
+    check_placed_items(instance, json_layout).map_err(invalid)?;
+
+    for json_item in json_layout.placed_items.iter().filter(|jpi| !jpi.locked) {
+        let item = instance.item(json_item.index);
+        let json_rotation = json_item.transformation.rotation;
+        let json_translation = json_item.transformation.translation;
+
+        let abs_transform = DTransformation::new(json_rotation, json_translation)
+            .with_mirror(json_item.transformation.mirror);
+        let transform = absolute_to_internal_transform(
+            &abs_transform,
+            &item.pretransform,
+            &problem.layout.bin().pretransform,
+        );
+
+        let d_transf = transform.decompose();
+
+        let placing_opt = PlacingOption {
+            layout_idx: STRIP_LAYOUT_IDX,
+            item_id: item.id,
+            d_transf,
+        };
+
+        problem.place_item(placing_opt);
+        problem.flush_changes();
+    }
+
+    Ok(problem.create_solution(None))
+}
+
+pub fn build_bin_packing_solution(
+    instance: &BPInstance,
+    json_layouts: &[JsonLayout],
+) -> Result<Solution, ParseError> {
     let mut problem = BPProblem::new(instance.clone());
 
-    for json_layout in json_layouts {
+    //check the total demand of items and stock of bins before placing anything
+    check_item_and_bin_counts(&instance.items, &instance.bins, json_layouts)?;
+
+    for (layout_index, json_layout) in json_layouts.iter().enumerate() {
+        let invalid = |reason: String| ParseError::InvalidLayout {
+            index: layout_index,
+            reason,
+        };
         let bin = match json_layout.container {
-            JsonContainer::Bin { index } => &instance.bins[index].0,
-            JsonContainer::Strip { .. } => {
-                panic!("Bin packing solution should not contain layouts with references to a Strip")
+            JsonContainer::Bin { index } => match instance.bins.get(index) {
+                Some((bin, _)) => bin,
+                None => {
+                    return Err(invalid(format!(
+                        "bin index {index} out of range, instance has {} bins",
+                        instance.bins.len()
+                    )))
+                }
+            },
+            JsonContainer::Strip { .. } | JsonContainer::OpenDimension { .. } => {
+                return Err(invalid(
+                    "bin packing solution should only contain layouts with references to a bin"
+                        .to_string(),
+                ))
             }
         };
+        check_placed_items(instance, json_layout).map_err(invalid)?;
+
         //Create the layout by inserting the first item
 
         //Find the template layout matching the bin id in the JSON solution
@@ -345,17 +859,18 @@ pub fn build_bin_packing_solution(instance: &BPInstance, json_layouts: &[JsonLay
             .template_layouts()
             .iter()
             .position(|tl| tl.bin().id == bin.id)
-            .expect("no template layout found for bin");
+            .ok_or_else(|| invalid(format!("no template layout found for bin {}", bin.id)))?;
 
-        let json_first_item = json_layout
-            .placed_items
-            .first()
-            .expect("no items in layout");
+        let mut json_items = json_layout.placed_items.iter().filter(|jpi| !jpi.locked);
+        let json_first_item = json_items
+            .next()
+            .ok_or_else(|| invalid("no items in layout".to_string()))?;
         let first_item = instance.item(json_first_item.index);
         let abs_transform = DTransformation::new(
             json_first_item.transformation.rotation,
             json_first_item.transformation.translation,
-        );
+        )
+        .with_mirror(json_first_item.transformation.mirror);
 
         let transform = absolute_to_internal_transform(
             &abs_transform,
@@ -373,12 +888,13 @@ pub fn build_bin_packing_solution(instance: &BPInstance, json_layouts: &[JsonLay
         problem.flush_changes();
 
         //Insert the rest of the items
-        for json_item in json_layout.placed_items.iter().skip(1) {
+        for json_item in json_items {
             let item = instance.item(json_item.index);
             let json_rotation = json_item.transformation.rotation;
             let json_translation = json_item.transformation.translation;
 
-            let abs_transform = DTransformation::new(json_rotation, json_translation);
+            let abs_transform = DTransformation::new(json_rotation, json_translation)
+                .with_mirror(json_item.transformation.mirror);
             let transform = absolute_to_internal_transform(
                 &abs_transform,
                 &item.pretransform,
@@ -397,31 +913,173 @@ pub fn build_bin_packing_solution(instance: &BPInstance, json_layouts: &[JsonLay
         }
     }
 
-    problem.create_solution(None)
+    Ok(problem.create_solution(None))
+}
+
+pub fn build_knapsack_solution(
+    instance: &KPInstance,
+    json_layouts: &[JsonLayout],
+) -> Result<Solution, ParseError> {
+    let mut problem = KPProblem::new(instance.clone());
+
+    //check the total quantity of items and stock of bins before placing anything
+    check_item_and_bin_counts(&instance.items, &instance.bins, json_layouts)?;
+
+    //every bin already has a layout, JSON layouts are matched to the first unused layout of their bin
+    let mut layout_used = vec![false; problem.layouts.len()];
+
+    for (layout_index, json_layout) in json_layouts.iter().enumerate() {
+        let invalid = |reason: String| ParseError::InvalidLayout {
+            index: layout_index,
+            reason,
+        };
+        let bin_id = match json_layout.container {
+            JsonContainer::Bin { index } if index < instance.bins.len() => index,
+            JsonContainer::Bin { index } => {
+                return Err(invalid(format!(
+                    "bin index {index} out of range, instance has {} bins",
+                    instance.bins.len()
+                )))
+            }
+            JsonContainer::Strip { .. } | JsonContainer::OpenDimension { .. } => {
+                return Err(invalid(
+                    "knapsack solution should only contain layouts with references to a bin"
+                        .to_string(),
+                ))
+            }
+        };
+        check_placed_items(instance, json_layout).map_err(invalid)?;
+
+        let i = (0..problem.layouts.len())
+            .find(|&i| !layout_used[i] && problem.layouts[i].bin().id == bin_id)
+            .expect("bin stock was checked");
+        layout_used[i] = true;
+        let bin = &instance.bins[bin_id].0;
+
+        for json_item in json_layout.placed_items.iter().filter(|jpi| !jpi.locked) {
+            let item = instance.item(json_item.index);
+            let json_rotation = json_item.transformation.rotation;
+            let json_translation = json_item.transformation.translation;
+
+            let abs_transform = DTransformation::new(json_rotation, json_translation)
+                .with_mirror(json_item.transformation.mirror);
+            let transform = absolute_to_internal_transform(
+                &abs_transform,
+                &item.pretransform,
+                &bin.pretransform,
+            );
+
+            let d_transf = transform.decompose();
+
+            let insert_opt = PlacingOption {
+                layout_idx: LayoutIndex::Real(i),
+                item_id: item.id,
+                d_transf,
+            };
+            problem.place_item(insert_opt);
+            problem.flush_changes();
+        }
+    }
+
+    Ok(problem.create_solution(None))
+}
+
+/// Checks whether no bin is used more than its stock, and no item is placed more than its quantity.
+fn check_item_and_bin_counts(
+    items: &[(Item, usize)],
+    bins: &[(Bin, usize)],
+    json_layouts: &[JsonLayout],
+) -> Result<(), ParseError> {
+    let bin_counts = json_layouts
+        .iter()
+        .filter_map(|jl| match jl.container {
+            JsonContainer::Bin { index } => Some(index),
+            JsonContainer::Strip { .. } | JsonContainer::OpenDimension { .. } => None,
+        })
+        .counts();
+    if let Some((&bin_id, &count)) = bin_counts
+        .iter()
+        .find(|(&id, &count)| id < bins.len() && count > bins[id].1)
+    {
+        return Err(ParseError::InvalidSolution(format!(
+            "bin {bin_id} is used {count} times, but only {} are in stock",
+            bins[bin_id].1
+        )));
+    }
+    //fixed items of the bins count toward the demand, and are not placed again
+    let item_counts = json_layouts
+        .iter()
+        .flat_map(|jl| {
+            let fixed_items = match jl.container {
+                JsonContainer::Bin { index } if index < bins.len() => {
+                    bins[index].0.fixed_items.as_slice()
+                }
+                _ => &[],
+            };
+            jl.placed_items
+                .iter()
+                .filter(|jpi| !jpi.locked)
+                .map(|jpi| jpi.index)
+                .chain(fixed_items.iter().map(|fi| fi.item_id))
+        })
+        .counts();
+    if let Some((&item_id, &count)) = item_counts
+        .iter()
+        .find(|(&id, &count)| id < items.len() && count > items[id].1)
+    {
+        return Err(ParseError::InvalidSolution(format!(
+            "item {item_id} is placed {count} times, but its demand is {}",
+            items[item_id].1
+        )));
+    }
+    Ok(())
+}
+
+/// Checks whether all placed items in a `JsonLayout` refer to items of the instance.
+fn check_placed_items(
+    instance: &impl InstanceGeneric,
+    json_layout: &JsonLayout,
+) -> Result<(), String> {
+    match json_layout
+        .placed_items
+        .iter()
+        .find(|jpi| jpi.index >= instance.items().len())
+    {
+        Some(jpi) => Err(format!(
+            "item index {} out of range, instance has {} items",
+            jpi.index,
+            instance.items().len()
+        )),
+        None => match json_layout.placed_items.iter().find(|jpi| {
+            jpi.transformation.mirror
+                && instance.item(jpi.index).allowed_mirroring == AllowedMirroring::None
+        }) {
+            Some(jpi) => Err(format!("item {} is not allowed to be mirrored", jpi.index)),
+            None => Ok(()),
+        },
+    }
 }
 
 /// Composes a `JsonSolution` from a `Solution` and an `Instance`.
 pub fn compose_json_solution(
     solution: &Solution,
-    instance: &Instance,
+    instance: &impl ComposeJson,
     epoch: Instant,
 ) -> JsonSolution {
     let layouts = solution
         .layout_snapshots
         .iter()
         .map(|sl| {
-            let container = match &instance {
-                Instance::BP(_bpi) => JsonContainer::Bin { index: sl.bin.id },
-                Instance::SP(spi) => JsonContainer::Strip {
-                    width: sl.bin.bbox().width(),
-                    height: spi.strip_height,
-                },
-            };
+            let container = instance.json_container(sl);
 
+            //fixed items are reported first, marked as locked
             let placed_items = sl
-                .placed_items
-                .values()
-                .map(|placed_item| {
+                .bin
+                .fixed_items
+                .iter()
+                .map(|fi| (fi, true))
+                .chain(sl.placed_items.values().map(|pi| (pi, false)))
+                .map(|(placed_item, locked)| {
                     let item_index = placed_item.item_id;
                     let item = instance.item(item_index);
 
@@ -437,7 +1095,9 @@ pub fn compose_json_solution(
                         transformation: JsonTransformation {
                             rotation: abs_transf.rotation(),
                             translation: abs_transf.translation(),
+                            mirror: abs_transf.mirror(),
                         },
+                        locked,
                     }
                 })
                 .collect::<Vec<JsonPlacedItem>>();
@@ -454,42 +1114,187 @@ pub fn compose_json_solution(
         layouts,
         usage: solution.usage,
         run_time_sec: solution.time_stamp.duration_since(epoch).as_secs(),
+        value: instance.json_value(solution),
     }
 }
 
-fn convert_json_simple_poly(
-    s_json_shape: &JsonSimplePoly,
-    center_polygon: bool,
-    simpl_config: PolySimplConfig,
-    simpl_mode: PolySimplMode,
-) -> (SimplePolygon, Transformation) {
-    let shape = SimplePolygon::new(json_simple_poly_to_points(s_json_shape));
-
-    let shape = match simpl_config {
-        PolySimplConfig::Enabled { tolerance } => {
-            polygon_simplification::simplify_shape(&shape, simpl_mode, tolerance)
+/// Describes how the layouts of an instance variant are represented in a [`JsonSolution`].
+/// Custom instance variants can implement it to compose their solutions with [`compose_json_solution`].
+pub trait ComposeJson: InstanceGeneric {
+    /// The container of a layout, by default a reference to its bin
+    fn json_container(&self, layout_snapshot: &LayoutSnapshot) -> JsonContainer {
+        JsonContainer::Bin {
+            index: layout_snapshot.bin.id,
         }
-        PolySimplConfig::Disabled => shape,
-    };
+    }
 
-    let (shape, centering_transform) = match center_polygon {
-        true => shape.center_around_centroid(),
-        false => (shape, Transformation::empty()),
-    };
+    /// The total value of the placed items, only reported by variants which maximize it
+    fn json_value(&self, _solution: &Solution) -> Option<u64> {
+        None
+    }
+}
 
-    (shape, centering_transform)
+impl ComposeJson for BPInstance {}
+
+impl ComposeJson for KPInstance {
+    fn json_value(&self, solution: &Solution) -> Option<u64> {
+        Some(solution.placed_item_value(self))
+    }
+}
+
+impl ComposeJson for SPInstance {
+    fn json_container(&self, layout_snapshot: &LayoutSnapshot) -> JsonContainer {
+        JsonContainer::Strip {
+            width: layout_snapshot.bin.bbox().width(),
+            height: self.strip_height,
+        }
+    }
+}
+
+impl ComposeJson for ODInstance {
+    fn json_container(&self, layout_snapshot: &LayoutSnapshot) -> JsonContainer {
+        let bbox = layout_snapshot.bin.bbox();
+        JsonContainer::OpenDimension {
+            width: bbox.width(),
+            height: bbox.height(),
+        }
+    }
+}
+
+impl ComposeJson for Instance {
+    fn json_container(&self, layout_snapshot: &LayoutSnapshot) -> JsonContainer {
+        match self {
+            Instance::BP(bpi) => bpi.json_container(layout_snapshot),
+            Instance::SP(spi) => spi.json_container(layout_snapshot),
+            Instance::KP(kpi) => kpi.json_container(layout_snapshot),
+            Instance::OD(odi) => odi.json_container(layout_snapshot),
+        }
+    }
+
+    fn json_value(&self, solution: &Solution) -> Option<u64> {
+        match self {
+            Instance::BP(bpi) => bpi.json_value(solution),
+            Instance::SP(spi) => spi.json_value(solution),
+            Instance::KP(kpi) => kpi.json_value(solution),
+            Instance::OD(odi) => odi.json_value(solution),
+        }
+    }
+}
+
+fn parse_open_dimension(json_open_dim: &JsonOpenDimension) -> Result<OpenDimension, ParseError> {
+    let positive = |v: fsize| v.is_finite() && v > 0.0;
+    match (json_open_dim.width, json_open_dim.aspect_ratio) {
+        (Some(_), Some(_)) => Err(ParseError::InvalidContainer(
+            "an open dimension container cannot have both a fixed width and aspect ratio"
+                .to_string(),
+        )),
+        (Some(width), None) if !positive(width) => Err(ParseError::InvalidContainer(format!(
+            "invalid width: {width}, must be positive"
+        ))),
+        (None, Some(ratio)) if !positive(ratio) => Err(ParseError::InvalidContainer(format!(
+            "invalid aspect ratio: {ratio}, must be positive"
+        ))),
+        (Some(width), None) => Ok(OpenDimension::Height { width }),
+        (None, aspect_ratio) => Ok(OpenDimension::Area { aspect_ratio }),
+    }
+}
+
+fn convert_json_rectangle(width: fsize, height: fsize) -> Result<SimplePolygon, String> {
+    match width > 0.0 && height > 0.0 && width.is_finite() && height.is_finite() {
+        true => Ok(SimplePolygon::from(AARectangle::new(
+            0.0, 0.0, width, height,
+        ))),
+        false => Err(format!(
+            "invalid rectangle dimensions: {width}x{height}, must be positive"
+        )),
+    }
+}
+
+/// Centers polygons as a whole around their combined centroid.
+fn center_shapes(mut shapes: Vec<SimplePolygon>) -> (Vec<SimplePolygon>, Transformation) {
+    if shapes.len() == 1 {
+        let (shape, transformation) = shapes.remove(0).center_around_centroid();
+        return (vec![shape], transformation);
+    }
+    let area = shapes.iter().map(|s| s.area()).sum::<fsize>();
+    let (m_x, m_y) = shapes.iter().fold((0.0, 0.0), |(m_x, m_y), s| {
+        let Point(c_x, c_y) = s.centroid();
+        (m_x + c_x * s.area(), m_y + c_y * s.area())
+    });
+    let transformation = Transformation::from_translation((-m_x / area, -m_y / area));
+    let shapes = shapes
+        .into_iter()
+        .map(|s| s.transform_clone(&transformation))
+        .collect_vec();
+    (shapes, transformation)
 }
 
 fn json_simple_poly_to_points(jsp: &JsonSimplePoly) -> Vec<Point> {
     //Strip the last vertex if it is the same as the first one
-    let n_vertices = match jsp.0[0] == jsp.0[jsp.0.len() - 1] {
-        true => jsp.0.len() - 1,
+    let n_vertices = match jsp.0.first() == jsp.0.last() {
+        true => jsp.0.len().saturating_sub(1),
         false => jsp.0.len(),
     };
 
     (0..n_vertices).map(|i| Point::from(jsp.0[i])).collect_vec()
 }
 
+/// Converts fixed placements into `PlacedItem`s, in the internal coordinates of a bin with `bin_pretransf`.
+fn parse_fixed_items(
+    items: &[(Item, usize)],
+    json_fixed_placements: &[JsonFixedPlacement],
+    bin_pretransf: &Transformation,
+) -> Result<Vec<PlacedItem>, String> {
+    json_fixed_placements
+        .iter()
+        .map(|jfp| {
+            let (item, _) = items.get(jfp.index).ok_or_else(|| {
+                format!(
+                    "fixed item index {} out of range, instance has {} items",
+                    jfp.index,
+                    items.len()
+                )
+            })?;
+            if jfp.transformation.mirror && item.allowed_mirroring == AllowedMirroring::None {
+                return Err(format!(
+                    "fixed item {} is not allowed to be mirrored",
+                    jfp.index
+                ));
+            }
+            let abs_transf =
+                DTransformation::new(jfp.transformation.rotation, jfp.transformation.translation)
+                    .with_mirror(jfp.transformation.mirror);
+            let transform =
+                absolute_to_internal_transform(&abs_transf, &item.pretransform, bin_pretransf);
+            Ok(PlacedItem::new(item, transform.decompose()))
+        })
+        .collect()
+}
+
+/// Checks whether the fixed items of a bin lie inside of it, without colliding with any of its hazards or each other.
+fn check_fixed_items(bin: &Bin) -> Result<(), String> {
+    let cde = bin.base_cde.as_ref();
+    for (id, fi) in bin.fixed_items.iter().enumerate() {
+        let mut irrelevant_hazards = vec![HazardEntity::FixedItem { id }];
+        if let Some(filter) = fi.hazard_filter.as_ref() {
+            irrelevant_hazards.extend(hazard_filter::generate_irrelevant_hazards(
+                filter,
+                cde.all_hazards(),
+            ));
+        }
+        if fi
+            .shapes()
+            .any(|shape| cde.poly_collides(shape, &irrelevant_hazards))
+        {
+            return Err(format!(
+                "fixed placement {id} of item {} does not fit in the container",
+                fi.item_id
+            ));
+        }
+    }
+    Ok(())
+}
+
 fn internal_to_absolute_transform(
     placed_item_transf: &DTransformation,
     item_pretransf: &Transformation,
@@ -15,6 +15,7 @@ use crate::collision_detection::quadtree::qt_hazard::QTHazard;
 use crate::collision_detection::quadtree::qt_node::QTNode;
 use crate::entities::bin::Bin;
 use crate::entities::item::Item;
+use crate::entities::layout;
 use crate::entities::layout::Layout;
 use crate::entities::layout::LayoutSnapshot;
 use crate::entities::problems::problem_generic::ProblemGeneric;
@@ -102,21 +103,17 @@ pub fn item_to_place_does_not_collide(
 ) -> bool {
     let haz_filter = &item.hazard_filter;
 
-    let shape = item.shape.as_ref();
-    let t_shape = shape.transform_clone(transformation);
-
     let entities_to_ignore = haz_filter.as_ref().map_or(vec![], |f| {
         hazard_filter::generate_irrelevant_hazards(f, layout.cde().all_hazards())
     });
 
-    if layout
-        .cde()
-        .surrogate_collides(shape.surrogate(), transformation, &entities_to_ignore)
-        || layout.cde().poly_collides(&t_shape, &entities_to_ignore)
-    {
-        return false;
-    }
-    true
+    item.shapes().all(|shape| {
+        let t_shape = shape.transform_clone(transformation);
+        !layout
+            .cde()
+            .surrogate_collides(shape.surrogate(), transformation, &entities_to_ignore)
+            && !layout.cde().poly_collides(&t_shape, &entities_to_ignore)
+    })
 }
 
 pub fn layout_is_collision_free(layout: &Layout) -> bool {
@@ -134,7 +131,10 @@ pub fn layout_is_collision_free(layout: &Layout) -> bool {
         let entities_to_ignore =
             hazard_filter::generate_irrelevant_hazards(&combo_filter, layout.cde().all_hazards());
 
-        if layout.cde().poly_collides(&pi.shape, &entities_to_ignore) {
+        if pi
+            .shapes()
+            .any(|shape| layout.cde().poly_collides(shape, &entities_to_ignore))
+        {
             println!("Collision detected for item {:.?}", pi.item_id);
             util::print_layout(layout);
             return false;
@@ -256,9 +256,15 @@ pub fn layout_qt_matches_fresh_qt(layout: &Layout) -> bool {
     //rebuild the quadtree
     let bin = layout.bin();
     let mut fresh_cde = bin.base_cde.as_ref().clone();
+    let is_custom = |h: &&Hazard| matches!(h.entity, HazardEntity::Custom { .. });
+    for hazard in layout.cde().static_hazards().iter().filter(is_custom) {
+        fresh_cde.register_static_hazard(hazard.clone());
+    }
+    for hazard in layout.cde().dynamic_hazards().iter().filter(is_custom) {
+        fresh_cde.register_hazard(hazard.clone());
+    }
     for (pik, pi) in layout.placed_items().iter() {
-        let hazard = Hazard::new(pik.into(), pi.shape.clone());
-        fresh_cde.register_hazard(hazard);
+        fresh_cde.register_hazard_components(layout::placed_item_hazards(pik, pi));
     }
 
     qt_nodes_match(Some(layout.cde().quadtree()), Some(fresh_cde.quadtree()))
@@ -437,6 +443,34 @@ pub fn hpg_update_no_affected_cells_remain(
     }
 }
 
+pub fn hpg_deregister_no_affected_cells_remain(
+    deregistered: &Hazard,
+    hpg: &HazardProximityGrid,
+) -> bool {
+    //To ensure the boundary fill algorithm did not miss any cells, check if the hazard is no longer the closest in any cell
+    let remaining_cells = hpg
+        .grid
+        .cells
+        .iter()
+        .enumerate()
+        .flat_map(|(i, cell)| cell.as_ref().map(|cell| (i, cell)))
+        .filter(|(_i, cell)| cell.uni_prox.1 == deregistered.entity)
+        .map(|(i, _cell)| hpg.grid.to_row_col(i).unwrap())
+        .collect_vec();
+
+    if !remaining_cells.is_empty() {
+        println!(
+            "{} cells still have {:?} as closest hazard: {:?}",
+            remaining_cells.len(),
+            deregistered.entity,
+            remaining_cells
+        );
+        false
+    } else {
+        true
+    }
+}
+
 pub fn quadrants_have_valid_layout(quadrants: &[&AARectangle; 4]) -> bool {
     //check top border
     let [nw, ne, sw, se] = quadrants;
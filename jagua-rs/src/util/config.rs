@@ -11,6 +11,12 @@ pub struct CDEConfig {
     pub hpg_n_cells: usize,
     ///Configuration of the surrogate generation for items
     pub item_surrogate_config: SPSurrogateConfig,
+    ///Use exact orientation predicates for edge intersection and point-in-polygon tests,
+    ///trading a little speed for results which are consistent across floating point precisions.
+    ///Only affects the collision queries of the [`CDEngine`](crate::collision_detection::cd_engine::CDEngine) configured with it,
+    ///other geometric operations (e.g. preprocessing) always use the floating point implementation.
+    #[serde(default)]
+    pub robust_predicates: bool,
 }
 
 #[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
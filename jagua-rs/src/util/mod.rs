@@ -11,6 +11,12 @@ pub mod fpa;
 /// Functions to simplify polygons in preprocessing
 pub mod polygon_simplification;
 
+/// Functions to validate and repair polygons in preprocessing
+pub mod polygon_validation;
+
+/// Exact geometric predicates, used by the [CDEngine](crate::collision_detection::cd_engine::CDEngine) when robust predicates are enabled in its [config::CDEConfig]
+pub mod predicates;
+
 ///Prints code to recreate a layout. Intended for debugging purposes.
 pub fn print_layout(layout: &Layout) {
     println!(
@@ -23,7 +29,13 @@ pub fn print_layout(layout: &Layout) {
         let transformation_str = {
             let t_decomp = &pi.d_transf;
             let (tr, (tx, ty)) = (t_decomp.rotation(), t_decomp.translation());
-            format!("&DTransformation::new({:.6},({:.6},{:.6}))", tr, tx, ty)
+            match t_decomp.mirror() {
+                false => format!("&DTransformation::new({:.6},({:.6},{:.6}))", tr, tx, ty),
+                true => format!(
+                    "&DTransformation::new({:.6},({:.6},{:.6})).with_mirror(true)",
+                    tr, tx, ty
+                ),
+            }
         };
 
         println!(
@@ -88,12 +88,13 @@ impl CornerType {
 
 /// Simplifies a shape (removing vertices) strictly inflating or deflating based on the mode.
 /// The number of edges is reduced by one at a time, until either the change in area would exceed the max_area_delta or the number of edges would become less than 4.
+/// Only the outer boundary is simplified, any holes are retained as they are.
 pub fn simplify_shape(
     shape: &SimplePolygon,
     mode: PolySimplMode,
     max_area_delta: fsize,
 ) -> SimplePolygon {
-    let original_area = shape.area();
+    let original_area = SimplePolygon::calculate_area(&shape.points);
 
     let mut ref_points = shape.points.clone();
 
@@ -171,7 +172,7 @@ pub fn simplify_shape(
     }
 
     //Convert it back to a simple polygon
-    let simpl_shape = SimplePolygon::new(ref_points);
+    let simpl_shape = SimplePolygon::with_holes(ref_points, shape.holes.clone());
 
     if simpl_shape.number_of_points() < shape.number_of_points() {
         info!(
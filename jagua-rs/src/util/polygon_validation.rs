@@ -0,0 +1,188 @@
+use std::fmt::{Display, Formatter};
+use std::iter;
+
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+
+use crate::fsize;
+use crate::geometry::primitives::edge::Edge;
+use crate::geometry::primitives::point::Point;
+
+/// Default tolerance to detect degenerate geometry, as a fraction of the diagonal of the polygon's bounding box
+pub const DEFAULT_VALIDATION_TOLERANCE: fsize = 1e-6;
+
+/// Determines how the parser handles polygons with defects
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum PolyValidationPolicy {
+    /// Polygons are not validated
+    #[default]
+    #[serde(rename = "disabled")]
+    Disabled,
+    /// Defects are logged, but the polygons are used as they are
+    #[serde(rename = "warn")]
+    Warn,
+    /// Polygons with defects are rejected
+    #[serde(rename = "reject")]
+    Reject,
+    /// Polygons with defects are repaired, see [`repair`]
+    #[serde(rename = "repair")]
+    Repair,
+}
+
+/// A defect in the contour of a polygon
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PolygonDefect {
+    /// The point (nearly) coincides with the next point of the contour
+    NearDuplicate(Point),
+    /// The point lies on the line between its neighbours
+    Collinear(Point),
+    /// The contour doubles back on itself at the point
+    Spike(Point),
+    /// Two non-adjacent edges of the contour intersect at the point
+    SelfIntersection(Point),
+}
+
+impl Display for PolygonDefect {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PolygonDefect::NearDuplicate(Point(x, y)) => {
+                write!(f, "near-duplicate point ({x}, {y})")
+            }
+            PolygonDefect::Collinear(Point(x, y)) => write!(f, "collinear point ({x}, {y})"),
+            PolygonDefect::Spike(Point(x, y)) => write!(f, "spike at ({x}, {y})"),
+            PolygonDefect::SelfIntersection(Point(x, y)) => {
+                write!(f, "self-intersection at ({x}, {y})")
+            }
+        }
+    }
+}
+
+/// Detects defects in a closed contour of points.
+/// `rel_tolerance` is a fraction of the diagonal of the bounding box of the points.
+pub fn validate(points: &[Point], rel_tolerance: fsize) -> Vec<PolygonDefect> {
+    let eps = rel_tolerance * bbox_diagonal(points);
+    let n = points.len();
+    if n < 3 {
+        return vec![];
+    }
+
+    let mut defects = (0..n)
+        .filter_map(|i| {
+            let (prev, p, next) = (points[(i + n - 1) % n], points[i], points[(i + 1) % n]);
+            match p.distance(next) <= eps {
+                true => Some(PolygonDefect::NearDuplicate(p)),
+                false => corner_defect(prev, p, next, eps),
+            }
+        })
+        .collect_vec();
+
+    //intersections are detected on the contour without consecutive duplicates, which are reported above
+    let mut contour = points.iter().copied().dedup().collect_vec();
+    if contour.len() > 1 && contour.first() == contour.last() {
+        contour.pop();
+    }
+    if contour.len() >= 3 {
+        defects.extend(
+            self_intersections(&contour).map(|(_, _, x)| PolygonDefect::SelfIntersection(x)),
+        );
+    }
+
+    defects
+}
+
+/// Repairs a closed contour of points by removing near-duplicate points, collinear points and spikes,
+/// and by splitting the contour into separate contours at its self-intersections (e.g. figure-eight shapes).
+/// Contours which collapse entirely are dropped, so the result can be empty.
+/// `rel_tolerance` is a fraction of the diagonal of the bounding box of the points.
+pub fn repair(points: &[Point], rel_tolerance: fsize) -> Vec<Vec<Point>> {
+    let eps = rel_tolerance * bbox_diagonal(points);
+    repair_contour(points.to_vec(), eps)
+}
+
+fn repair_contour(mut contour: Vec<Point>, eps: fsize) -> Vec<Vec<Point>> {
+    //remove degenerate points one by one, until none are left
+    loop {
+        let n = contour.len();
+        if n < 3 {
+            return vec![];
+        }
+        let degenerate = (0..n).find(|&i| {
+            let (prev, p, next) = (contour[(i + n - 1) % n], contour[i], contour[(i + 1) % n]);
+            p.distance(next) <= eps || corner_defect(prev, p, next, eps).is_some()
+        });
+        match degenerate {
+            Some(i) => {
+                contour.remove(i);
+            }
+            None => break,
+        }
+    }
+
+    //split the contour at its first self-intersection, both parts are strictly smaller
+    let split = self_intersections(&contour).next();
+    match split {
+        None => vec![contour],
+        Some((i, j, x)) => {
+            let first = iter::once(x)
+                .chain(contour[i + 1..=j].iter().copied())
+                .collect_vec();
+            let second = iter::once(x)
+                .chain(contour[j + 1..].iter().copied())
+                .chain(contour[..=i].iter().copied())
+                .collect_vec();
+            [first, second]
+                .into_iter()
+                .flat_map(|c| repair_contour(c, eps))
+                .collect_vec()
+        }
+    }
+}
+
+/// Classifies the corner at `p` if it is collinear or a spike
+fn corner_defect(prev: Point, p: Point, next: Point, eps: fsize) -> Option<PolygonDefect> {
+    let (dx, dy) = (next.0 - prev.0, next.1 - prev.1);
+    let length = (dx * dx + dy * dy).sqrt();
+    if length <= eps {
+        //the contour goes back to where it came from
+        return Some(PolygonDefect::Spike(p));
+    }
+    //distance from p to the line through its neighbours
+    let deviation = (dx * (p.1 - prev.1) - dy * (p.0 - prev.0)).abs() / length;
+    if deviation > eps {
+        return None;
+    }
+    let dot = (p.0 - prev.0) * (next.0 - p.0) + (p.1 - prev.1) * (next.1 - p.1);
+    match dot >= 0.0 {
+        true => Some(PolygonDefect::Collinear(p)),
+        false => Some(PolygonDefect::Spike(p)),
+    }
+}
+
+/// All intersections between non-adjacent edges of a contour without consecutive duplicates,
+/// as the indices of both edges and the point of intersection
+fn self_intersections(contour: &[Point]) -> impl Iterator<Item = (usize, usize, Point)> + '_ {
+    let n = contour.len();
+    let edge = move |i: usize| Edge::new(contour[i], contour[(i + 1) % n]);
+    (0..n)
+        .tuple_combinations()
+        .filter(move |&(i, j)| j - i > 1 && !(i == 0 && j == n - 1))
+        .filter_map(move |(i, j)| edge(i).collides_at(&edge(j)).map(|x| (i, j, x)))
+}
+
+fn bbox_diagonal(points: &[Point]) -> fsize {
+    let (x_min, y_min, x_max, y_max) = points.iter().fold(
+        (fsize::MAX, fsize::MAX, fsize::MIN, fsize::MIN),
+        |(x_min, y_min, x_max, y_max), p| {
+            (
+                x_min.min(p.0),
+                y_min.min(p.1),
+                x_max.max(p.0),
+                y_max.max(p.1),
+            )
+        },
+    );
+    match points.is_empty() {
+        true => 0.0,
+        false => ((x_max - x_min).powi(2) + (y_max - y_min).powi(2)).sqrt(),
+    }
+}
@@ -0,0 +1,236 @@
+use crate::fsize;
+use crate::geometry::primitives::point::Point;
+
+/// Orientation of `c` with respect to the directed line from `a` to `b`:
+/// positive if counterclockwise (left of the line), negative if clockwise (right of the line), zero if collinear.
+/// <br>
+/// The sign of the result is exact, regardless of the precision of [`fsize`](crate::fsize):
+/// the determinant is evaluated in f64 with adaptive precision, based on
+/// [Shewchuk's predicates](https://www.cs.cmu.edu/~quake/robust.html).
+/// Only the sign is meaningful, the magnitude is approximate.
+pub fn orient2d(a: Point, b: Point, c: Point) -> f64 {
+    let (ax, ay) = (to_f64(a.0), to_f64(a.1));
+    let (bx, by) = (to_f64(b.0), to_f64(b.1));
+    let (cx, cy) = (to_f64(c.0), to_f64(c.1));
+
+    let det_left = (ax - cx) * (by - cy);
+    let det_right = (ay - cy) * (bx - cx);
+    let det = det_left - det_right;
+
+    let det_sum = if det_left > 0.0 {
+        if det_right <= 0.0 {
+            return det;
+        }
+        det_left + det_right
+    } else if det_left < 0.0 {
+        if det_right >= 0.0 {
+            return det;
+        }
+        -det_left - det_right
+    } else {
+        return det;
+    };
+
+    //fast path: the floating point result is certainly correct
+    let err_bound = CCW_ERR_BOUND_A * det_sum;
+    if det >= err_bound || -det >= err_bound {
+        return det;
+    }
+    orient2d_adapt([ax, ay], [bx, by], [cx, cy], det_sum)
+}
+
+cfg_if::cfg_if! {
+    if #[cfg(feature = "double-precision")] {
+        /// Widens an [fsize] to f64, which is lossless for both precisions
+        #[inline(always)]
+        fn to_f64(x: fsize) -> f64 {
+            x
+        }
+    } else {
+        /// Widens an [fsize] to f64, which is lossless for both precisions
+        #[inline(always)]
+        fn to_f64(x: fsize) -> f64 {
+            f64::from(x)
+        }
+    }
+}
+
+const EPSILON: f64 = f64::EPSILON / 2.0;
+const SPLITTER: f64 = 134_217_729.0;
+const RESULT_ERR_BOUND: f64 = (3.0 + 8.0 * EPSILON) * EPSILON;
+const CCW_ERR_BOUND_A: f64 = (3.0 + 16.0 * EPSILON) * EPSILON;
+const CCW_ERR_BOUND_B: f64 = (2.0 + 12.0 * EPSILON) * EPSILON;
+const CCW_ERR_BOUND_C: f64 = (9.0 + 64.0 * EPSILON) * EPSILON * EPSILON;
+
+fn orient2d_adapt([ax, ay]: [f64; 2], [bx, by]: [f64; 2], [cx, cy]: [f64; 2], det_sum: f64) -> f64 {
+    let (acx, bcx) = (ax - cx, bx - cx);
+    let (acy, bcy) = (ay - cy, by - cy);
+
+    let (det_left, det_left_tail) = two_product(acx, bcy);
+    let (det_right, det_right_tail) = two_product(acy, bcx);
+    let b = two_two_diff(det_left, det_left_tail, det_right, det_right_tail);
+
+    let mut det: f64 = b.iter().sum();
+    let err_bound = CCW_ERR_BOUND_B * det_sum;
+    if det >= err_bound || -det >= err_bound {
+        return det;
+    }
+
+    let acx_tail = two_diff_tail(ax, cx, acx);
+    let bcx_tail = two_diff_tail(bx, cx, bcx);
+    let acy_tail = two_diff_tail(ay, cy, acy);
+    let bcy_tail = two_diff_tail(by, cy, bcy);
+    if acx_tail == 0.0 && acy_tail == 0.0 && bcx_tail == 0.0 && bcy_tail == 0.0 {
+        return det;
+    }
+
+    let err_bound = CCW_ERR_BOUND_C * det_sum + RESULT_ERR_BOUND * det.abs();
+    det += (acx * bcy_tail + bcy * acx_tail) - (acy * bcx_tail + bcx * acy_tail);
+    if det >= err_bound || -det >= err_bound {
+        return det;
+    }
+
+    //exact evaluation of the remaining terms
+    let (s1, s0) = two_product(acx_tail, bcy);
+    let (t1, t0) = two_product(acy_tail, bcx);
+    let c1 = expansion_sum(&b, &two_two_diff(s1, s0, t1, t0));
+
+    let (s1, s0) = two_product(acx, bcy_tail);
+    let (t1, t0) = two_product(acy, bcx_tail);
+    let c2 = expansion_sum(&c1, &two_two_diff(s1, s0, t1, t0));
+
+    let (s1, s0) = two_product(acx_tail, bcy_tail);
+    let (t1, t0) = two_product(acy_tail, bcx_tail);
+    let d = expansion_sum(&c2, &two_two_diff(s1, s0, t1, t0));
+
+    *d.last().unwrap()
+}
+
+/// Sum of two nonoverlapping expansions (ordered by increasing magnitude), with zero components eliminated
+fn expansion_sum(e: &[f64], f: &[f64]) -> Vec<f64> {
+    let mut h = Vec::with_capacity(e.len() + f.len());
+    let (mut i, mut j) = (0, 0);
+    //takes the next component with the smallest magnitude
+    let next = |i: &mut usize, j: &mut usize| {
+        let take_e = match (e.get(*i), f.get(*j)) {
+            (Some(&ei), Some(&fj)) => (fj > ei) == (fj > -ei),
+            (Some(_), None) => true,
+            _ => false,
+        };
+        match take_e {
+            true => {
+                *i += 1;
+                e[*i - 1]
+            }
+            false => {
+                *j += 1;
+                f[*j - 1]
+            }
+        }
+    };
+
+    let mut q = next(&mut i, &mut j);
+    if i < e.len() && j < f.len() {
+        let (q_new, hh) = fast_two_sum(next(&mut i, &mut j), q);
+        q = q_new;
+        if hh != 0.0 {
+            h.push(hh);
+        }
+    }
+    while i < e.len() || j < f.len() {
+        let (q_new, hh) = two_sum(q, next(&mut i, &mut j));
+        q = q_new;
+        if hh != 0.0 {
+            h.push(hh);
+        }
+    }
+    if q != 0.0 || h.is_empty() {
+        h.push(q);
+    }
+    h
+}
+
+fn fast_two_sum(a: f64, b: f64) -> (f64, f64) {
+    let x = a + b;
+    let b_virtual = x - a;
+    (x, b - b_virtual)
+}
+
+fn two_sum(a: f64, b: f64) -> (f64, f64) {
+    let x = a + b;
+    let b_virtual = x - a;
+    let a_virtual = x - b_virtual;
+    (x, (a - a_virtual) + (b - b_virtual))
+}
+
+fn two_diff(a: f64, b: f64) -> (f64, f64) {
+    let x = a - b;
+    (x, two_diff_tail(a, b, x))
+}
+
+fn two_diff_tail(a: f64, b: f64, x: f64) -> f64 {
+    let b_virtual = a - x;
+    let a_virtual = x + b_virtual;
+    (a - a_virtual) + (b_virtual - b)
+}
+
+fn split(a: f64) -> (f64, f64) {
+    let c = SPLITTER * a;
+    let a_big = c - a;
+    let a_hi = c - a_big;
+    (a_hi, a - a_hi)
+}
+
+fn two_product(a: f64, b: f64) -> (f64, f64) {
+    let x = a * b;
+    let (a_hi, a_lo) = split(a);
+    let (b_hi, b_lo) = split(b);
+    let err = x - a_hi * b_hi - a_lo * b_hi - a_hi * b_lo;
+    (x, a_lo * b_lo - err)
+}
+
+/// Exact difference of two two-component expansions, as an expansion of increasing magnitude
+fn two_two_diff(a1: f64, a0: f64, b1: f64, b0: f64) -> [f64; 4] {
+    let (j, r0, x0) = two_one_diff(a1, a0, b0);
+    let (x3, x2, x1) = two_one_diff(j, r0, b1);
+    [x0, x1, x2, x3]
+}
+
+fn two_one_diff(a1: f64, a0: f64, b: f64) -> (f64, f64, f64) {
+    let (i, x0) = two_diff(a0, b);
+    let (x2, x1) = two_sum(a1, i);
+    (x2, x1, x0)
+}
+
+/// Checks whether two segments intersect, using exact orientation tests.
+/// Collinear segments are never considered intersecting, consistent with the floating point implementation.
+pub fn segments_intersect((p1, p2): (Point, Point), (q1, q2): (Point, Point)) -> bool {
+    let (o1, o2) = (orient2d(p1, p2, q1), orient2d(p1, p2, q2));
+    if o1 == 0.0 && o2 == 0.0 {
+        return false;
+    }
+    let (o3, o4) = (orient2d(q1, q2, p1), orient2d(q1, q2, p2));
+    opposite_or_zero(o1, o2) && opposite_or_zero(o3, o4)
+}
+
+/// Point-in-ring test based on the crossing number, using exact orientation tests.
+/// Edges are treated as half-open in the vertical direction, so rays through vertices are counted consistently.
+pub fn ring_contains(ring: &[Point], p: Point) -> bool {
+    let n = ring.len();
+    let mut inside = false;
+    for i in 0..n {
+        let (a, b) = (ring[i], ring[(i + 1) % n]);
+        if (a.1 > p.1) != (b.1 > p.1) {
+            //the edge crosses the horizontal line through p, check whether it does so right of p
+            let o = orient2d(a, b, p);
+            if (b.1 > a.1 && o > 0.0) || (b.1 < a.1 && o < 0.0) {
+                inside = !inside;
+            }
+        }
+    }
+    inside
+}
+
+fn opposite_or_zero(a: f64, b: f64) -> bool {
+    (a <= 0.0 && b >= 0.0) || (a >= 0.0 && b <= 0.0)
+}
@@ -11,6 +11,8 @@ use rand::SeedableRng;
 use jagua_rs::entities::instances::bin_packing::BPInstance;
 use jagua_rs::entities::instances::instance::Instance;
 use jagua_rs::entities::instances::instance_generic::InstanceGeneric;
+use jagua_rs::entities::instances::knapsack::KPInstance;
+use jagua_rs::entities::instances::open_dimension::ODInstance;
 use jagua_rs::entities::instances::strip_packing::SPInstance;
 use jagua_rs::entities::item::Item;
 use jagua_rs::entities::problems::problem_generic::{LayoutIndex, ProblemGeneric};
@@ -167,8 +169,14 @@ fn modify_instance(instance: &Instance, multiplier: usize, config: LBFConfig) ->
         .collect_vec();
 
     match instance {
-        Instance::SP(spi) => Instance::SP(SPInstance::new(modified_items, spi.strip_height)),
+        Instance::SP(spi) => Instance::SP(SPInstance::new(
+            modified_items,
+            spi.strip_height,
+            spi.fixed_items.clone(),
+        )),
         Instance::BP(bpi) => Instance::BP(BPInstance::new(modified_items, bpi.bins.clone())),
+        Instance::KP(kpi) => Instance::KP(KPInstance::new(modified_items, kpi.bins.clone())),
+        Instance::OD(odi) => Instance::OD(ODInstance::new(modified_items, odi.open_dim)),
     }
 }
 
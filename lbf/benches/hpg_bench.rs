@@ -1,11 +1,14 @@
 use std::fs::File;
 use std::io::BufReader;
+use std::time::{Duration, Instant};
 
 use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
 use itertools::Itertools;
 use rand::rngs::SmallRng;
 use rand::SeedableRng;
 
+use jagua_rs::collision_detection::hazard::Hazard;
+use jagua_rs::collision_detection::hpg::hazard_proximity_grid::HazardProximityGrid;
 use jagua_rs::entities::instances::instance::Instance;
 use jagua_rs::entities::instances::instance_generic::InstanceGeneric;
 use jagua_rs::entities::placing_option::PlacingOption;
@@ -19,7 +22,12 @@ use lbf::samplers::hpg_sampler::HPGSampler;
 use crate::util::{create_base_config, N_ITEMS_REMOVED, SWIM_PATH};
 
 criterion_main!(benches);
-criterion_group!(benches, hpg_update_bench, hpg_query_bench);
+criterion_group!(
+    benches,
+    hpg_update_bench,
+    hpg_deregister_bench,
+    hpg_query_bench
+);
 
 mod util;
 
@@ -62,7 +70,7 @@ fn hpg_query_bench(c: &mut Criterion) {
             config.poly_simpl_tolerance,
         );
         let mut problem = match instance.clone() {
-            Instance::BP(_) => panic!("Expected SPInstance"),
+            Instance::BP(_) | Instance::KP(_) | Instance::OD(_) => panic!("Expected SPInstance"),
             Instance::SP(instance) => {
                 SPProblem::new(instance, base_problem.strip_width(), config.cde_config)
             }
@@ -150,7 +158,7 @@ fn hpg_update_bench(c: &mut Criterion) {
             config.poly_simpl_tolerance,
         );
         let mut problem = match instance.clone() {
-            Instance::BP(_) => panic!("Expected SPInstance"),
+            Instance::BP(_) | Instance::KP(_) | Instance::OD(_) => panic!("Expected SPInstance"),
             Instance::SP(instance) => {
                 SPProblem::new(instance, base_problem.strip_width(), config.cde_config)
             }
@@ -216,3 +224,142 @@ fn hpg_update_bench(c: &mut Criterion) {
     }
     group.finish();
 }
+
+/// Measures only the deregistration of a hazard from the HPG, isolated from the rest of the CDE.
+/// Deregistration only updates the cells in the vicinity of the removed hazard,
+/// compared against sweeping the entire grid as a baseline.
+fn hpg_deregister_bench(c: &mut Criterion) {
+    let json_instance: JsonInstance =
+        serde_json::from_reader(BufReader::new(File::open(SWIM_PATH).unwrap())).unwrap();
+    let base_config = create_base_config();
+    let base_instance = util::create_instance(
+        &json_instance,
+        base_config.cde_config,
+        base_config.poly_simpl_tolerance,
+    );
+    let (base_problem, _) =
+        util::create_blf_problem(base_instance.clone(), base_config, N_ITEMS_REMOVED);
+    let base_p_opts = base_problem
+        .get_layout(LayoutIndex::Real(0))
+        .placed_items()
+        .values()
+        .map(|pi| PlacingOption {
+            layout_idx: LayoutIndex::Real(0),
+            item_id: pi.item_id,
+            d_transf: pi.d_transf,
+        })
+        .collect_vec();
+
+    let mut group = c.benchmark_group("hpg_bench_deregister");
+    for n_hpg_cells in N_HPG_CELLS {
+        let mut config = base_config;
+        config.cde_config.hpg_n_cells = n_hpg_cells;
+        let instance = util::create_instance(
+            &json_instance,
+            config.cde_config,
+            config.poly_simpl_tolerance,
+        );
+        let mut problem = match instance.clone() {
+            Instance::BP(_) | Instance::KP(_) | Instance::OD(_) => panic!("Expected SPInstance"),
+            Instance::SP(instance) => {
+                SPProblem::new(instance, base_problem.strip_width(), config.cde_config)
+            }
+        };
+        // Place the items in exactly the same way as the base problem
+        for p_opt in base_p_opts.iter() {
+            problem.place_item(*p_opt);
+        }
+
+        let mut rng = SmallRng::seed_from_u64(0);
+
+        let item = instance.item(SELECTED_ITEM_ID);
+        let layout = problem.get_layout(LayoutIndex::Real(0));
+        let surrogate = item.shape.surrogate();
+        let mut buffer_shape = item.shape.as_ref().clone();
+        let mut sampler = HPGSampler::new(item, layout).unwrap();
+
+        //collect N_VALID_SAMPLES
+        let mut valid_placements = vec![];
+        while valid_placements.len() < N_VALID_SAMPLES {
+            let transf = sampler.sample(&mut rng);
+            if !layout.cde().surrogate_collides(surrogate, &transf, &[]) {
+                buffer_shape.transform_from(&item.shape, &transf);
+                if !layout.cde().poly_collides(&buffer_shape, &[]) {
+                    valid_placements.push(PlacingOption {
+                        layout_idx: LayoutIndex::Real(0),
+                        item_id: SELECTED_ITEM_ID,
+                        d_transf: transf.decompose(),
+                    });
+                }
+            }
+        }
+
+        //create the hazards of all valid placements
+        let remaining_hazards = layout.cde().dynamic_hazards().clone();
+        let mut hazards = vec![];
+        for opt in valid_placements.iter() {
+            let (l_idx, pik) = problem.place_item(*opt);
+            let hazard = problem
+                .get_layout(l_idx)
+                .cde()
+                .dynamic_hazards()
+                .iter()
+                .find(|h| h.entity == pik.into())
+                .unwrap()
+                .clone();
+            hazards.push(hazard);
+            problem.remove_item(l_idx, pik, true);
+        }
+        let mut hazards_cycler = hazards.iter().cycle();
+
+        let mut hpg = problem
+            .get_layout(LayoutIndex::Real(0))
+            .cde()
+            .haz_prox_grid()
+            .unwrap()
+            .clone();
+
+        group.bench_function(BenchmarkId::new("localized", n_hpg_cells), |b| {
+            b.iter_custom(|iters| {
+                let mut elapsed = Duration::ZERO;
+                for _ in 0..iters {
+                    let hazard = hazards_cycler.next().unwrap();
+                    hpg.register_hazard(hazard);
+                    let start = Instant::now();
+                    hpg.deregister_hazard(hazard, remaining_hazards.iter(), true);
+                    elapsed += start.elapsed();
+                }
+                elapsed
+            })
+        });
+
+        group.bench_function(BenchmarkId::new("full_grid", n_hpg_cells), |b| {
+            b.iter_custom(|iters| {
+                let mut elapsed = Duration::ZERO;
+                for _ in 0..iters {
+                    let hazard = hazards_cycler.next().unwrap();
+                    hpg.register_hazard(hazard);
+                    let start = Instant::now();
+                    deregister_full_grid(&mut hpg, hazard, remaining_hazards.iter());
+                    elapsed += start.elapsed();
+                }
+                elapsed
+            })
+        });
+    }
+    group.finish();
+}
+
+/// Baseline deregistration, visiting every cell of the grid.
+/// Cells for which the deregistered hazard was the closest are reset and all remaining hazards are registered again.
+fn deregister_full_grid<'a, I>(hpg: &mut HazardProximityGrid, to_deregister: &Hazard, remaining: I)
+where
+    I: Iterator<Item = &'a Hazard> + Clone,
+{
+    for cell in hpg.grid.cells.iter_mut().flatten() {
+        if cell.uni_prox.1 == to_deregister.entity {
+            cell.uni_prox = cell.static_uni_prox;
+            cell.register_hazards(remaining.clone());
+        }
+    }
+}
@@ -15,6 +15,7 @@ use jagua_rs::io::json_instance::JsonInstance;
 use jagua_rs::io::parser::Parser;
 use jagua_rs::util::config::{CDEConfig, SPSurrogateConfig};
 use jagua_rs::util::polygon_simplification::PolySimplConfig;
+use jagua_rs::util::polygon_validation::PolyValidationPolicy;
 use lbf::io;
 use lbf::io::svg_util::SvgDrawOptions;
 use lbf::lbf_config::LBFConfig;
@@ -118,11 +119,14 @@ pub fn create_base_config() -> LBFConfig {
                 n_ff_poles: 4,
                 n_ff_piers: 0,
             },
+            robust_predicates: false,
         },
         poly_simpl_tolerance: Some(0.001),
+        poly_validation: PolyValidationPolicy::Disabled,
         prng_seed: Some(0),
         n_samples: 5000,
         ls_frac: 0.2,
+        slide_to_contact: false,
         svg_draw_options: Default::default(),
     }
 }
@@ -93,24 +93,26 @@ pub fn layout_to_svg(layout: &Layout, instance: &Instance, options: SvgDrawOptio
     //draw items
     let items_group = {
         let mut items_group = Group::new();
-        for pi in layout.placed_items().values() {
+        //fixed items of the bin are drawn like any other placed item
+        for pi in bin.fixed_items.iter().chain(layout.placed_items().values()) {
             let mut group = Group::new();
             let item = instance.item(pi.item_id);
-            let shape = &pi.shape;
             let color = match item.base_quality {
                 None => theme.item_fill.to_owned(),
                 Some(q) => svg_util::blend_colors(theme.item_fill, theme.qz_fill[q]),
             };
-            group = group.add(svg_export::data_to_path(
-                svg_export::simple_polygon_data(shape),
-                &[
-                    ("fill", &*format!("{}", color)),
-                    ("stroke-width", &*format!("{}", stroke_width)),
-                    ("fill-rule", "nonzero"),
-                    ("stroke", "black"),
-                    ("opacity", "0.9"),
-                ],
-            ));
+            for shape in pi.shapes() {
+                group = group.add(svg_export::data_to_path(
+                    svg_export::simple_polygon_data(shape),
+                    &[
+                        ("fill", &*format!("{}", color)),
+                        ("stroke-width", &*format!("{}", stroke_width)),
+                        ("fill-rule", "nonzero"),
+                        ("stroke", "black"),
+                        ("opacity", "0.9"),
+                    ],
+                ));
+            }
 
             if options.surrogate {
                 let poi_style = [
@@ -136,30 +138,30 @@ pub fn layout_to_svg(layout: &Layout, instance: &Instance, options: SvgDrawOptio
                     ("stroke-linejoin", "round"),
                 ];
 
-                let transformed_surrogate = item
-                    .shape
-                    .surrogate()
-                    .transform_clone(&pi.d_transf.compose());
-                let poi = &transformed_surrogate.poles[0];
-                let ff_poles = transformed_surrogate.ff_poles();
-
-                for i in 0..transformed_surrogate.poles.len() {
-                    let pole = &transformed_surrogate.poles[i];
-                    if pole == poi {
-                        group = group.add(svg_export::circle(pole, &poi_style));
+                for shape in item.shapes() {
+                    let transformed_surrogate =
+                        shape.surrogate().transform_clone(&pi.d_transf.compose());
+                    let poi = &transformed_surrogate.poles[0];
+                    let ff_poles = transformed_surrogate.ff_poles();
+
+                    for i in 0..transformed_surrogate.poles.len() {
+                        let pole = &transformed_surrogate.poles[i];
+                        if pole == poi {
+                            group = group.add(svg_export::circle(pole, &poi_style));
+                        }
+                        if ff_poles.contains(pole) {
+                            group = group.add(svg_export::circle(pole, &ff_style));
+                        } else {
+                            group = group.add(svg_export::circle(pole, &no_ff_style));
+                        }
                     }
-                    if ff_poles.contains(pole) {
-                        group = group.add(svg_export::circle(pole, &ff_style));
-                    } else {
-                        group = group.add(svg_export::circle(pole, &no_ff_style));
+                    for pier in &transformed_surrogate.piers {
+                        group = group.add(svg_export::data_to_path(
+                            svg_export::edge_data(pier),
+                            &ff_style,
+                        ));
                     }
                 }
-                for pier in &transformed_surrogate.piers {
-                    group = group.add(svg_export::data_to_path(
-                        svg_export::edge_data(pier),
-                        &ff_style,
-                    ));
-                }
             }
             items_group = items_group.add(group);
         }
@@ -14,7 +14,16 @@ pub fn simple_polygon_data(s_poly: &SimplePolygon) -> Data {
     for i in 1..s_poly.number_of_points() {
         data = data.line_to::<(fsize, fsize)>(s_poly.get_point(i).into());
     }
-    data.close()
+    data = data.close();
+    //holes are drawn in the opposite direction, so they are left unfilled regardless of the fill rule
+    for hole in s_poly.holes.iter() {
+        data = data.move_to::<(fsize, fsize)>(hole.get_point(0).into());
+        for i in (1..hole.number_of_points()).rev() {
+            data = data.line_to::<(fsize, fsize)>(hole.get_point(i).into());
+        }
+        data = data.close();
+    }
+    data
 }
 
 pub fn quad_tree_data(qt_root: &QTNode, irrelevant_hazards: &[HazardEntity]) -> (Data, Data, Data) {
@@ -22,4 +22,17 @@ impl LBFPlacingCost {
     pub fn from_shape(shape: &SimplePolygon) -> Self {
         LBFPlacingCost::new(shape.bbox().x_max, shape.bbox().y_max)
     }
+
+    /// Cost of an item consisting of multiple shapes, based on the bounding box of all of them
+    pub fn from_shapes(shapes: &[SimplePolygon]) -> Self {
+        let x_max = shapes
+            .iter()
+            .map(|s| s.bbox().x_max)
+            .fold(fsize::MIN, fsize::max);
+        let y_max = shapes
+            .iter()
+            .map(|s| s.bbox().y_max)
+            .fold(fsize::MIN, fsize::max);
+        LBFPlacingCost::new(x_max, y_max)
+    }
 }
@@ -8,21 +8,30 @@ use rand::prelude::SmallRng;
 use rand::Rng;
 use thousands::Separable;
 
+use jagua_rs::collision_detection::cd_engine::CDEngine;
+#[cfg(feature = "cde-stats")]
+use jagua_rs::collision_detection::cde_stats::CDEStats;
+use jagua_rs::collision_detection::hazard::HazardEntity;
 use jagua_rs::collision_detection::hazard_filter;
 use jagua_rs::entities::instances::instance::Instance;
 use jagua_rs::entities::instances::instance_generic::InstanceGeneric;
+use jagua_rs::entities::instances::open_dimension::OpenDimension;
 use jagua_rs::entities::item::Item;
 use jagua_rs::entities::layout::Layout;
 use jagua_rs::entities::placing_option::PlacingOption;
 use jagua_rs::entities::problems::bin_packing::BPProblem;
+use jagua_rs::entities::problems::knapsack::KPProblem;
+use jagua_rs::entities::problems::open_dimension::ODProblem;
 use jagua_rs::entities::problems::problem::Problem;
 use jagua_rs::entities::problems::problem_generic::{LayoutIndex, ProblemGeneric};
 use jagua_rs::entities::problems::strip_packing::SPProblem;
 use jagua_rs::entities::solution::Solution;
 use jagua_rs::fsize;
 use jagua_rs::geometry::convex_hull::convex_hull_from_points;
+use jagua_rs::geometry::d_transformation::DTransformation;
 use jagua_rs::geometry::geo_traits::{Shape, TransformableFrom};
 use jagua_rs::geometry::primitives::simple_polygon::SimplePolygon;
+use jagua_rs::geometry::transformation::Transformation;
 
 use crate::lbf_config::LBFConfig;
 use crate::lbf_cost::LBFPlacingCost;
@@ -39,6 +48,9 @@ pub struct LBFOptimizer {
     /// SmallRng is a fast, non-cryptographic PRNG <https://rust-random.github.io/book/guide-rngs.html>
     pub rng: SmallRng,
     pub sample_counter: usize,
+    /// Statistics harvested from the CDEs of layouts before they were resized or replaced during the optimization
+    #[cfg(feature = "cde-stats")]
+    pub replaced_cde_stats: CDEStats,
 }
 
 impl LBFOptimizer {
@@ -46,10 +58,26 @@ impl LBFOptimizer {
         assert!(config.n_samples > 0);
         let problem = match instance.clone() {
             Instance::BP(bpi) => BPProblem::new(bpi.clone()).into(),
+            Instance::KP(kpi) => KPProblem::new(kpi.clone()).into(),
             Instance::SP(spi) => {
                 let strip_width = instance.item_area() * 2.0 / spi.strip_height; //initiate with 50% usage
                 SPProblem::new(spi.clone(), strip_width, config.cde_config).into()
             }
+            Instance::OD(odi) => {
+                //initiate with 100% usage, the container grows whenever an item does not fit.
+                //The placement cost favours compactness in the horizontal direction only,
+                //so a loose vertical dimension would never be filled.
+                let item_area = instance.item_area();
+                let (width, height) = match odi.open_dim {
+                    OpenDimension::Height { width } => (width, item_area / width),
+                    OpenDimension::Area { aspect_ratio } => {
+                        let ratio = aspect_ratio.unwrap_or(1.0);
+                        let width = fsize::sqrt(item_area * ratio);
+                        (width, width / ratio)
+                    }
+                };
+                ODProblem::new(odi.clone(), width, height, config.cde_config).into()
+            }
         };
 
         Self {
@@ -58,19 +86,33 @@ impl LBFOptimizer {
             config,
             rng,
             sample_counter: 0,
+            #[cfg(feature = "cde-stats")]
+            replaced_cde_stats: CDEStats::default(),
         }
     }
 
     pub fn solve(&mut self) -> Solution {
-        //sort the items by descending diameter of convex hull
-        let sorted_item_indices = (0..self.instance.items().len())
-            .sorted_by_cached_key(|i| {
-                let item = &self.instance.items()[*i].0;
-                let ch = SimplePolygon::new(convex_hull_from_points(item.shape.points.clone()));
-                let ch_diam = NotNan::new(ch.diameter()).expect("convex hull diameter is NaN");
-                Reverse(ch_diam)
-            })
-            .collect_vec();
+        let sorted_item_indices = match &self.instance {
+            //sort the items by descending value density, the most valuable items per unit of area are placed first
+            Instance::KP(_) => (0..self.instance.items().len())
+                .sorted_by_cached_key(|i| {
+                    let item = &self.instance.items()[*i].0;
+                    let density = NotNan::new(item.value as fsize / item.area())
+                        .expect("value density is NaN");
+                    Reverse(density)
+                })
+                .collect_vec(),
+            //sort the items by descending diameter of convex hull
+            Instance::BP(_) | Instance::SP(_) | Instance::OD(_) => (0..self.instance.items().len())
+                .sorted_by_cached_key(|i| {
+                    let item = &self.instance.items()[*i].0;
+                    let points = item.shapes().flat_map(|s| s.points.clone()).collect_vec();
+                    let ch = SimplePolygon::new(convex_hull_from_points(points));
+                    let ch_diam = NotNan::new(ch.diameter()).expect("convex hull diameter is NaN");
+                    Reverse(ch_diam)
+                })
+                .collect_vec(),
+        };
 
         let start = Instant::now();
 
@@ -103,12 +145,34 @@ impl LBFOptimizer {
                     }
                     None => {
                         match &mut self.problem {
-                            Problem::BP(_) => break,
+                            Problem::BP(_) | Problem::KP(_) => break,
                             Problem::SP(sp_problem) => {
                                 let new_width = sp_problem.strip_width() * 1.1;
                                 info!("[LBF] no placement found, extending strip width by 10% to {:.3}", new_width);
+                                #[cfg(feature = "cde-stats")]
+                                {
+                                    self.replaced_cde_stats += sp_problem.layout.take_cde_stats();
+                                }
                                 sp_problem.modify_strip_in_back(new_width);
                             }
+                            Problem::OD(od_problem) => {
+                                //grow the area of the container by 10%
+                                let (width, height) =
+                                    (od_problem.container_width(), od_problem.container_height());
+                                let (width, height) = match od_problem.instance.open_dim {
+                                    OpenDimension::Height { .. } => (width, height * 1.1),
+                                    OpenDimension::Area { .. } => {
+                                        let factor = fsize::sqrt(1.1);
+                                        (width * factor, height * factor)
+                                    }
+                                };
+                                info!("[LBF] no placement found, extending container by 10% to {:.3}x{:.3}", width, height);
+                                #[cfg(feature = "cde-stats")]
+                                {
+                                    self.replaced_cde_stats += od_problem.layout.take_cde_stats();
+                                }
+                                od_problem.modify_container(width, height);
+                            }
                         }
                     }
                 }
@@ -116,13 +180,36 @@ impl LBFOptimizer {
         }
         match &mut self.problem {
             Problem::BP(_) => {}
+            Problem::KP(kp_problem) => {
+                info!(
+                    "[LBF] placed items with a total value of {}/{}",
+                    kp_problem.placed_value(),
+                    kp_problem.instance.item_value()
+                );
+            }
             Problem::SP(sp_problem) => {
+                #[cfg(feature = "cde-stats")]
+                {
+                    self.replaced_cde_stats += sp_problem.layout.take_cde_stats();
+                }
                 sp_problem.fit_strip();
                 info!(
                     "[LBF] fitted strip width to {:.3}",
                     sp_problem.strip_width()
                 );
             }
+            Problem::OD(od_problem) => {
+                #[cfg(feature = "cde-stats")]
+                {
+                    self.replaced_cde_stats += od_problem.layout.take_cde_stats();
+                }
+                od_problem.fit_container();
+                info!(
+                    "[LBF] fitted container to {:.3}x{:.3}",
+                    od_problem.container_width(),
+                    od_problem.container_height()
+                );
+            }
         }
 
         let solution: Solution = self.problem.create_solution(None);
@@ -138,6 +225,18 @@ impl LBFOptimizer {
             solution.n_items_placed(),
             solution.usage * 100.0
         );
+
+        #[cfg(feature = "cde-stats")]
+        {
+            let cde_stats = self
+                .problem
+                .layouts()
+                .iter()
+                .chain(self.problem.template_layouts())
+                .fold(self.replaced_cde_stats, |acc, l| acc + l.cde().stats());
+            info!("[LBF] CDE statistics: {}", cde_stats);
+        }
+
         solution
     }
 }
@@ -179,13 +278,15 @@ pub fn sample_layout(
         Some(hf) => hazard_filter::generate_irrelevant_hazards(hf, layout.cde().all_hazards()),
     };
 
-    let surrogate = item.shape.surrogate();
-    //create a clone of the shape which will we can use to apply the transformations
-    let mut buffer = {
-        let mut buffer = (*item.shape).clone();
-        buffer.surrogate = None; //strip the surrogate for faster transforms, we don't need it for the buffer shape
-        buffer
-    };
+    //create a clone of every shape of the item which will we can use to apply the transformations
+    let mut buffers = item
+        .shapes()
+        .map(|shape| {
+            let mut buffer = (**shape).clone();
+            buffer.surrogate = None; //strip the surrogate for faster transforms, we don't need it for the buffer shape
+            buffer
+        })
+        .collect_vec();
 
     let mut best: Option<(PlacingOption, LBFPlacingCost)> = None;
 
@@ -198,10 +299,10 @@ pub fn sample_layout(
 
     for i in 0..uni_sample_budget {
         let transform = hpg_sampler.sample(rng);
-        if !cde.surrogate_collides(surrogate, &transform, &irrel_hazards) {
-            //if no collision is detected on the surrogate, apply the transformation
-            buffer.transform_from(&item.shape, &transform);
-            let cost = LBFPlacingCost::from_shape(&buffer);
+        if !surrogates_collide(cde, item, &transform, &irrel_hazards) {
+            //if no collision is detected on the surrogates, apply the transformation
+            transform_buffers(item, &transform, &mut buffers);
+            let cost = LBFPlacingCost::from_shapes(&buffers);
 
             //only validate the sample if it possibly can replace the current best
             let worth_testing = match (best.as_ref(), &cost) {
@@ -211,7 +312,7 @@ pub fn sample_layout(
                 (None, _) => true,
             };
 
-            if worth_testing && !cde.poly_collides(&buffer, &irrel_hazards) {
+            if worth_testing && !polys_collide(cde, &buffers, &irrel_hazards) {
                 //sample is valid and improves on the current best
                 let p_opt = PlacingOption {
                     layout_idx,
@@ -245,15 +346,23 @@ pub fn sample_layout(
     for i in 0..ls_sample_budget {
         let d_transf = ls_sampler.sample(rng);
         let transf = d_transf.compose();
-        if !cde.surrogate_collides(surrogate, &transf, &irrel_hazards) {
-            buffer.transform_from(&item.shape, &transf);
-            let cost = LBFPlacingCost::from_shape(&buffer);
+        if !surrogates_collide(cde, item, &transf, &irrel_hazards) {
+            transform_buffers(item, &transf, &mut buffers);
+            let cost = LBFPlacingCost::from_shapes(&buffers);
 
             //only validate the sample if it possibly can replace the current best
             let worth_testing = cost < *best_cost;
 
-            if worth_testing && !cde.poly_collides(&buffer, &irrel_hazards) {
-                //sample is valid and improves on the current best
+            if worth_testing && !polys_collide(cde, &buffers, &irrel_hazards) {
+                //sample is valid and improves on the current best, optionally slide it into contact with its surroundings
+                let (d_transf, cost) = match config.slide_to_contact {
+                    true => {
+                        let d_transf =
+                            slide_to_contact(cde, item, d_transf, &irrel_hazards, &mut buffers);
+                        (d_transf, LBFPlacingCost::from_shapes(&buffers))
+                    }
+                    false => (d_transf, cost),
+                };
                 let p_opt = PlacingOption {
                     layout_idx,
                     item_id: item.id,
@@ -272,3 +381,69 @@ pub fn sample_layout(
 
     best.map(|(p_opt, _)| p_opt)
 }
+
+/// Fraction of the item's diameter kept as clearance when sliding it into contact,
+/// as touching hazards are considered colliding.
+const SLIDE_CLEARANCE: fsize = 1e-4;
+
+/// Slides a valid placement to the left and then downwards, until it (almost) touches a hazard.
+/// Exactly moves the item into contact, instead of approaching it through many small local search steps.
+/// `buffers` contain the item's shapes with the returned transformation applied.
+fn slide_to_contact(
+    cde: &CDEngine,
+    item: &Item,
+    mut d_transf: DTransformation,
+    irrel_hazards: &[HazardEntity],
+    buffers: &mut [SimplePolygon],
+) -> DTransformation {
+    let clearance = item.shape.diameter * SLIDE_CLEARANCE;
+    for direction in [(-1.0, 0.0), (0.0, -1.0)] {
+        transform_buffers(item, &d_transf.compose(), buffers);
+        //the item can only travel as far as its most constrained shape
+        let distance = buffers
+            .iter()
+            .map(|buffer| cde.max_translation(buffer, direction, irrel_hazards).0)
+            .fold(fsize::INFINITY, fsize::min);
+        let travel = distance - clearance;
+        if travel > 0.0 {
+            let (tx, ty) = d_transf.translation();
+            let slid = DTransformation::new(
+                d_transf.rotation(),
+                (tx + direction.0 * travel, ty + direction.1 * travel),
+            )
+            .with_mirror(d_transf.mirror());
+            transform_buffers(item, &slid.compose(), buffers);
+            //fp errors can cause the slid shape to collide after all, only accept it if it is still valid
+            if !polys_collide(cde, buffers, irrel_hazards) {
+                d_transf = slid;
+            }
+        }
+    }
+    transform_buffers(item, &d_transf.compose(), buffers);
+    d_transf
+}
+
+/// Checks whether the surrogate of any of the item's shapes collides, with the transformation applied
+fn surrogates_collide(
+    cde: &CDEngine,
+    item: &Item,
+    transform: &Transformation,
+    irrel_hazards: &[HazardEntity],
+) -> bool {
+    item.shapes()
+        .any(|shape| cde.surrogate_collides(shape.surrogate(), transform, irrel_hazards))
+}
+
+/// Checks whether any of the (already transformed) shapes of an item collides
+fn polys_collide(cde: &CDEngine, shapes: &[SimplePolygon], irrel_hazards: &[HazardEntity]) -> bool {
+    shapes
+        .iter()
+        .any(|shape| cde.poly_collides(shape, irrel_hazards))
+}
+
+/// Applies the transformation to every shape of the item, storing the results in `buffers`
+fn transform_buffers(item: &Item, transform: &Transformation, buffers: &mut [SimplePolygon]) {
+    for (buffer, shape) in buffers.iter_mut().zip(item.shapes()) {
+        buffer.transform_from(shape, transform);
+    }
+}
@@ -55,7 +55,8 @@ fn main() {
         None => PolySimplConfig::Disabled,
     };
 
-    let parser = Parser::new(poly_simpl_config, config.cde_config, true);
+    let parser = Parser::new(poly_simpl_config, config.cde_config, true)
+        .with_validation_policy(config.poly_validation);
     let instance = parser.parse(&json_instance);
 
     let rng = match config.prng_seed {
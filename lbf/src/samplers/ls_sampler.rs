@@ -18,6 +18,7 @@ pub const SD_ROT: (fsize, fsize) = (2.0 * PI / 180.0, 0.5 * PI / 180.0);
 ///Creates `Transformation` samples for a given item.
 ///The samples are drawn from normal distributions with decaying standard deviations.
 ///Each time an improvement is found, the mean of the distributions is shifted to the new best transformation.
+///The samples are mirrored only if the reference transformation is.
 pub struct LSSampler {
     normal_x: Normal<fsize>,
     normal_y: Normal<fsize>,
@@ -26,6 +27,7 @@ pub struct LSSampler {
     sd_rot: fsize,
     sd_transl_range: (fsize, fsize),
     sd_rot_range: (fsize, fsize),
+    mirror: bool,
     pub(crate) n_samples: usize,
 }
 
@@ -51,6 +53,7 @@ impl LSSampler {
             sd_rot,
             sd_transl_range,
             sd_rot_range,
+            mirror: ref_transform.mirror(),
             n_samples: 0,
         }
     }
@@ -67,6 +70,7 @@ impl LSSampler {
         self.normal_x = Normal::new(ref_transform.translation().0, self.sd_transl).unwrap();
         self.normal_y = Normal::new(ref_transform.translation().1, self.sd_transl).unwrap();
         self.normal_r.set_mean(ref_transform.rotation());
+        self.mirror = ref_transform.mirror();
     }
 
     /// Sets the standard deviation of the normal distributions.
@@ -103,5 +107,6 @@ impl LSSampler {
             self.normal_r.sample(rng),
             (self.normal_x.sample(rng), self.normal_y.sample(rng)),
         )
+        .with_mirror(self.mirror)
     }
 }
@@ -13,17 +13,21 @@ pub trait RotationSampler {
     fn sample(&self, rng: &mut impl Rng) -> fsize;
 }
 
-/// Samples a rotation from a uniform distribution over a given range or a discrete set of rotations.
+/// Samples a rotation from a uniform distribution over a given range, a set of ranges or a discrete set of rotations.
+/// In case of multiple ranges, each range is chosen proportionally to its width.
 pub enum UniformRotDistr {
     Range(Uniform<fsize>),
+    Ranges(Vec<(fsize, fsize)>),
     Discrete(Vec<fsize>),
     None,
 }
 
-/// Samples a rotation from a normal distribution over a given range or a discrete set of rotations.
+/// Samples a rotation from a normal distribution over a given range, a set of ranges or a discrete set of rotations.
+/// In case of ranges, samples are clamped to the range closest to the mean.
 /// In case of discrete rotations the mean is always returned.
 pub enum NormalRotDistr {
     Range(Normal<fsize>),
+    Ranges(Normal<fsize>, Vec<(fsize, fsize)>),
     Discrete(fsize),
     None,
 }
@@ -34,6 +38,7 @@ impl UniformRotDistr {
             AllowedRotation::None => UniformRotDistr::None,
             AllowedRotation::Continuous => UniformRotDistr::Range(Uniform::new(0.0, 2.0 * PI)),
             AllowedRotation::Discrete(a_o) => UniformRotDistr::Discrete(a_o.clone()),
+            AllowedRotation::Ranges(ranges) => UniformRotDistr::Ranges(ranges.clone()),
         }
     }
 
@@ -41,6 +46,21 @@ impl UniformRotDistr {
         match self {
             UniformRotDistr::None => 0.0,
             UniformRotDistr::Range(u) => u.sample(rng),
+            UniformRotDistr::Ranges(ranges) => {
+                let total_width: fsize = ranges.iter().map(|(min, max)| max - min).sum();
+                if total_width == 0.0 {
+                    //only degenerate ranges
+                    return ranges.choose(rng).unwrap().0;
+                }
+                let mut r = rng.gen_range(0.0..total_width);
+                for &(min, max) in ranges {
+                    if r < max - min {
+                        return min + r;
+                    }
+                    r -= max - min;
+                }
+                ranges.last().unwrap().1
+            }
             UniformRotDistr::Discrete(a_o) => *a_o.choose(rng).unwrap(),
         }
     }
@@ -53,13 +73,16 @@ impl NormalRotDistr {
             AllowedRotation::Continuous => {
                 NormalRotDistr::Range(Normal::new(r_ref, stddev).unwrap())
             }
+            AllowedRotation::Ranges(ranges) => {
+                NormalRotDistr::Ranges(Normal::new(r_ref, stddev).unwrap(), ranges.clone())
+            }
             AllowedRotation::Discrete(_) => NormalRotDistr::Discrete(r_ref),
         }
     }
 
     pub fn set_mean(&mut self, mean: fsize) {
         match self {
-            NormalRotDistr::Range(n) => {
+            NormalRotDistr::Range(n) | NormalRotDistr::Ranges(n, _) => {
                 *n = Normal::new(mean, n.std_dev()).unwrap();
             }
             NormalRotDistr::Discrete(_) | NormalRotDistr::None => {}
@@ -68,7 +91,7 @@ impl NormalRotDistr {
 
     pub fn set_stddev(&mut self, stddev: fsize) {
         match self {
-            NormalRotDistr::Range(n) => {
+            NormalRotDistr::Range(n) | NormalRotDistr::Ranges(n, _) => {
                 *n = Normal::new(n.mean(), stddev).unwrap();
             }
             NormalRotDistr::Discrete(_) | NormalRotDistr::None => {}
@@ -79,7 +102,30 @@ impl NormalRotDistr {
         match self {
             NormalRotDistr::None => 0.0,
             NormalRotDistr::Range(n) => n.sample(rng),
+            NormalRotDistr::Ranges(n, ranges) => {
+                let range = ranges
+                    .iter()
+                    .min_by(|a, b| {
+                        let d_a = angular_distance(clamp_to_range(n.mean(), **a), n.mean());
+                        let d_b = angular_distance(clamp_to_range(n.mean(), **b), n.mean());
+                        d_a.partial_cmp(&d_b).unwrap()
+                    })
+                    .unwrap();
+                clamp_to_range(n.sample(rng), *range)
+            }
             NormalRotDistr::Discrete(r) => *r,
         }
     }
 }
+
+/// Clamps a rotation to a range, after bringing it within half a turn of the range's center.
+fn clamp_to_range(r: fsize, (min, max): (fsize, fsize)) -> fsize {
+    let center = (min + max) / 2.0;
+    let r = center + (r - center + PI).rem_euclid(2.0 * PI) - PI;
+    r.clamp(min, max)
+}
+
+/// Smallest absolute difference between two rotations, in [0, π].
+fn angular_distance(r1: fsize, r2: fsize) -> fsize {
+    ((r1 - r2 + PI).rem_euclid(2.0 * PI) - PI).abs()
+}
@@ -4,16 +4,19 @@ use rand::Rng;
 use jagua_rs::entities::item::Item;
 use jagua_rs::fsize;
 use jagua_rs::geometry::d_transformation::DTransformation;
+use jagua_rs::geometry::geo_enums::AllowedMirroring;
 use jagua_rs::geometry::primitives::aa_rectangle::AARectangle;
 
 use crate::samplers::rotation_distr::UniformRotDistr;
 
 /// Samples a `DTransformation` from a uniform distribution over a given `AARectangle` and a `UniformRotDistr`.
+/// Items which are allowed to be mirrored are mirrored in half of the samples.
 pub struct UniformAARectSampler {
     pub bbox: AARectangle,
     pub uniform_x: Uniform<fsize>,
     pub uniform_y: Uniform<fsize>,
     pub uniform_r: UniformRotDistr,
+    pub mirror: bool,
 }
 
 impl UniformAARectSampler {
@@ -21,11 +24,13 @@ impl UniformAARectSampler {
         let uniform_x = Uniform::new(bbox.x_min, bbox.x_max);
         let uniform_y = Uniform::new(bbox.y_min, bbox.y_max);
         let uniform_r = UniformRotDistr::from_item(item);
+        let mirror = item.allowed_mirroring == AllowedMirroring::Optional;
         Self {
             bbox,
             uniform_x,
             uniform_y,
             uniform_r,
+            mirror,
         }
     }
 
@@ -33,7 +38,8 @@ impl UniformAARectSampler {
         let r_sample = self.uniform_r.sample(rng);
         let x_sample = self.uniform_x.sample(rng);
         let y_sample = self.uniform_y.sample(rng);
+        let m_sample = self.mirror && rng.gen_bool(0.5);
 
-        DTransformation::new(r_sample, (x_sample, y_sample))
+        DTransformation::new(r_sample, (x_sample, y_sample)).with_mirror(m_sample)
     }
 }
@@ -1,22 +1,92 @@
 #[cfg(test)]
 mod tests {
     use std::path::Path;
+    use std::slice;
+    use std::sync::Arc;
+    use std::time::Instant;
 
+    use itertools::Itertools;
     use rand::prelude::IteratorRandom;
     use rand::prelude::SmallRng;
     use rand::{Rng, SeedableRng};
     use test_case::test_case;
 
+    use jagua_rs::collision_detection::cd_engine::CDEngine;
+    #[cfg(feature = "cde-stats")]
+    use jagua_rs::collision_detection::cde_stats::CDEStats;
+    use jagua_rs::collision_detection::hazard::{Hazard, HazardEntity};
+    use jagua_rs::collision_detection::hazard_filter::{self, CustomHazardFilter, HazardFilter};
+    use jagua_rs::collision_detection::hpg::hpg_cell::HPGCell;
+    use jagua_rs::entities::bin::{Bin, STRIP_CDE_HEADROOM};
+    use jagua_rs::entities::instances::instance::Instance;
+    use jagua_rs::entities::instances::instance_generic::InstanceGeneric;
+    use jagua_rs::entities::item::Item;
+    use jagua_rs::entities::layout::Layout;
+    use jagua_rs::entities::placed_item::{PItemKey, PlacedItem};
+    use jagua_rs::entities::placing_option::PlacingOption;
+    use jagua_rs::entities::problems::problem::Problem;
+    use jagua_rs::entities::problems::problem_generic::private::ProblemGenericPrivate;
     use jagua_rs::entities::problems::problem_generic::LayoutIndex;
     use jagua_rs::entities::problems::problem_generic::ProblemGeneric;
-    use jagua_rs::io::parser::Parser;
+    use jagua_rs::entities::problems::problem_generic::Transaction;
+    use jagua_rs::entities::problems::strip_packing::SPProblem;
+    use jagua_rs::entities::solution::Solution;
+    use jagua_rs::geometry::boolean_ops;
+    use jagua_rs::geometry::d_transformation::DTransformation;
+    use jagua_rs::geometry::geo_enums::{
+        AllowedMirroring, AllowedRotation, GeoPosition, GeoRelation,
+    };
+    use jagua_rs::geometry::geo_traits::{
+        CollidesWith, DistanceFrom, Shape, Transformable, TransformableFrom,
+    };
+    use jagua_rs::geometry::nfp::{self, NFPCache};
+    use jagua_rs::geometry::offset::JoinStyle;
+    use jagua_rs::geometry::primitives::aa_rectangle::AARectangle;
+    use jagua_rs::geometry::primitives::point::Point;
+    use jagua_rs::geometry::primitives::simple_polygon::SimplePolygon;
+    use jagua_rs::geometry::transformation::Transformation;
+    use jagua_rs::io::compact_solution::{
+        CompactLayout, CompactSolution, COMPACT_SOLUTION_VERSION,
+    };
+    use jagua_rs::io::json_instance::{
+        JsonBin, JsonFixedPlacement, JsonInstance, JsonItem, JsonObjective, JsonOpenDimension,
+        JsonOrientation, JsonPoly, JsonQualityZone, JsonShape, JsonSimplePoly, JsonStrip,
+    };
+    use jagua_rs::io::json_solution::{
+        JsonContainer, JsonLayout, JsonLayoutStats, JsonPlacedItem, JsonTransformation,
+    };
+    use jagua_rs::io::parse_error::ParseError;
+    use jagua_rs::io::parser;
+    use jagua_rs::io::parser::{ComposeJson, Parser};
+    use jagua_rs::util::assertions;
+    use jagua_rs::util::config::CDEConfig;
     use jagua_rs::util::polygon_simplification::PolySimplConfig;
+    use jagua_rs::util::polygon_validation::{
+        self, PolyValidationPolicy, PolygonDefect, DEFAULT_VALIDATION_TOLERANCE,
+    };
+    use jagua_rs::util::predicates;
+    use jagua_rs::{fsize, PI};
     use lbf::io;
     use lbf::lbf_config::LBFConfig;
     use lbf::lbf_optimizer::LBFOptimizer;
+    use lbf::samplers::rotation_distr::{NormalRotDistr, UniformRotDistr};
 
     const N_ITEMS_TO_REMOVE: usize = 5;
 
+    /// Default configuration with a reduced number of samples, to keep the tests fast
+    fn test_config() -> LBFConfig {
+        LBFConfig {
+            n_samples: 100,
+            ..LBFConfig::default()
+        }
+    }
+
+    /// Parser without polygon simplification, following the validation policy and CDE configuration of `config`
+    fn test_parser(config: &LBFConfig) -> Parser {
+        Parser::new(PolySimplConfig::Disabled, config.cde_config, true)
+            .with_validation_policy(config.poly_validation)
+    }
+
     #[test_case("../assets/swim.json"; "swim")]
     #[test_case("../assets/shirts.json"; "shirts")]
     #[test_case("../assets/trousers.json"; "trousers")]
@@ -76,4 +146,2568 @@ mod tests {
             optimizer.solve();
         }
     }
+
+    const N_BATCH_TRANSFORMS: usize = 1000;
+
+    fn assert_sync<T: Sync>() {}
+
+    #[test]
+    fn cde_and_layout_are_sync() {
+        assert_sync::<CDEngine>();
+        assert_sync::<Layout>();
+    }
+
+    #[test_case("../assets/swim.json"; "swim")]
+    #[test_case("../assets/trousers.json"; "trousers")]
+    #[test_case("../assets/baldacci1.json"; "baldacci1")]
+    fn test_evaluate_batch(instance_path: &str) {
+        let instance = Path::new(instance_path);
+        let config = test_config();
+        let json_instance = io::read_json_instance(instance);
+        let parser = test_parser(&config);
+        let instance = parser.parse(&json_instance);
+
+        let mut optimizer = LBFOptimizer::new(instance.clone(), config, SmallRng::seed_from_u64(0));
+        optimizer.solve();
+
+        let mut rng = SmallRng::seed_from_u64(0);
+        let layout = optimizer.problem.get_layout(LayoutIndex::Real(0));
+        let cde = layout.cde();
+        let bbox = layout.bin().bbox();
+        let mut n_collisions = 0;
+
+        for (item, _) in instance.items() {
+            let transforms = (0..N_BATCH_TRANSFORMS)
+                .map(|_| {
+                    let rotation = rng.gen_range(0.0..2.0 * PI);
+                    let translation: (fsize, fsize) = (
+                        rng.gen_range(bbox.x_min..bbox.x_max),
+                        rng.gen_range(bbox.y_min..bbox.y_max),
+                    );
+                    Transformation::from_rotation(rotation).translate(translation)
+                })
+                .collect::<Vec<_>>();
+
+            let batch_results = cde.evaluate_batch(&item.shape, &transforms, &[]);
+            n_collisions += batch_results.iter().filter(|&&c| c).count();
+
+            //the batch evaluation should be identical to a sequential one
+            let mut buffer = (*item.shape).clone();
+            for (transform, batch_result) in transforms.iter().zip(batch_results) {
+                buffer.transform_from(&item.shape, transform);
+                assert_eq!(cde.poly_collides(&buffer, &[]), batch_result);
+            }
+        }
+
+        //both outcomes should have been compared
+        let n_evaluations = N_BATCH_TRANSFORMS * instance.items().len();
+        assert!(n_collisions > 0 && n_collisions < n_evaluations);
+    }
+
+    fn custom_hazard(id: usize, position: GeoPosition, rect: AARectangle) -> Hazard {
+        let entity = HazardEntity::Custom { id, position };
+        Hazard::new(entity, Arc::new(SimplePolygon::from(rect)))
+    }
+
+    /// Deems all hazards except custom ones as irrelevant
+    struct NonCustomHazardFilter;
+
+    impl HazardFilter for NonCustomHazardFilter {
+        fn is_irrelevant(&self, entity: &HazardEntity) -> bool {
+            !matches!(entity, HazardEntity::Custom { .. })
+        }
+    }
+
+    #[test_case("../assets/swim.json"; "swim")]
+    #[test_case("../assets/shirts.json"; "shirts")]
+    fn test_custom_hazards_respected(instance_path: &str) {
+        let instance = Path::new(instance_path);
+        let config = test_config();
+        let json_instance = io::read_json_instance(instance);
+        let parser = test_parser(&config);
+        let instance = parser.parse(&json_instance);
+
+        let mut optimizer = LBFOptimizer::new(instance, config, SmallRng::seed_from_u64(0));
+        {
+            let layout = &mut optimizer.problem.layouts_mut()[0];
+            let bbox = layout.bin().bbox();
+            let (w, h) = (bbox.width(), bbox.height());
+            //a static block in the bottom left corner and a dynamic one in the top left corner
+            let static_rect = AARectangle::new(0.0, 0.0, 0.1 * w, 0.3 * h);
+            let dynamic_rect = AARectangle::new(0.0, 0.7 * h, 0.1 * w, h);
+            layout.add_custom_hazard(custom_hazard(0, GeoPosition::Interior, static_rect), true);
+            layout.add_custom_hazard(custom_hazard(1, GeoPosition::Interior, dynamic_rect), false);
+        }
+        optimizer.solve();
+
+        //the custom hazards should have survived the modifications of the strip, and no item should collide with them
+        let layout = optimizer.problem.get_layout(LayoutIndex::Real(0));
+        assert_eq!(layout.custom_hazards().count(), 2);
+        assert!(!layout.placed_items().is_empty());
+        let non_custom = hazard_filter::generate_irrelevant_hazards(
+            &NonCustomHazardFilter,
+            layout.cde().all_hazards(),
+        );
+        for (_, pi) in layout.placed_items().iter() {
+            assert!(!layout.cde().poly_collides(&pi.shape, &non_custom));
+        }
+    }
+
+    #[test]
+    fn test_custom_hazard_snapshots() {
+        let json_instance = io::read_json_instance(Path::new("../assets/swim.json"));
+        let config = LBFConfig::default();
+        let parser = test_parser(&config);
+        let instance = parser.parse(&json_instance);
+        let optimizer = LBFOptimizer::new(instance, config, SmallRng::seed_from_u64(0));
+
+        let mut layout = optimizer.problem.layouts()[0].clone();
+        let bbox = layout.bin().bbox();
+        let (w, h) = (bbox.width(), bbox.height());
+        let center_rect = AARectangle::new(0.4 * w, 0.4 * h, 0.6 * w, 0.6 * h);
+        let probe = SimplePolygon::from(AARectangle::new(0.45 * w, 0.45 * h, 0.55 * w, 0.55 * h));
+        let corner_probe =
+            SimplePolygon::from(AARectangle::new(0.02 * w, 0.02 * h, 0.07 * w, 0.07 * h));
+
+        let dynamic_haz = custom_hazard(0, GeoPosition::Interior, center_rect.clone());
+        layout.add_custom_hazard(dynamic_haz.clone(), false);
+        assert!(layout.cde().poly_collides(&probe, &[]));
+        let irrelevant = hazard_filter::generate_irrelevant_hazards(
+            &CustomHazardFilter(vec![0]),
+            layout.cde().all_hazards(),
+        );
+        assert!(!layout.cde().poly_collides(&probe, &irrelevant));
+
+        let snapshot = layout.create_snapshot();
+
+        //swap the dynamic hazard for a static one, and restrict the layout with an exterior hazard
+        layout.remove_custom_hazard(dynamic_haz.entity, false);
+        layout.add_custom_hazard(custom_hazard(1, GeoPosition::Interior, center_rect), true);
+        let inner_rect = AARectangle::new(0.1 * w, 0.1 * h, 0.9 * w, 0.9 * h);
+        layout.add_custom_hazard(custom_hazard(2, GeoPosition::Exterior, inner_rect), false);
+        assert!(layout.cde().poly_collides(&probe, &[]));
+        assert!(layout.cde().poly_collides(&corner_probe, &[]));
+        layout.flush_changes();
+        assert!(layout.cde().haz_prox_grid().is_ok());
+
+        //static hazards persist when restoring, dynamic ones are rolled back
+        layout.restore(&snapshot);
+        let mut entities = layout
+            .custom_hazards()
+            .map(|h| h.entity)
+            .collect::<Vec<_>>();
+        entities.sort_by_key(|e| match e {
+            HazardEntity::Custom { id, .. } => *id,
+            _ => unreachable!(),
+        });
+        assert_eq!(
+            entities,
+            vec![
+                HazardEntity::Custom {
+                    id: 0,
+                    position: GeoPosition::Interior
+                },
+                HazardEntity::Custom {
+                    id: 1,
+                    position: GeoPosition::Interior
+                },
+            ]
+        );
+        assert!(!layout.cde().poly_collides(&corner_probe, &[]));
+
+        layout.remove_custom_hazard(
+            HazardEntity::Custom {
+                id: 1,
+                position: GeoPosition::Interior,
+            },
+            true,
+        );
+        assert_eq!(layout.custom_hazards().count(), 1);
+        assert!(layout.cde().haz_prox_grid().is_ok());
+    }
+
+    #[test]
+    fn test_custom_hazards_update_hpg_incrementally() {
+        let json_instance = io::read_json_instance(Path::new("../assets/baldacci1.json"));
+        let config = test_config();
+        let instance = test_parser(&config).parse(&json_instance);
+        let mut optimizer = LBFOptimizer::new(instance, config, SmallRng::seed_from_u64(0));
+        optimizer.solve();
+
+        let mut layout = optimizer.problem.layouts()[0].clone();
+        assert!(!layout.placed_items().is_empty());
+        let bbox = layout.bin().bbox();
+        let (w, h) = (bbox.width(), bbox.height());
+        let proximities = |layout: &Layout| {
+            let hpg = layout.cde().haz_prox_grid().unwrap();
+            hpg.grid
+                .cells
+                .iter()
+                .map(|c| c.as_ref().map(|c| (c.uni_prox.0, c.static_uni_prox.0)))
+                .collect_vec()
+        };
+        let initial = proximities(&layout);
+
+        let hazards = [
+            custom_hazard(
+                0,
+                GeoPosition::Interior,
+                AARectangle::new(0.3 * w, 0.3 * h, 0.5 * w, 0.6 * h),
+            ),
+            custom_hazard(
+                1,
+                GeoPosition::Exterior,
+                AARectangle::new(0.1 * w, 0.1 * h, 0.9 * w, 0.9 * h),
+            ),
+        ];
+        for hazard in hazards.iter() {
+            let before = proximities(&layout);
+            layout.add_custom_hazard(hazard.clone(), true);
+
+            //every cell should know its exact distance to the static hazards, as if the grid was built from scratch
+            let cde = layout.cde();
+            let hpg = cde.haz_prox_grid().unwrap();
+            for (cell, prev) in hpg.grid.cells.iter().zip(before.iter()) {
+                if let (Some(cell), Some((prev_uni_prox, _))) = (cell, prev) {
+                    let fresh = HPGCell::new(cell.bbox.clone(), cde.static_hazards());
+                    assert_eq!(cell.static_uni_prox, fresh.static_uni_prox);
+                    assert_eq!(cell.uni_prox.0, cell.static_uni_prox.0.min(*prev_uni_prox));
+                }
+            }
+        }
+
+        //removing the hazards again should restore the grid to its initial state
+        for hazard in hazards.iter() {
+            layout.remove_custom_hazard(hazard.entity, true);
+        }
+        assert_eq!(proximities(&layout), initial);
+    }
+
+    fn square(min: fsize, max: fsize) -> JsonSimplePoly {
+        JsonSimplePoly(vec![(min, min), (max, min), (max, max), (min, max)])
+    }
+
+    #[test]
+    fn test_items_with_holes() {
+        //a frame with a square hole, and small squares which fit inside the hole
+        let frame = JsonItem {
+            demand: 1,
+            allowed_orientations: Some(vec![JsonOrientation::Angle(0.0)]),
+            allowed_mirroring: None,
+            shape: JsonShape::Polygon(JsonPoly {
+                outer: square(0.0, 8.0),
+                inner: vec![square(2.0, 6.0)],
+            }),
+            value: None,
+            base_quality: None,
+        };
+        let small_square = JsonItem {
+            demand: 3,
+            allowed_orientations: Some(vec![JsonOrientation::Angle(0.0)]),
+            allowed_mirroring: None,
+            shape: JsonShape::SimplePolygon(square(0.0, 3.0)),
+            value: None,
+            base_quality: None,
+        };
+        let json_instance = JsonInstance {
+            name: "frame".to_string(),
+            items: vec![frame, small_square],
+            bins: None,
+            strip: Some(JsonStrip {
+                height: 10.0,
+                fixed_placements: vec![],
+            }),
+            objective: None,
+            open_dimension: None,
+        };
+
+        let config = LBFConfig {
+            n_samples: 1000,
+            ..LBFConfig::default()
+        };
+        let parser = test_parser(&config);
+        let instance = parser.parse(&json_instance);
+        let frame_shape = &instance.item(0).shape;
+        assert_eq!(frame_shape.holes.len(), 1);
+        assert!((frame_shape.area - 48.0).abs() < 1e-3);
+
+        let mut optimizer = LBFOptimizer::new(instance.clone(), config, SmallRng::seed_from_u64(0));
+        let solution = optimizer.solve();
+        assert_eq!(solution.n_items_placed(), 4);
+
+        let layout = &optimizer.problem.layouts()[0];
+        let (frame_key, placed_frame) = layout
+            .placed_items()
+            .iter()
+            .find(|(_, pi)| pi.item_id == 0)
+            .unwrap();
+        let hole_bbox = placed_frame.shape.holes[0].bbox.clone();
+
+        //the hole itself is free, the material of the frame is not
+        let inside_hole = SimplePolygon::from(hole_bbox.clone().scale(0.8));
+        let overlapping_frame = SimplePolygon::from(hole_bbox.clone().scale(1.2));
+        let all_but_frame = layout
+            .cde()
+            .all_hazards()
+            .map(|h| h.entity)
+            .filter(|e| *e != HazardEntity::PlacedItem(frame_key))
+            .collect::<Vec<_>>();
+        assert!(!layout.cde().poly_collides(&inside_hole, &all_but_frame));
+        assert!(layout
+            .cde()
+            .poly_collides(&overlapping_frame, &all_but_frame));
+
+        //at least one of the small squares should have been nested inside the hole
+        let n_nested = layout
+            .placed_items()
+            .values()
+            .filter(|pi| pi.item_id == 1)
+            .filter(|pi| hole_bbox.relation_to(&pi.shape.bbox) == GeoRelation::Surrounding)
+            .count();
+        assert!(n_nested >= 1);
+    }
+
+    fn rect_poly(x_min: fsize, y_min: fsize, x_max: fsize, y_max: fsize) -> JsonPoly {
+        JsonPoly {
+            outer: JsonSimplePoly(vec![
+                (x_min, y_min),
+                (x_max, y_min),
+                (x_max, y_max),
+                (x_min, y_max),
+            ]),
+            inner: vec![],
+        }
+    }
+
+    #[test]
+    fn test_multi_polygon_items_and_bins() {
+        //pairs of squares which have to be placed together, in a bin consisting of two separate sheets
+        let pair = JsonItem {
+            demand: 4,
+            allowed_orientations: Some(vec![
+                JsonOrientation::Angle(0.0),
+                JsonOrientation::Angle(90.0),
+            ]),
+            allowed_mirroring: None,
+            shape: JsonShape::MultiPolygon(vec![
+                rect_poly(0.0, 0.0, 2.0, 2.0),
+                rect_poly(3.0, 0.0, 5.0, 2.0),
+            ]),
+            value: None,
+            base_quality: None,
+        };
+        let square = JsonItem {
+            demand: 4,
+            allowed_orientations: None,
+            allowed_mirroring: None,
+            shape: JsonShape::SimplePolygon(square(0.0, 2.0)),
+            value: None,
+            base_quality: None,
+        };
+        let bin = JsonBin {
+            cost: 1,
+            stock: Some(1),
+            shape: JsonShape::MultiPolygon(vec![
+                rect_poly(0.0, 0.0, 10.0, 10.0),
+                rect_poly(12.0, 0.0, 22.0, 10.0),
+            ]),
+            zones: vec![],
+            fixed_placements: vec![],
+        };
+        let json_instance = JsonInstance {
+            name: "sheets".to_string(),
+            items: vec![pair, square],
+            bins: Some(vec![bin]),
+            strip: None,
+            objective: None,
+            open_dimension: None,
+        };
+
+        let config = LBFConfig {
+            n_samples: 1000,
+            ..LBFConfig::default()
+        };
+        let parser = test_parser(&config);
+        let instance = parser.parse(&json_instance);
+        assert_eq!(instance.item(0).extra_shapes.len(), 1);
+        assert!((instance.item(0).area() - 8.0).abs() < 1e-3);
+
+        let mut optimizer = LBFOptimizer::new(instance.clone(), config, SmallRng::seed_from_u64(0));
+        let solution = optimizer.solve();
+        assert_eq!(solution.n_items_placed(), 8);
+        assert!((solution.usage - 48.0 / 200.0).abs() < 1e-3);
+
+        let layout = &optimizer.problem.layouts()[0];
+        assert!((layout.bin().area - 200.0).abs() < 1e-3);
+        //the space between the sheets is modeled without nesting holes
+        assert!(layout.bin().holes.iter().all(|h| h.holes.is_empty()));
+        let c = layout.bin().bbox().centroid();
+        let sheets = [
+            AARectangle::new(c.0 - 11.0, c.1 - 5.0, c.0 - 1.0, c.1 + 5.0),
+            AARectangle::new(c.0 + 1.0, c.1 - 5.0, c.0 + 11.0, c.1 + 5.0),
+        ]
+        .map(SimplePolygon::from);
+
+        //shapes within a sheet are accepted, shapes straddling the sheets or outside of them are not
+        let base_cde = layout.bin().base_cde.as_ref();
+        let probe = |x_min: fsize, x_max: fsize| {
+            let rect = AARectangle::new(c.0 + x_min, c.1 - 1.0, c.0 + x_max, c.1 + 1.0);
+            base_cde.poly_collides(&SimplePolygon::from(rect), &[])
+        };
+        assert!(!probe(-8.0, -4.0));
+        assert!(!probe(4.0, 8.0));
+        assert!(probe(-3.0, 3.0));
+        assert!(probe(-0.5, 0.5));
+        assert!(probe(-12.0, -11.5));
+
+        //cells of the hazard proximity grid on a sheet know their distance to its edge, all others lie within a hazard
+        let hpg = base_cde.haz_prox_grid().unwrap();
+        for cell in hpg.grid.cells.iter().flatten() {
+            let distance = match sheets.iter().find(|s| s.collides_with(&cell.centroid)) {
+                Some(sheet) => sheet.distance_from_border(&cell.centroid).1,
+                None => 0.0,
+            };
+            assert!((cell.static_uni_prox.0 - distance).abs() < 1e-3);
+        }
+
+        //every component of every item has to lie entirely on one of the sheets
+        for (pik, pi) in layout.placed_items().iter() {
+            let n_hazards = layout
+                .cde()
+                .dynamic_hazards()
+                .iter()
+                .filter(|h| h.entity == HazardEntity::PlacedItem(pik))
+                .count();
+            assert_eq!(n_hazards, 1 + pi.extra_shapes.len());
+            for shape in pi.shapes() {
+                assert!(sheets
+                    .iter()
+                    .any(|sheet| sheet.bbox.relation_to(&shape.bbox) == GeoRelation::Surrounding));
+            }
+        }
+
+        //a single transformation is reported per item, regardless of the number of components
+        let json_solution = parser::compose_json_solution(&solution, &instance, Instant::now());
+        assert_eq!(json_solution.layouts[0].placed_items.len(), 8);
+
+        //removing a multi-polygon item removes all of its components
+        let problem = &mut optimizer.problem;
+        let pair_key = problem.layouts()[0]
+            .placed_items()
+            .iter()
+            .find(|(_, pi)| pi.item_id == 0)
+            .map(|(pik, _)| pik)
+            .unwrap();
+        problem.remove_item(LayoutIndex::Real(0), pair_key, false);
+        problem.flush_changes();
+        assert!(!problem.layouts()[0]
+            .cde()
+            .all_hazards()
+            .any(|h| h.entity == HazardEntity::PlacedItem(pair_key)));
+    }
+
+    #[test]
+    fn test_quality_zone_with_hole() {
+        //the entire bin is of inferior quality, except for a hole in the middle of the zone
+        let ring = JsonPoly {
+            outer: square(0.0, 10.0),
+            inner: vec![square(3.0, 7.0)],
+        };
+        let bin = JsonBin {
+            cost: 1,
+            stock: Some(1),
+            shape: JsonShape::Rectangle {
+                width: 10.0,
+                height: 10.0,
+            },
+            zones: vec![JsonQualityZone {
+                quality: 0,
+                shape: JsonShape::Polygon(ring),
+            }],
+            fixed_placements: vec![],
+        };
+        let item = JsonItem {
+            demand: 1,
+            allowed_orientations: Some(vec![JsonOrientation::Angle(0.0)]),
+            allowed_mirroring: None,
+            shape: JsonShape::SimplePolygon(square(0.0, 3.0)),
+            value: None,
+            base_quality: None,
+        };
+        let json_instance = JsonInstance {
+            name: "ring_zone".to_string(),
+            items: vec![item],
+            bins: Some(vec![bin]),
+            strip: None,
+            objective: None,
+            open_dimension: None,
+        };
+
+        let config = LBFConfig {
+            n_samples: 1000,
+            ..LBFConfig::default()
+        };
+        let parser = test_parser(&config);
+        let instance = parser.parse(&json_instance);
+        let mut optimizer = LBFOptimizer::new(instance.clone(), config, SmallRng::seed_from_u64(0));
+
+        //inspect the empty layout of the bin
+        let template = &optimizer.problem.template_layouts()[0];
+        let zone = template.bin().quality_zones[0].as_ref().unwrap().zones[0].clone();
+        let hole = &zone.holes[0];
+        let hole_center = hole.centroid();
+        let cde = template.cde();
+
+        //the quadtree reflects the hole in the zone
+        let in_hole = SimplePolygon::from(hole.bbox.clone().scale(0.8));
+        let on_zone = SimplePolygon::from(hole.bbox.clone().scale(1.2));
+        assert!(!cde.poly_collides(&in_hole, &[]));
+        assert!(cde.poly_collides(&on_zone, &[]));
+
+        //the cell at the center of the hole is not affected by the zone
+        let hpg = cde.haz_prox_grid().unwrap();
+        let cell = hpg
+            .grid
+            .cells
+            .iter()
+            .flatten()
+            .find(|cell| cell.bbox.collides_with(&hole_center))
+            .unwrap();
+        assert!(cell.qz_prox[0] > 1.0);
+
+        //the only valid position for the item is inside the hole
+        let solution = optimizer.solve();
+        assert_eq!(solution.n_items_placed(), 1);
+        let layout = &optimizer.problem.layouts()[0];
+        let placed_item = layout.placed_items().values().next().unwrap();
+        assert_eq!(
+            hole.bbox.relation_to(&placed_item.shape.bbox),
+            GeoRelation::Surrounding
+        );
+    }
+    #[test]
+    fn test_parse_errors() {
+        let item = |shape: JsonSimplePoly| JsonItem {
+            demand: 1,
+            allowed_orientations: None,
+            allowed_mirroring: None,
+            shape: JsonShape::SimplePolygon(shape),
+            value: None,
+            base_quality: None,
+        };
+        let mut json_instance = JsonInstance {
+            name: "invalid".to_string(),
+            items: vec![item(square(0.0, 1.0)), item(square(0.0, 1.0))],
+            bins: None,
+            strip: None,
+            objective: None,
+            open_dimension: None,
+        };
+        let config = LBFConfig::default();
+        let parser = test_parser(&config);
+
+        //neither bins nor strip
+        assert!(matches!(
+            parser.try_parse(&json_instance),
+            Err(ParseError::InvalidContainer(_))
+        ));
+
+        //the second item contains a duplicate vertex
+        json_instance.strip = Some(JsonStrip {
+            height: 10.0,
+            fixed_placements: vec![],
+        });
+        json_instance.items[1] = item(JsonSimplePoly(vec![
+            (0.0, 0.0),
+            (1.0, 0.0),
+            (1.0, 0.0),
+            (0.0, 1.0),
+        ]));
+        assert!(matches!(
+            parser.try_parse(&json_instance),
+            Err(ParseError::InvalidItem { index: 1, .. })
+        ));
+
+        //a solution referring to an item which does not exist
+        json_instance.items[1] = item(square(0.0, 1.0));
+        let instance = parser.try_parse(&json_instance).unwrap();
+        let json_layout = JsonLayout {
+            container: JsonContainer::Strip {
+                width: 10.0,
+                height: 10.0,
+            },
+            placed_items: vec![JsonPlacedItem {
+                index: 2,
+                transformation: JsonTransformation {
+                    rotation: 0.0,
+                    translation: (0.0, 0.0),
+                    mirror: false,
+                },
+                locked: false,
+            }],
+            statistics: JsonLayoutStats { usage: 0.0 },
+        };
+        let result =
+            parser::try_build_solution_from_json(&instance, &[json_layout], config.cde_config);
+        assert!(matches!(
+            result,
+            Err(ParseError::InvalidLayout { index: 0, .. })
+        ));
+    }
+    #[test]
+    fn test_polygon_validation_and_repair() {
+        //a square with a collinear point and a spike
+        let square = vec![
+            (0.0, 0.0),
+            (1.0, 0.0),
+            (2.0, 0.0),
+            (2.0, 3.0),
+            (2.0, 2.0),
+            (0.0, 2.0),
+        ];
+        //two triangles touching in (1, 1)
+        let bowtie = vec![(0.0, 0.0), (2.0, 2.0), (2.0, 0.0), (0.0, 2.0)];
+        let to_points =
+            |ps: &[(fsize, fsize)]| ps.iter().map(|&p| Point::from(p)).collect::<Vec<_>>();
+
+        let defects =
+            polygon_validation::validate(&to_points(&square), DEFAULT_VALIDATION_TOLERANCE);
+        assert!(defects.contains(&PolygonDefect::Collinear(Point(1.0, 0.0))));
+        assert!(defects.contains(&PolygonDefect::Spike(Point(2.0, 3.0))));
+        let repaired =
+            polygon_validation::repair(&to_points(&square), DEFAULT_VALIDATION_TOLERANCE);
+        assert_eq!(repaired.len(), 1);
+        assert_eq!(repaired[0].len(), 4);
+
+        let defects =
+            polygon_validation::validate(&to_points(&bowtie), DEFAULT_VALIDATION_TOLERANCE);
+        assert!(defects.contains(&PolygonDefect::SelfIntersection(Point(1.0, 1.0))));
+        let repaired =
+            polygon_validation::repair(&to_points(&bowtie), DEFAULT_VALIDATION_TOLERANCE);
+        assert_eq!(repaired.len(), 2);
+        for contour in repaired {
+            let triangle = SimplePolygon::new(contour);
+            assert!(triangle.validate().is_empty());
+            assert!((triangle.area() - 1.0).abs() < 1e-6);
+        }
+
+        let item = |points: &[(fsize, fsize)]| JsonItem {
+            demand: 1,
+            allowed_orientations: None,
+            allowed_mirroring: None,
+            shape: JsonShape::SimplePolygon(JsonSimplePoly(points.to_vec())),
+            value: None,
+            base_quality: None,
+        };
+        let json_instance = JsonInstance {
+            name: "defects".to_string(),
+            items: vec![item(&square), item(&bowtie)],
+            bins: None,
+            strip: Some(JsonStrip {
+                height: 10.0,
+                fixed_placements: vec![],
+            }),
+            objective: None,
+            open_dimension: None,
+        };
+        let config = LBFConfig::default();
+        let parser = |policy| test_parser(&config).with_validation_policy(policy);
+
+        assert!(matches!(
+            parser(PolyValidationPolicy::Reject).try_parse(&json_instance),
+            Err(ParseError::InvalidItem { index: 0, .. })
+        ));
+        assert!(parser(PolyValidationPolicy::Warn)
+            .try_parse(&json_instance)
+            .is_err());
+
+        //the bowtie is split into an item consisting of two triangles
+        let instance = parser(PolyValidationPolicy::Repair).parse(&json_instance);
+        assert!((instance.item(0).area() - 4.0).abs() < 1e-6);
+        assert_eq!(instance.item(1).shapes().count(), 2);
+        assert!((instance.item(1).area() - 2.0).abs() < 1e-6);
+    }
+
+    #[test_case("../assets/swim.json"; "swim")]
+    #[test_case("../assets/shirts.json"; "shirts")]
+    #[test_case("../assets/trousers.json"; "trousers")]
+    fn test_boolean_ops(instance_path: &str) {
+        let json_instance = io::read_json_instance(Path::new(instance_path));
+        let config = LBFConfig::default();
+        let parser = test_parser(&config);
+        let instance = parser.parse(&json_instance);
+        let total_area = |polys: &[SimplePolygon]| polys.iter().map(|p| p.area()).sum::<fsize>();
+
+        for (item, _) in instance.items().iter() {
+            let a = item.shape.as_ref().clone();
+            //a rotated and shifted copy, which partially overlaps the original
+            let offset = 0.2 * a.bbox().width();
+            let b = a.transform_clone(&Transformation::from_rotation(0.3).translate((offset, 0.0)));
+            let tolerance = 1e-3 * a.area();
+
+            let union = boolean_ops::union(&a, &b);
+            let intersection = boolean_ops::intersection(&a, &b);
+            let difference = boolean_ops::difference(&a, &b);
+            assert!(!intersection.is_empty());
+
+            //inclusion-exclusion principle
+            let sum_areas = total_area(&union) + total_area(&intersection);
+            assert!((sum_areas - a.area() - b.area()).abs() < tolerance);
+            let sum_areas = total_area(&difference) + total_area(&intersection);
+            assert!((sum_areas - a.area()).abs() < tolerance);
+
+            //the intersection is covered by both operands and the difference by none of the other
+            for poly in intersection.iter() {
+                assert!(a.collides_with(&poly.poi.center) && b.collides_with(&poly.poi.center));
+            }
+            for poly in difference.iter() {
+                assert!(!b.collides_with(&poly.poi.center));
+            }
+
+            //operations with itself
+            assert!((total_area(&boolean_ops::union(&a, &a)) - a.area()).abs() < tolerance);
+            assert!(boolean_ops::difference(&a, &a).is_empty());
+        }
+
+        //subtracting a shape from its bounding box, results in a polygon with a hole
+        let shape = instance.item(0).shape.as_ref();
+        let bbox = SimplePolygon::from(shape.bbox().scale(1.1));
+        let remainder = boolean_ops::difference(&bbox, shape);
+        assert_eq!(remainder.len(), 1);
+        assert_eq!(remainder[0].holes.len(), 1);
+        assert!((remainder[0].area() - (bbox.area() - shape.area())).abs() < 1e-3 * bbox.area());
+    }
+
+    #[test]
+    fn test_nfp_and_ifp_of_rectangles() {
+        let rect = |w: fsize, h: fsize| {
+            SimplePolygon::from(AARectangle::new(-w / 2.0, -h / 2.0, w / 2.0, h / 2.0))
+        };
+        let total_area = |polys: &[SimplePolygon]| polys.iter().map(|p| p.area()).sum::<fsize>();
+        let cde_config = LBFConfig::default().cde_config;
+
+        //the NFP of two rectangles is a rectangle with the sum of their dimensions
+        let (fixed, orbiting) = (rect(2.0, 1.0), rect(1.0, 0.5));
+        let nfp_0 = nfp::nfp(&fixed, 0.0, &orbiting, 0.0);
+        assert_eq!(nfp_0.len(), 1);
+        assert!((total_area(&nfp_0) - 3.0 * 1.5).abs() < 1e-3);
+        let nfp_90 = nfp::nfp(&fixed, 0.0, &orbiting, PI / 2.0);
+        assert!((total_area(&nfp_90) - 2.5 * 2.0).abs() < 1e-3);
+
+        //a small square fits inside the hole of a larger one
+        let frame = SimplePolygon::with_holes(rect(10.0, 10.0).points, vec![rect(4.0, 4.0)]);
+        let nfp_frame = nfp::nfp(&frame, 0.0, &rect(1.0, 1.0), 0.0);
+        assert_eq!(nfp_frame.len(), 1);
+        assert_eq!(nfp_frame[0].holes.len(), 1);
+        assert!((total_area(&nfp_frame) - (11.0 * 11.0 - 3.0 * 3.0)).abs() < 1e-2);
+
+        //the IFP of a rectangle in a rectangular bin with a hole
+        let bin = Bin::new(
+            0,
+            rect(10.0, 5.0),
+            1,
+            Transformation::empty(),
+            vec![rect(2.0, 2.0)],
+            vec![],
+            cde_config,
+        );
+        let ifp = nfp::ifp(&bin, &rect(1.0, 1.0), 0.0);
+        assert_eq!(ifp.len(), 1);
+        assert!((total_area(&ifp) - (9.0 * 4.0 - 3.0 * 3.0)).abs() < 1e-2);
+        assert!(ifp[0].collides_with(&Point(3.0, 1.0)));
+        assert!(!ifp[0].collides_with(&Point(0.0, 0.0)));
+        assert!(nfp::ifp(&bin, &rect(11.0, 1.0), 0.0).is_empty());
+
+        //cached IFPs and NFPs between items are reused
+        let item = Item::new(
+            0,
+            rect(2.0, 1.0),
+            1,
+            AllowedRotation::Continuous,
+            Transformation::empty(),
+            None,
+            cde_config.item_surrogate_config,
+        );
+        let mut cache = NFPCache::new();
+        let ifp_90 = cache.ifp(&bin, &item, PI / 2.0);
+        assert!(Arc::ptr_eq(&ifp_90, &cache.ifp(&bin, &item, PI / 2.0)));
+        assert!((total_area(&ifp_90) - (9.0 * 3.0 - 3.0 * 3.0)).abs() < 1e-2);
+        let nfp_items = cache.nfp(&item, 0.0, &item, PI / 2.0);
+        assert!(Arc::ptr_eq(
+            &nfp_items,
+            &cache.nfp(&item, 0.0, &item, PI / 2.0)
+        ));
+        assert!((total_area(&nfp_items) - 3.0 * 3.0).abs() < 1e-2);
+        cache.nfp(&item, 0.0, &item, 0.0);
+        assert_eq!((cache.n_nfps(), cache.n_ifps()), (2, 1));
+    }
+
+    #[test_case("../assets/swim.json"; "swim")]
+    #[test_case("../assets/shirts.json"; "shirts")]
+    #[test_case("../assets/trousers.json"; "trousers")]
+    fn test_nfp_of_items(instance_path: &str) {
+        let json_instance = io::read_json_instance(Path::new(instance_path));
+        let config = LBFConfig::default();
+        let parser = test_parser(&config);
+        let instance = parser.parse(&json_instance);
+        let fixed = instance.item(0).shape.as_ref();
+        let orbiting = instance.item(instance.items().len() - 1).shape.as_ref();
+
+        for (fixed_rotation, orbiting_rotation) in [(0.0, 0.0), (0.0, PI / 2.0), (PI, 0.3)] {
+            let no_fit = nfp::nfp(fixed, fixed_rotation, orbiting, orbiting_rotation);
+            assert!(!no_fit.is_empty());
+            let fixed = fixed.transform_clone(&Transformation::from_rotation(fixed_rotation));
+            let bbox = no_fit
+                .iter()
+                .map(|p| p.bbox())
+                .reduce(|a, b| AARectangle::bounding_rectangle(&a, &b))
+                .unwrap()
+                .scale(1.2);
+
+            //the orbiting polygon overlaps the fixed one if and only if its reference point is inside the NFP
+            let n_samples = 15;
+            for (i, j) in (0..n_samples).cartesian_product(0..n_samples) {
+                let t = Point(
+                    bbox.x_min + bbox.width() * (i as fsize + 0.5) / n_samples as fsize,
+                    bbox.y_min + bbox.height() * (j as fsize + 0.5) / n_samples as fsize,
+                );
+                let distance_to_border = no_fit
+                    .iter()
+                    .map(|p| p.distance_from_border(&t).1)
+                    .fold(fsize::MAX, fsize::min);
+                if distance_to_border < 1e-2 * orbiting.diameter {
+                    continue;
+                }
+                let placed = orbiting.transform_clone(
+                    &Transformation::from_rotation(orbiting_rotation).translate((t.0, t.1)),
+                );
+                let overlaps = !boolean_ops::intersection(&fixed, &placed).is_empty();
+                let in_nfp = no_fit.iter().any(|p| p.collides_with(&t));
+                assert_eq!(overlaps, in_nfp, "mismatch at {:?}", t);
+            }
+        }
+    }
+
+    #[test]
+    fn test_robust_predicates() {
+        //coordinates on a grid of 2^-20, so the determinant can be computed exactly with integers
+        let scale = (1 << 20) as fsize;
+        let exact_orientation = |a: Point, b: Point, c: Point| {
+            let int = |v: fsize| (v * scale) as i128;
+            let (acx, acy) = (int(a.0) - int(c.0), int(a.1) - int(c.1));
+            let (bcx, bcy) = (int(b.0) - int(c.0), int(b.1) - int(c.1));
+            (acx * bcy - acy * bcx).signum()
+        };
+        let orientation = |a: Point, b: Point, c: Point| {
+            let o = predicates::orient2d(a, b, c);
+            (o > 0.0) as i128 - (o < 0.0) as i128
+        };
+
+        let mut rng = SmallRng::seed_from_u64(0);
+        let mut random_point = || {
+            let mut coord = || (rng.gen_range(-4.0..4.0) * scale).round() / scale;
+            Point(coord(), coord())
+        };
+        for _ in 0..10_000 {
+            let (a, b) = (random_point(), random_point());
+            //points on or within a few grid units of the line through a and b
+            let s = random_point().0 / 4.0;
+            let nudge = random_point().1.signum() / scale;
+            let on_line = Point(
+                ((a.0 + s * (b.0 - a.0)) * scale).round() / scale,
+                ((a.1 + s * (b.1 - a.1)) * scale).round() / scale + nudge,
+            );
+            for c in [random_point(), on_line] {
+                assert_eq!(orientation(a, b, c), exact_orientation(a, b, c));
+            }
+        }
+
+        //exactly collinear points, on which the naive determinant can fail
+        let (a, b, c) = (Point(0.5, 0.5), Point(12.0, 12.0), Point(24.0, 24.0));
+        assert_eq!(predicates::orient2d(a, b, c), 0.0);
+        assert!(!predicates::segments_intersect(
+            (a, c),
+            (b, Point(30.0, 30.0))
+        ));
+        assert!(predicates::segments_intersect(
+            (a, c),
+            (Point(0.0, 24.0), Point(24.0, 0.0))
+        ));
+
+        //the exact point-in-polygon test agrees with the default one away from the boundary
+        let json_instance = io::read_json_instance(Path::new("../assets/swim.json"));
+        let config = test_config();
+        assert!(!config.cde_config.robust_predicates);
+        let instance = test_parser(&config).parse(&json_instance);
+        for (item, _) in instance.items().iter() {
+            let shape = item.shape.as_ref();
+            let bbox = shape.bbox();
+            for _ in 0..100 {
+                let p = Point(
+                    rng.gen_range(bbox.x_min..bbox.x_max),
+                    rng.gen_range(bbox.y_min..bbox.y_max),
+                );
+                if shape.distance_from_border(&p).1 < 1e-3 * shape.diameter {
+                    continue;
+                }
+                let robust = predicates::ring_contains(&shape.points, p)
+                    && shape
+                        .holes
+                        .iter()
+                        .all(|h| !predicates::ring_contains(&h.points, p));
+                assert_eq!(robust, shape.contains_point(&p, true));
+                assert_eq!(robust, shape.collides_with(&p));
+            }
+        }
+    }
+
+    #[test]
+    fn test_robust_predicates_per_engine() {
+        //engines with and without robust predicates, side by side in the same process
+        let cde_config = |robust_predicates| CDEConfig {
+            robust_predicates,
+            ..test_config().cde_config
+        };
+        let bbox = AARectangle::new(-10.0, -10.0, 10.0, 10.0);
+        let mut rng = SmallRng::seed_from_u64(0);
+        let mut n_differing = 0;
+        for _ in 0..100 {
+            //hazard triangle right of the slanted edge from a to b
+            let a = Point(rng.gen_range(-8.0..-1.0), rng.gen_range(-8.0..-1.0));
+            let b = Point(rng.gen_range(1.0..8.0), rng.gen_range(1.0..8.0));
+            let hazard_shape = Arc::new(SimplePolygon::new(vec![a, Point(b.0, a.1), b]));
+            let hazard = Hazard::new(HazardEntity::BinHole { id: 0 }, hazard_shape.clone());
+
+            //the default engine is created last, it should not affect the robust one
+            let robust_cde = CDEngine::new(bbox.clone(), vec![hazard.clone()], cde_config(true));
+            let default_cde = CDEngine::new(bbox.clone(), vec![hazard], cde_config(false));
+
+            for _ in 0..100 {
+                //triangle left of the edge, with a vertex on it (up to rounding)
+                let s = rng.gen_range(0.1..0.9);
+                let p = Point(a.0 + s * (b.0 - a.0), a.1 + s * (b.1 - a.1));
+                let shape = SimplePolygon::new(vec![
+                    p,
+                    Point(p.0 - 0.5, p.1 + 1.0),
+                    Point(p.0 - 1.0, p.1 + 0.5),
+                ]);
+                let edges_intersect = |robust| {
+                    shape.border_edge_iter().any(|e1| {
+                        hazard_shape
+                            .border_edge_iter()
+                            .any(|e2| e1.intersects(&e2, robust))
+                    })
+                };
+                let (robust, default) = (edges_intersect(true), edges_intersect(false));
+                assert_eq!(robust_cde.poly_collides(&shape, &[]), robust);
+                assert_eq!(default_cde.poly_collides(&shape, &[]), default);
+                n_differing += (robust != default) as usize;
+            }
+        }
+        //the engines must have disagreed at least once, otherwise the test is vacuous
+        assert!(n_differing > 0);
+    }
+
+    #[test_case("../assets/swim.json"; "swim")]
+    #[test_case("../assets/trousers.json"; "trousers")]
+    fn test_edge_bvh_collisions(instance_path: &str) {
+        let instance = Path::new(instance_path);
+        let config = test_config();
+        let json_instance = io::read_json_instance(instance);
+        let parser = test_parser(&config);
+        let instance = parser.parse(&json_instance);
+
+        let mut optimizer = LBFOptimizer::new(instance.clone(), config, SmallRng::seed_from_u64(0));
+        optimizer.solve();
+
+        let mut rng = SmallRng::seed_from_u64(0);
+        let layout = optimizer.problem.get_layout(LayoutIndex::Real(0));
+        let cde = layout.cde();
+        let bbox = layout.bin().bbox();
+
+        let mut n_collisions = 0;
+        for (item, _) in instance.items() {
+            //split every edge in eight, so the shape gets an edge hierarchy
+            let split_edges = |shape: &SimplePolygon| {
+                shape
+                    .edge_iter()
+                    .flat_map(|e| {
+                        (0..8).map(move |i| {
+                            let t = i as fsize / 8.0;
+                            Point(
+                                e.start.0 + t * (e.end.0 - e.start.0),
+                                e.start.1 + t * (e.end.1 - e.start.1),
+                            )
+                        })
+                    })
+                    .collect::<Vec<_>>()
+            };
+            let shape =
+                SimplePolygon::with_holes(split_edges(&item.shape), item.shape.holes.clone());
+            assert!(shape.edge_bvh.is_some());
+            let mut without_bvh = shape.clone();
+            without_bvh.edge_bvh = None;
+
+            let (mut buffer, mut buffer_without_bvh) = (shape.clone(), without_bvh.clone());
+            for _ in 0..N_BATCH_TRANSFORMS {
+                let rotation = rng.gen_range(0.0..2.0 * PI);
+                let translation: (fsize, fsize) = (
+                    rng.gen_range(bbox.x_min..bbox.x_max),
+                    rng.gen_range(bbox.y_min..bbox.y_max),
+                );
+                let transform = Transformation::from_rotation(rotation).translate(translation);
+                buffer.transform_from(&shape, &transform);
+                buffer_without_bvh.transform_from(&without_bvh, &transform);
+
+                //the hierarchy is refitted to the transformed edges
+                assert_eq!(buffer.edge_bvh.as_ref().unwrap().root().bbox, buffer.bbox);
+                let collides = cde.poly_collides(&buffer, &[]);
+                assert_eq!(collides, cde.poly_collides(&buffer_without_bvh, &[]));
+                n_collisions += collides as usize;
+            }
+        }
+        //both outcomes should have been tested
+        assert!(n_collisions > 0 && n_collisions < N_BATCH_TRANSFORMS * instance.items().len());
+    }
+
+    #[test_case("../assets/swim.json"; "swim")]
+    fn test_polygon_distance(instance_path: &str) {
+        let rect = |x_min: fsize, y_min: fsize, x_max: fsize, y_max: fsize| {
+            SimplePolygon::from(AARectangle::new(x_min, y_min, x_max, y_max))
+        };
+
+        //two unit squares, side by side
+        let (a, b) = (rect(0.0, 0.0, 1.0, 1.0), rect(3.0, 0.5, 4.0, 1.5));
+        let (d, p, q) = a.closest_points(&b);
+        assert!((d - 2.0).abs() < 1e-5);
+        assert!((p.0 - 1.0).abs() < 1e-5 && (q.0 - 3.0).abs() < 1e-5);
+        assert!((p.1 - q.1).abs() < 1e-5 && p.1 >= 0.5 && p.1 <= 1.0);
+        assert!((a.distance(&b) - 2.0).abs() < 1e-5);
+        assert!(a.closest_points_within(&b, 1.0).is_none());
+        assert!(a.closest_points_within(&b, 2.5).is_some());
+        assert!(!a.within_distance(&b, 1.0));
+        assert!(a.within_distance(&b, 2.5));
+
+        //diagonally separated squares, the closest points are corners
+        let c = rect(2.0, 2.0, 3.0, 3.0);
+        let (d, p, q) = a.closest_points(&c);
+        assert!((d - fsize::sqrt(2.0)).abs() < 1e-5);
+        assert!(p.distance(Point(1.0, 1.0)) < 1e-5 && q.distance(Point(2.0, 2.0)) < 1e-5);
+
+        //overlapping and contained polygons are at distance zero
+        let (d, p, q) = a.closest_points(&rect(0.5, 0.5, 2.0, 2.0));
+        assert_eq!((d, p), (0.0, q));
+        assert_eq!(rect(-1.0, -1.0, 2.0, 2.0).distance(&a), 0.0);
+        assert_eq!(
+            a.distance_from_border(&rect(0.25, 0.25, 0.75, 0.75)),
+            (GeoPosition::Interior, 0.25)
+        );
+        //a cross whose arms pass through the square, without any vertex inside the other polygon
+        let cross = rect(-1.0, 0.25, 2.0, 0.75);
+        assert_eq!(rect(0.25, -1.0, 0.75, 2.0).distance(&cross), 0.0);
+
+        //a polygon inside the hole of another one is not colliding
+        let frame = SimplePolygon::with_holes(
+            rect(-5.0, -5.0, 5.0, 5.0).points,
+            vec![rect(-2.0, -2.0, 2.0, 2.0)],
+        );
+        let inner = rect(-1.0, -1.0, 0.5, 1.0);
+        assert!((frame.distance(&inner) - 1.0).abs() < 1e-5);
+        assert_eq!(inner.distance_from_border(&frame).0, GeoPosition::Exterior);
+
+        //compare with the distances between the vertices and the other polygon
+        let json_instance = io::read_json_instance(Path::new(instance_path));
+        let config = LBFConfig::default();
+        let parser = test_parser(&config);
+        let instance = parser.parse(&json_instance);
+        let mut rng = SmallRng::seed_from_u64(0);
+        let shapes = instance
+            .items()
+            .iter()
+            .map(|(item, _)| item.shape.as_ref().clone())
+            .collect_vec();
+        //translations in the order of the size of the shapes, so they both overlap and lie apart
+        let range = shapes.iter().map(|s| s.diameter).fold(0.0, fsize::max);
+        for _ in 0..100 {
+            let mut transformed = shapes
+                .iter()
+                .choose_multiple(&mut rng, 2)
+                .into_iter()
+                .cloned()
+                .collect_vec();
+            for shape in transformed.iter_mut() {
+                let transform = Transformation::from_rotation(rng.gen_range(0.0..2.0 * PI))
+                    .translate((rng.gen_range(-range..range), rng.gen_range(-range..range)));
+                shape.transform(&transform);
+            }
+            let (s1, s2) = (&transformed[0], &transformed[1]);
+            let (d, p, q) = s1.closest_points(s2);
+            assert!((d - s2.distance(s1)).abs() < 1e-3);
+            assert!((d - p.distance(q)).abs() < 1e-3);
+            if d > 0.0 {
+                //the distance is attained by the returned points, and never larger than from any vertex
+                assert!(s1.distance(&p) < 1e-3 && s2.distance(&q) < 1e-3);
+                let vertex_distance = s1
+                    .points
+                    .iter()
+                    .map(|v| s2.distance(v))
+                    .chain(s2.points.iter().map(|v| s1.distance(v)))
+                    .fold(fsize::INFINITY, fsize::min);
+                assert!(d <= vertex_distance + 1e-3);
+                assert!(s1.closest_points_within(s2, d * 0.99).is_none());
+                assert!(s1.within_distance(s2, d * 1.01));
+            } else {
+                assert!(s1.within_distance(s2, 0.0));
+            }
+        }
+    }
+
+    #[test]
+    fn test_knapsack() {
+        let mut json_instance = io::read_json_instance(Path::new("../assets/swim.json"));
+        let config = test_config();
+        let parser = test_parser(&config);
+
+        //a single bin, only large enough for about half of the items
+        let item_area = parser.parse(&json_instance).item_area();
+        let strip_height = json_instance.strip.take().unwrap().height;
+        let json_bin = |stock| JsonBin {
+            cost: 1,
+            stock,
+            shape: JsonShape::Rectangle {
+                width: 0.5 * item_area / strip_height,
+                height: strip_height,
+            },
+            zones: vec![],
+            fixed_placements: vec![],
+        };
+        json_instance.bins = Some(vec![json_bin(Some(1))]);
+        json_instance.objective = Some(JsonObjective::MaxItemValue);
+        for (i, json_item) in json_instance.items.iter_mut().enumerate() {
+            json_item.value = Some((i as u64 % 3 + 1) * 1000);
+        }
+
+        let instance = parser.parse(&json_instance);
+        let Instance::KP(kp_instance) = &instance else {
+            panic!("expected a knapsack instance");
+        };
+        assert_eq!(kp_instance.bins.len(), 1);
+
+        let mut optimizer = LBFOptimizer::new(instance.clone(), config, SmallRng::seed_from_u64(0));
+        let solution = optimizer.solve();
+
+        //not everything fits, but the densest item is always placed first
+        let value = solution.placed_item_value(&instance);
+        assert!(!solution.is_complete(&instance));
+        assert!(value > 0 && value < kp_instance.item_value());
+        let densest_item = (0..instance.items().len())
+            .max_by(|&a, &b| {
+                let density = |i: usize| instance.item(i).value as fsize / instance.item(i).area();
+                density(a).partial_cmp(&density(b)).unwrap()
+            })
+            .unwrap();
+        assert!(solution.placed_item_qtys[densest_item] > 0);
+
+        //the layout of the bin remains, even when all items are removed
+        let Problem::KP(kp_problem) = &mut optimizer.problem else {
+            panic!("expected a knapsack problem");
+        };
+        assert_eq!(kp_problem.placed_value(), value);
+        let placed_items = kp_problem.layouts[0].placed_items().keys().collect_vec();
+        for pik in placed_items {
+            kp_problem.remove_item(LayoutIndex::Real(0), pik, true);
+        }
+        assert_eq!(kp_problem.layouts().len(), 1);
+        assert_eq!(kp_problem.placed_value(), 0);
+        kp_problem.restore_to_solution(&solution);
+        assert_eq!(kp_problem.placed_value(), value);
+
+        //the JSON solution contains the value, and can be parsed back into the same solution
+        let json_solution = parser::compose_json_solution(&solution, &instance, Instant::now());
+        assert_eq!(json_solution.value, Some(value));
+        assert!(matches!(
+            json_solution.layouts[..],
+            [JsonLayout {
+                container: JsonContainer::Bin { index: 0 },
+                ..
+            }]
+        ));
+        let rebuilt = parser::try_build_solution_from_json(
+            &instance,
+            &json_solution.layouts,
+            config.cde_config,
+        )
+        .unwrap();
+        assert_eq!(rebuilt.placed_item_qtys, solution.placed_item_qtys);
+
+        //bins of a knapsack instance need a stock, and a strip cannot have an objective
+        json_instance.bins = Some(vec![json_bin(None)]);
+        assert!(matches!(
+            parser.try_parse(&json_instance),
+            Err(ParseError::InvalidBin { index: 0, .. })
+        ));
+        json_instance.bins = None;
+        json_instance.strip = Some(JsonStrip {
+            height: strip_height,
+            fixed_placements: vec![],
+        });
+        assert!(matches!(
+            parser.try_parse(&json_instance),
+            Err(ParseError::InvalidContainer(_))
+        ));
+    }
+
+    #[test_case(Some(6000.0), None; "fixed width")]
+    #[test_case(None, None; "area")]
+    #[test_case(None, Some(2.0); "aspect ratio")]
+    fn test_open_dimension(width: Option<fsize>, aspect_ratio: Option<fsize>) {
+        let mut json_instance = io::read_json_instance(Path::new("../assets/swim.json"));
+        json_instance.strip = None;
+        json_instance.open_dimension = Some(JsonOpenDimension {
+            width,
+            aspect_ratio,
+        });
+        let config = test_config();
+        let parser = test_parser(&config);
+        let instance = parser.parse(&json_instance);
+        assert!(matches!(instance, Instance::OD(_)));
+
+        let mut optimizer = LBFOptimizer::new(instance.clone(), config, SmallRng::seed_from_u64(0));
+        let solution = optimizer.solve();
+        assert!(solution.is_complete(&instance));
+
+        //the container is fitted around the items, respecting the fixed width or aspect ratio
+        let Problem::OD(od_problem) = &mut optimizer.problem else {
+            panic!("expected an open dimension problem");
+        };
+        let (c_width, c_height) = (od_problem.container_width(), od_problem.container_height());
+        let occupied = od_problem.occupied_bbox().unwrap();
+        assert!(c_width >= occupied.width() && c_height >= occupied.height());
+        assert!(od_problem.container_area() >= instance.item_area());
+        match (width, aspect_ratio) {
+            (Some(width), _) => assert_eq!(c_width, width),
+            (None, Some(ratio)) => assert!((c_width / c_height - ratio).abs() < 1e-3),
+            (None, None) => {
+                assert!((c_width - occupied.width()) / c_width < 1e-3);
+                assert!((c_height - occupied.height()) / c_height < 1e-3);
+            }
+        }
+
+        //the JSON solution contains the dimensions of the container, and can be parsed back into the same solution
+        let json_solution = parser::compose_json_solution(&solution, &instance, Instant::now());
+        let JsonContainer::OpenDimension {
+            width: json_width,
+            height: json_height,
+        } = json_solution.layouts[0].container
+        else {
+            panic!("expected an open dimension container");
+        };
+        assert_eq!((json_width, json_height), (c_width, c_height));
+        let rebuilt = parser::try_build_solution_from_json(
+            &instance,
+            &json_solution.layouts,
+            config.cde_config,
+        )
+        .unwrap();
+        assert_eq!(rebuilt.placed_item_qtys, solution.placed_item_qtys);
+
+        //shrinking the container in both axes removes the items which no longer fit
+        od_problem.modify_container(c_width * 0.5, c_height * 0.5);
+        let n_placed = od_problem.placed_item_qtys().sum::<usize>();
+        assert!(n_placed < solution.n_items_placed());
+        assert_eq!(od_problem.layout.placed_items().len(), n_placed);
+        assert!(od_problem.missing_item_qtys().iter().all(|&qty| qty >= 0));
+        od_problem.modify_container(c_width * 2.0, c_height * 2.0);
+        assert_eq!(od_problem.placed_item_qtys().sum::<usize>(), n_placed);
+        od_problem.restore_to_solution(&solution);
+        assert_eq!(
+            od_problem.placed_item_qtys().sum::<usize>(),
+            solution.n_items_placed()
+        );
+
+        //invalid open dimensions
+        for (width, aspect_ratio) in [
+            (Some(10.0), Some(1.0)),
+            (Some(-1.0), None),
+            (None, Some(0.0)),
+        ] {
+            json_instance.open_dimension = Some(JsonOpenDimension {
+                width,
+                aspect_ratio,
+            });
+            assert!(matches!(
+                parser.try_parse(&json_instance),
+                Err(ParseError::InvalidContainer(_))
+            ));
+        }
+        json_instance.open_dimension = Some(JsonOpenDimension {
+            width: None,
+            aspect_ratio: None,
+        });
+        json_instance.strip = Some(JsonStrip {
+            height: 10.0,
+            fixed_placements: vec![],
+        });
+        assert!(matches!(
+            parser.try_parse(&json_instance),
+            Err(ParseError::InvalidContainer(_))
+        ));
+    }
+
+    #[test_case("../assets/swim.json"; "swim")]
+    #[test_case("../assets/trousers.json"; "trousers")]
+    fn test_incremental_strip_resize(instance_path: &str) {
+        let json_instance = io::read_json_instance(Path::new(instance_path));
+        let config = test_config();
+        let parser = test_parser(&config);
+        let instance = parser.parse(&json_instance);
+
+        let mut optimizer = LBFOptimizer::new(instance.clone(), config, SmallRng::seed_from_u64(0));
+        let solution = optimizer.solve();
+        let Problem::SP(sp_problem) = &mut optimizer.problem else {
+            panic!("expected a strip packing problem");
+        };
+        let mut rng = SmallRng::seed_from_u64(0);
+        let full_width = sp_problem.strip_width();
+        let cde_bbox = sp_problem.layout.cde().bbox().clone();
+
+        //shrinking and extending within the headroom of the current CDE, and finally beyond it
+        for width_ratio in [0.8, 0.5, 1.0, 1.5, 4.0] {
+            let old_items = sp_problem.layout.placed_items().clone();
+            sp_problem.modify_strip_in_back(full_width * width_ratio);
+            let layout = &sp_problem.layout;
+            let strip = layout.bin().bbox();
+            assert_eq!(strip.width(), full_width * width_ratio);
+
+            //remaining items kept their keys and positions, only items sticking out of the strip were removed
+            for (pik, pi) in old_items.iter() {
+                match layout.placed_items().get(pik) {
+                    Some(new_pi) => assert_eq!(new_pi.d_transf, pi.d_transf),
+                    None => assert_ne!(strip.relation_to(&pi.bbox()), GeoRelation::Surrounding),
+                }
+            }
+            assert!(layout
+                .placed_items()
+                .keys()
+                .all(|pik| old_items.contains_key(pik)));
+            let n_placed = sp_problem.placed_item_qtys().sum::<usize>();
+            assert_eq!(n_placed, layout.placed_items().len());
+
+            //the CDE answers collision queries exactly like one built from scratch
+            let mut fresh_layout =
+                Layout::new(0, Bin::from_strip(strip.clone(), config.cde_config));
+            for pi in layout.placed_items().values() {
+                fresh_layout.place_item(instance.item(pi.item_id), pi.d_transf);
+            }
+            let (cde, fresh_cde) = (layout.cde(), fresh_layout.cde());
+            //the CDE is only rebuilt once the strip outgrows its headroom, and then gets fresh headroom
+            match cde_bbox.relation_to(&strip) {
+                GeoRelation::Surrounding => assert_eq!(cde.bbox(), &cde_bbox),
+                _ => {
+                    assert!(width_ratio > 1.5);
+                    let headroom = strip.width() * STRIP_CDE_HEADROOM;
+                    assert!(cde.bbox().x_max >= strip.x_max + headroom);
+                    assert!(cde.bbox().x_min <= strip.x_min - headroom);
+                }
+            }
+            for (item, _) in instance.items() {
+                for _ in 0..100 {
+                    let transform = Transformation::from_rotation(rng.gen_range(0.0..2.0 * PI))
+                        .translate((
+                            rng.gen_range(strip.x_min..strip.x_max * 1.1),
+                            rng.gen_range(strip.y_min..strip.y_max),
+                        ));
+                    for shape in item.shapes() {
+                        let shape = shape.transform_clone(&transform);
+                        assert_eq!(
+                            cde.poly_collides(&shape, &[]),
+                            fresh_cde.poly_collides(&shape, &[])
+                        );
+                    }
+                }
+            }
+
+            //every cell of the hazard proximity grid is up to date, and the grid covers the entire strip
+            let grid = &cde.haz_prox_grid().unwrap().grid;
+            for cell in grid.cells.iter().flatten() {
+                let mut fresh_cell = HPGCell::new(cell.bbox.clone(), cde.static_hazards());
+                fresh_cell.register_hazards(cde.dynamic_hazards().iter());
+                assert_eq!(cell.uni_prox, fresh_cell.uni_prox);
+            }
+            assert!(grid
+                .cells
+                .iter()
+                .flatten()
+                .any(|c| c.bbox.x_max >= strip.x_max));
+        }
+
+        //solutions created before the strip was resized can still be restored
+        sp_problem.restore_to_solution(&solution);
+        assert_eq!(
+            sp_problem.placed_item_qtys().sum::<usize>(),
+            solution.n_items_placed()
+        );
+    }
+
+    #[test]
+    fn test_mirroring() {
+        let mut json_instance = io::read_json_instance(Path::new("../assets/swim.json"));
+        for json_item in json_instance.items.iter_mut() {
+            json_item.allowed_mirroring = Some(true);
+        }
+        let config = test_config();
+        let parser = test_parser(&config);
+        let instance = parser.parse(&json_instance);
+        let mut rng = SmallRng::seed_from_u64(0);
+
+        for (item, _) in instance.items() {
+            assert_eq!(item.allowed_mirroring, AllowedMirroring::Optional);
+            let shape = item.shape.as_ref();
+            let d_transf =
+                DTransformation::new(rng.gen_range(-PI..PI), (10.0, -5.0)).with_mirror(true);
+            let transform = d_transf.compose();
+            assert!(transform.is_mirroring());
+
+            //the decomposition of the transformation retains the mirroring
+            let decomposed = transform.decompose();
+            assert!(decomposed.mirror());
+            assert!((decomposed.rotation() - d_transf.rotation()).abs() < 1e-3);
+
+            //the mirrored shape stays counterclockwise and covers exactly the mirrored points
+            let mirrored = shape.transform_clone(&transform);
+            assert!(SimplePolygon::calculate_area(&mirrored.points) > 0.0);
+            let bbox = shape.bbox();
+            for _ in 0..1000 {
+                let p = Point(
+                    rng.gen_range(bbox.x_min..bbox.x_max),
+                    rng.gen_range(bbox.y_min..bbox.y_max),
+                );
+                let t_p = p.transform_clone(&transform);
+                assert_eq!(shape.collides_with(&p), mirrored.collides_with(&t_p));
+            }
+
+            //the surrogate of the mirrored shape is still valid: its convex hull and poles are mirrored as well
+            let ch_points = |s: &SimplePolygon| {
+                s.surrogate()
+                    .convex_hull_indices
+                    .iter()
+                    .map(|&i| s.points[i])
+                    .collect_vec()
+            };
+            let expected_ch = ch_points(shape)
+                .iter()
+                .map(|p| p.transform_clone(&transform))
+                .collect_vec();
+            assert!(ch_points(&mirrored)
+                .iter()
+                .all(|p| expected_ch.iter().any(|e| e.distance(*p) < 1e-3)));
+            assert!(SimplePolygon::calculate_area(&ch_points(&mirrored)) > 0.0);
+            for pole in mirrored.surrogate().poles.iter() {
+                assert!(mirrored.collides_with(&pole.center));
+            }
+
+            //a buffer can switch between mirrored and non-mirrored transformations
+            let mut buffer = shape.clone();
+            buffer.transform_from(shape, &transform);
+            assert_eq!(buffer.points, mirrored.points);
+            let proper = DTransformation::new(1.0, (3.0, 4.0)).compose();
+            buffer.transform_from(shape, &proper);
+            assert_eq!(buffer.points, shape.transform_clone(&proper).points);
+            assert_eq!(
+                buffer.surrogate().convex_hull_indices,
+                shape.surrogate().convex_hull_indices
+            );
+        }
+
+        //LBF places some of the items mirrored
+        let mut optimizer = LBFOptimizer::new(instance.clone(), config, SmallRng::seed_from_u64(0));
+        let solution = optimizer.solve();
+        let placed_items = solution.layout_snapshots[0]
+            .placed_items
+            .values()
+            .collect_vec();
+        assert!(placed_items.iter().any(|pi| pi.d_transf.mirror()));
+        assert!(placed_items.iter().any(|pi| !pi.d_transf.mirror()));
+
+        //mirrored placements survive a round trip through the JSON solution
+        let json_solution = parser::compose_json_solution(&solution, &instance, Instant::now());
+        let json_layout = &json_solution.layouts[0];
+        let n_mirrored = placed_items
+            .iter()
+            .filter(|pi| pi.d_transf.mirror())
+            .count();
+        let n_json_mirrored = json_layout
+            .placed_items
+            .iter()
+            .filter(|jpi| jpi.transformation.mirror)
+            .count();
+        assert_eq!(n_mirrored, n_json_mirrored);
+        let rebuilt = parser::try_build_solution_from_json(
+            &instance,
+            &json_solution.layouts,
+            config.cde_config,
+        )
+        .unwrap();
+        let rebuilt_items = rebuilt.layout_snapshots[0]
+            .placed_items
+            .values()
+            .collect_vec();
+        //the rebuilt strip starts at the origin, so all items are shifted by the same offset
+        let offset = |pi: &PlacedItem, rebuilt_pi: &PlacedItem| {
+            let (bbox, rebuilt_bbox) = (pi.shape.bbox(), rebuilt_pi.shape.bbox());
+            (
+                rebuilt_bbox.x_min - bbox.x_min,
+                rebuilt_bbox.y_max - bbox.y_max,
+            )
+        };
+        let strip_offset = offset(placed_items[0], rebuilt_items[0]);
+        for (pi, rebuilt_pi) in placed_items.iter().zip(rebuilt_items) {
+            assert_eq!(pi.d_transf.mirror(), rebuilt_pi.d_transf.mirror());
+            let (dx, dy) = offset(pi, rebuilt_pi);
+            assert!((dx - strip_offset.0).abs() < 1e-2 && (dy - strip_offset.1).abs() < 1e-2);
+        }
+
+        //items which are not allowed to be mirrored cannot be imported mirrored
+        json_instance.items[0].allowed_mirroring = None;
+        let instance = parser.parse(&json_instance);
+        let mut json_layout = json_layout.clone();
+        json_layout.placed_items[0].index = 0;
+        json_layout.placed_items[0].transformation.mirror = true;
+        let result =
+            parser::try_build_solution_from_json(&instance, &[json_layout], config.cde_config);
+        assert!(matches!(result, Err(ParseError::InvalidLayout { .. })));
+    }
+
+    #[test]
+    fn test_rotation_ranges() {
+        //ranges and single angles can be mixed in the JSON representation
+        let allowed_orientations: Vec<JsonOrientation> =
+            serde_json::from_str("[90.0, [-3.0, 3.0], [177, 183]]").unwrap();
+        assert_eq!(
+            allowed_orientations,
+            vec![
+                JsonOrientation::Angle(90.0),
+                JsonOrientation::Range(-3.0, 3.0),
+                JsonOrientation::Range(177.0, 183.0),
+            ]
+        );
+
+        let mut json_instance = io::read_json_instance(Path::new("../assets/swim.json"));
+        for json_item in json_instance.items.iter_mut() {
+            json_item.allowed_orientations = Some(vec![
+                JsonOrientation::Range(-3.0, 3.0),
+                JsonOrientation::Range(177.0, 183.0),
+            ]);
+        }
+        let config = test_config();
+        let parser = test_parser(&config);
+        let instance = parser.parse(&json_instance);
+        let ranges = vec![
+            ((-3.0 as fsize).to_radians(), (3.0 as fsize).to_radians()),
+            ((177.0 as fsize).to_radians(), (183.0 as fsize).to_radians()),
+        ];
+        let in_ranges = |r: fsize| {
+            ranges.iter().any(|(min, max)| {
+                let r = min + (r - min).rem_euclid(2.0 * PI);
+                r <= max + 1e-4 || r >= min + 2.0 * PI - 1e-4
+            })
+        };
+
+        let mut rng = SmallRng::seed_from_u64(0);
+        let item = &instance.item(0);
+        assert_eq!(
+            item.allowed_rotation,
+            AllowedRotation::Ranges(ranges.clone())
+        );
+        let uniform = UniformRotDistr::from_item(item);
+        let samples = (0..1000).map(|_| uniform.sample(&mut rng)).collect_vec();
+        assert!(samples.iter().all(|&r| in_ranges(r)));
+        assert!(samples.iter().any(|&r| r < PI) && samples.iter().any(|&r| r > PI));
+
+        //samples around a reference are clamped to the range closest to it
+        let mut normal = NormalRotDistr::from_item(item, (182.0 as fsize).to_radians(), 0.5);
+        for _ in 0..1000 {
+            let r = normal.sample(&mut rng);
+            assert!(r >= ranges[1].0 && r <= ranges[1].1);
+        }
+        normal.set_mean((-2.0 as fsize).to_radians());
+        for _ in 0..1000 {
+            let r = normal.sample(&mut rng);
+            assert!(r >= ranges[0].0 && r <= ranges[0].1);
+        }
+
+        //LBF only places items within the allowed ranges
+        let mut optimizer = LBFOptimizer::new(instance.clone(), config, SmallRng::seed_from_u64(0));
+        let solution = optimizer.solve();
+        let placed_items = &solution.layout_snapshots[0].placed_items;
+        assert!(!placed_items.is_empty());
+        assert!(placed_items
+            .values()
+            .all(|pi| in_ranges(pi.d_transf.rotation())));
+
+        //single angles are degenerate ranges, and invalid ranges are rejected
+        json_instance.items[0].allowed_orientations = Some(vec![
+            JsonOrientation::Angle(90.0),
+            JsonOrientation::Range(-3.0, 3.0),
+        ]);
+        let instance = parser.parse(&json_instance);
+        let right = (90.0 as fsize).to_radians();
+        assert_eq!(
+            instance.item(0).allowed_rotation,
+            AllowedRotation::Ranges(vec![(right, right), ranges[0]])
+        );
+        json_instance.items[0].allowed_orientations = Some(vec![JsonOrientation::Range(3.0, -3.0)]);
+        assert!(parser.try_parse(&json_instance).is_err());
+    }
+
+    #[test]
+    fn test_fixed_placements() {
+        let mut json_instance = io::read_json_instance(Path::new("../assets/swim.json"));
+        let config = test_config();
+        let parser = test_parser(&config);
+
+        //fix the first few items of a previous solution in place
+        let instance = parser.parse(&json_instance);
+        let solution =
+            LBFOptimizer::new(instance.clone(), config, SmallRng::seed_from_u64(0)).solve();
+        let json_solution = parser::compose_json_solution(&solution, &instance, Instant::now());
+        let JsonContainer::Strip { width, height } = json_solution.layouts[0].container else {
+            panic!("expected a strip");
+        };
+        let fixed_placements = json_solution.layouts[0].placed_items[0..3]
+            .iter()
+            .map(|jpi| JsonFixedPlacement {
+                index: jpi.index,
+                transformation: jpi.transformation.clone(),
+            })
+            .collect_vec();
+        let fixed_qtys = fixed_placements.iter().map(|jfp| jfp.index).counts();
+        json_instance.strip.as_mut().unwrap().fixed_placements = fixed_placements.clone();
+        let instance = parser.parse(&json_instance);
+        let Instance::SP(sp_instance) = &instance else {
+            panic!("expected a strip packing instance");
+        };
+        assert_eq!(sp_instance.fixed_items.len(), 3);
+
+        //the fixed items count toward the demand and are never part of the placed items
+        let problem = SPProblem::new(sp_instance.clone(), width, config.cde_config);
+        for (i, qty) in problem.placed_item_qtys().enumerate() {
+            assert_eq!(qty, fixed_qtys.get(&i).copied().unwrap_or(0));
+        }
+        assert!(problem.layout.is_empty());
+        let n_fixed_hazards = problem
+            .layout
+            .cde()
+            .static_hazards()
+            .iter()
+            .filter(|h| matches!(h.entity, HazardEntity::FixedItem { .. }))
+            .count();
+        assert_eq!(n_fixed_hazards, 3);
+
+        //the optimizer places the remaining items around the fixed ones
+        let mut optimizer = LBFOptimizer::new(instance.clone(), config, SmallRng::seed_from_u64(0));
+        let solution = optimizer.solve();
+        let layout = &optimizer.problem.layouts()[0];
+        assert!(solution.is_complete(&instance));
+        assert_eq!(layout.placed_items().len() + 3, instance.total_item_qty());
+        for (id, fi) in layout.bin().fixed_items.iter().enumerate() {
+            let entity = HazardEntity::FixedItem { id };
+            assert!(!layout.cde().poly_collides(&fi.shape, &[entity]));
+        }
+
+        //fixed items are reported as locked, and are not placed again when rebuilding the solution
+        let json_solution = parser::compose_json_solution(&solution, &instance, Instant::now());
+        let json_layout = &json_solution.layouts[0];
+        let locked = json_layout
+            .placed_items
+            .iter()
+            .filter(|jpi| jpi.locked)
+            .collect_vec();
+        assert_eq!(locked.len(), 3);
+        assert!(locked
+            .iter()
+            .zip(fixed_placements.iter())
+            .all(|(jpi, jfp)| jpi.index == jfp.index));
+        let rebuilt = parser::try_build_solution_from_json(
+            &instance,
+            &json_solution.layouts,
+            config.cde_config,
+        )
+        .unwrap();
+        assert_eq!(rebuilt.placed_item_qtys, solution.placed_item_qtys);
+        //the rebuilt strip is aligned with the fixed items, so all items are at the same position
+        let rebuilt_layout = &rebuilt.layout_snapshots[0];
+        for pi in solution.layout_snapshots[0].placed_items.values() {
+            assert!(rebuilt_layout.placed_items.values().any(|rebuilt_pi| {
+                let (bbox, rebuilt_bbox) = (pi.bbox(), rebuilt_pi.bbox());
+                rebuilt_pi.item_id == pi.item_id
+                    && (bbox.x_min - rebuilt_bbox.x_min).abs() < 1e-2
+                    && (bbox.y_min - rebuilt_bbox.y_min).abs() < 1e-2
+            }));
+        }
+
+        //the same fixed placements in a bin
+        let mut bin_json_instance = json_instance.clone();
+        bin_json_instance.strip = None;
+        bin_json_instance.bins = Some(vec![JsonBin {
+            cost: 1,
+            stock: Some(1),
+            shape: JsonShape::Rectangle {
+                width: 2.0 * width,
+                height,
+            },
+            zones: vec![],
+            fixed_placements: fixed_placements.clone(),
+        }]);
+        let bin_instance = parser.parse(&bin_json_instance);
+        let bin_solution =
+            LBFOptimizer::new(bin_instance.clone(), config, SmallRng::seed_from_u64(0)).solve();
+        let bin_layout = &bin_solution.layout_snapshots[0];
+        assert_eq!(bin_layout.bin.fixed_items.len(), 3);
+        assert_eq!(
+            bin_solution.n_items_placed(),
+            bin_layout.placed_items.len() + 3
+        );
+        let json_bin_solution =
+            parser::compose_json_solution(&bin_solution, &bin_instance, Instant::now());
+        let rebuilt = parser::try_build_solution_from_json(
+            &bin_instance,
+            &json_bin_solution.layouts,
+            config.cde_config,
+        )
+        .unwrap();
+        assert_eq!(rebuilt.placed_item_qtys, bin_solution.placed_item_qtys);
+
+        //overlapping fixed placements and unknown items are rejected
+        let mut overlapping = fixed_placements.clone();
+        overlapping[1] = overlapping[0].clone();
+        json_instance.strip.as_mut().unwrap().fixed_placements = overlapping;
+        assert!(matches!(
+            parser.try_parse(&json_instance),
+            Err(ParseError::InvalidContainer(_))
+        ));
+        let mut unknown = fixed_placements;
+        unknown[0].index = instance.items().len();
+        bin_json_instance.bins.as_mut().unwrap()[0].fixed_placements = unknown;
+        assert!(matches!(
+            parser.try_parse(&bin_json_instance),
+            Err(ParseError::InvalidBin { index: 0, .. })
+        ));
+    }
+
+    #[test_case("../assets/swim.json"; "swim")]
+    #[test_case("../assets/baldacci1.json"; "baldacci1")]
+    fn test_transactions(instance_path: &str) {
+        let json_instance = io::read_json_instance(Path::new(instance_path));
+        let config = test_config();
+        let instance = test_parser(&config).parse(&json_instance);
+        let mut optimizer = LBFOptimizer::new(instance.clone(), config, SmallRng::seed_from_u64(0));
+        optimizer.solve();
+        let problem = &mut optimizer.problem;
+        let solution = problem.create_solution(None);
+        let mut rng = SmallRng::seed_from_u64(0);
+
+        let state = |problem: &Problem| {
+            let layouts = problem
+                .layouts()
+                .iter()
+                .map(|l| {
+                    let items = l
+                        .placed_items()
+                        .iter()
+                        .map(|(pik, pi)| (pik, pi.item_id, pi.d_transf))
+                        .collect_vec();
+                    (l.id(), items)
+                })
+                .sorted_by_key(|(id, _)| *id)
+                .collect_vec();
+            (
+                layouts,
+                problem.missing_item_qtys().to_vec(),
+                problem.bin_qtys().to_vec(),
+            )
+        };
+        let original_state = state(problem);
+
+        //remove some items and place them back, partly in a new layout, then undo everything
+        problem.begin();
+        let mut removed = vec![];
+        for _ in 0..N_ITEMS_TO_REMOVE {
+            let layout_idx = problem.layout_indices().choose(&mut rng).unwrap();
+            let pik = problem
+                .get_layout(layout_idx)
+                .placed_items()
+                .keys()
+                .choose(&mut rng);
+            if let Some(pik) = pik {
+                removed.push(problem.remove_item(layout_idx, pik, false));
+            }
+        }
+        assert_ne!(state(problem), original_state);
+        for (i, mut p_opt) in removed.into_iter().enumerate() {
+            if i == 0 {
+                if let Some(template_idx) = problem.template_layout_indices_with_stock().next() {
+                    p_opt.layout_idx = template_idx;
+                }
+            }
+            problem.place_item(p_opt);
+        }
+        problem.rollback();
+        assert!(state(problem) == original_state);
+        problem.flush_changes();
+        for layout in problem.layouts() {
+            assert!(assertions::layout_qt_matches_fresh_qt(layout));
+            for (pik, pi) in layout.placed_items().iter() {
+                let mut irrelevant = vec![HazardEntity::PlacedItem(pik)];
+                if let Some(qz_filter) = pi.hazard_filter.as_ref() {
+                    irrelevant.extend(hazard_filter::generate_irrelevant_hazards(
+                        qz_filter,
+                        layout.cde().all_hazards(),
+                    ));
+                }
+                assert!(!layout.cde().poly_collides(&pi.shape, &irrelevant));
+            }
+        }
+
+        //empty the first layout and keep the changes
+        problem.begin();
+        let layout_idx = LayoutIndex::Real(0);
+        let keys = problem
+            .get_layout(layout_idx)
+            .placed_items()
+            .keys()
+            .collect_vec();
+        let n_layouts = problem.layouts().len();
+        for pik in keys.iter() {
+            problem.remove_item(layout_idx, *pik, false);
+        }
+        problem.commit();
+        let n_removed_layouts = match problem {
+            Problem::BP(_) => 1,
+            _ => 0,
+        };
+        assert_eq!(problem.layouts().len(), n_layouts - n_removed_layouts);
+        assert_eq!(
+            problem.placed_item_qtys().sum::<usize>(),
+            solution.n_items_placed() - keys.len()
+        );
+
+        //solutions can still be restored after a transaction
+        problem.restore_to_solution(&solution);
+        assert!(state(problem) == original_state);
+
+        //layouts which were already empty at the start of a transaction are kept when committing
+        if let Problem::BP(_) = problem {
+            let bin_id = problem.bin_qtys().iter().position(|&qty| qty > 0).unwrap();
+            let mut compact = CompactSolution::from(&solution);
+            compact.layouts.push(CompactLayout {
+                bin_id,
+                bin_bbox: problem.template_layouts()[bin_id].bin().bbox(),
+                placed_items: vec![],
+            });
+            let with_empty_layout = problem.import_solution(&compact).unwrap();
+            problem.restore_to_solution(&with_empty_layout);
+            let n_layouts = problem.layouts().len();
+            assert!(problem.layouts()[n_layouts - 1].is_empty());
+
+            problem.begin();
+            let (layout_idx, pik) = problem
+                .layout_indices()
+                .find_map(|l_idx| {
+                    let placed_items = problem.get_layout(l_idx).placed_items();
+                    (placed_items.len() > 1).then(|| (l_idx, placed_items.keys().next().unwrap()))
+                })
+                .unwrap();
+            problem.remove_item(layout_idx, pik, false);
+            problem.commit();
+            assert_eq!(problem.layouts().len(), n_layouts);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot modify the strip during a transaction")]
+    fn test_strip_resize_during_transaction() {
+        let json_instance = io::read_json_instance(Path::new("../assets/swim.json"));
+        let config = test_config();
+        let instance = test_parser(&config).parse(&json_instance);
+        let Instance::SP(sp_instance) = instance else {
+            panic!("expected a strip packing instance");
+        };
+        let mut problem = SPProblem::new(sp_instance, 1000.0, config.cde_config);
+
+        //the resize could not be rolled back, so it is refused
+        problem.begin();
+        problem.modify_strip_in_back(2000.0);
+    }
+
+    #[test_case("../assets/swim.json"; "swim")]
+    #[test_case("../assets/baldacci1.json"; "baldacci1")]
+    fn test_compact_solution(instance_path: &str) {
+        let json_instance = io::read_json_instance(Path::new(instance_path));
+        let config = test_config();
+        let instance = test_parser(&config).parse(&json_instance);
+        let mut optimizer = LBFOptimizer::new(instance.clone(), config, SmallRng::seed_from_u64(0));
+        let solution = optimizer.solve();
+
+        //the compact solution survives a round-trip through JSON unchanged
+        let compact = CompactSolution::from(&solution);
+        assert_eq!(compact.version, COMPACT_SOLUTION_VERSION);
+        let serialized = serde_json::to_string(&compact).unwrap();
+        let deserialized: CompactSolution = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(deserialized, compact);
+
+        let placements = |s: &Solution| {
+            s.layout_snapshots
+                .iter()
+                .map(|sl| {
+                    let items = sl
+                        .placed_items
+                        .values()
+                        .map(|pi| (pi.item_id, pi.d_transf))
+                        .collect_vec();
+                    (sl.bin.id, sl.bin.bbox(), items)
+                })
+                .collect_vec()
+        };
+
+        //resume in a new problem, as another process would, without any loss of precision
+        let mut problem =
+            LBFOptimizer::new(instance.clone(), config, SmallRng::seed_from_u64(1)).problem;
+        let imported = problem.import_solution(&deserialized).unwrap();
+        assert_eq!(placements(&imported), placements(&solution));
+        assert_eq!(imported.placed_item_qtys, solution.placed_item_qtys);
+        assert_eq!(imported.target_item_qtys, solution.target_item_qtys);
+        assert_eq!(imported.bin_qtys, solution.bin_qtys);
+        assert_eq!(imported.usage, solution.usage);
+        problem.restore_to_solution(&imported);
+        assert_eq!(problem.usage(), solution.usage);
+        assert_eq!(
+            placements(&problem.create_solution(None)),
+            placements(&solution)
+        );
+
+        //imported solutions get new ids, so they can also be restored in the original problem
+        let problem = &mut optimizer.problem;
+        let imported = problem.import_solution(&deserialized).unwrap();
+        assert_ne!(imported.id, solution.id);
+        problem.restore_to_solution(&imported);
+        assert_eq!(
+            placements(&problem.create_solution(None)),
+            placements(&solution)
+        );
+
+        //other versions and unknown items are rejected
+        let mut invalid = compact.clone();
+        invalid.version += 1;
+        assert!(matches!(
+            problem.import_solution(&invalid),
+            Err(ParseError::InvalidSolution(_))
+        ));
+        let mut invalid = compact.clone();
+        invalid.layouts[0].placed_items[0].item_id = instance.items().len();
+        assert!(matches!(
+            problem.import_solution(&invalid),
+            Err(ParseError::InvalidLayout { index: 0, .. })
+        ));
+
+        //so are placements with a rotation or mirroring which the item does not allow
+        let placement = &compact.layouts[0].placed_items[0];
+        let item = instance.item(placement.item_id);
+        let (rotation, translation) = (
+            placement.d_transf.rotation(),
+            placement.d_transf.translation(),
+        );
+        let disallowed_rotation = (0..360)
+            .map(|deg| (deg as fsize).to_radians())
+            .find(|&r| !item.allowed_rotation.allows(r));
+        if let Some(r) = disallowed_rotation {
+            let mut invalid = compact.clone();
+            invalid.layouts[0].placed_items[0].d_transf = DTransformation::new(r, translation);
+            assert!(matches!(
+                problem.import_solution(&invalid),
+                Err(ParseError::InvalidLayout { index: 0, .. })
+            ));
+        }
+        assert!(item.allowed_rotation.allows(rotation + 2.0 * PI));
+        assert_eq!(item.allowed_mirroring, AllowedMirroring::None);
+        let mut invalid = compact.clone();
+        invalid.layouts[0].placed_items[0].d_transf =
+            DTransformation::new(rotation, translation).with_mirror(true);
+        assert!(matches!(
+            problem.import_solution(&invalid),
+            Err(ParseError::InvalidLayout { index: 0, .. })
+        ));
+
+        //a solution without any layouts has no usage, instead of an undefined one
+        if let Problem::BP(_) = problem {
+            let empty = CompactSolution {
+                layouts: vec![],
+                ..compact.clone()
+            };
+            assert_eq!(problem.import_solution(&empty).unwrap().usage, 0.0);
+        }
+    }
+
+    /// Instance of a custom problem variant, as a crate depending on `jagua-rs` would define it:
+    /// all items have to be placed on a single sheet, leaving the largest possible remnant.
+    #[derive(Clone)]
+    struct SheetInstance {
+        items: Vec<(Item, usize)>,
+        sheet: Bin,
+    }
+
+    impl InstanceGeneric for SheetInstance {
+        fn items(&self) -> &[(Item, usize)] {
+            &self.items
+        }
+
+        fn item_area(&self) -> fsize {
+            self.items
+                .iter()
+                .map(|(item, qty)| item.area() * *qty as fsize)
+                .sum()
+        }
+    }
+
+    impl ComposeJson for SheetInstance {}
+
+    #[derive(Clone)]
+    struct SheetProblem {
+        instance: SheetInstance,
+        layout: Layout,
+        missing_item_qtys: Vec<isize>,
+        layout_id_counter: usize,
+        solution_id_counter: usize,
+        transaction: Option<Transaction>,
+    }
+
+    impl SheetProblem {
+        fn new(instance: SheetInstance) -> Self {
+            let missing_item_qtys = instance
+                .items
+                .iter()
+                .map(|(_, qty)| *qty as isize)
+                .collect_vec();
+            let layout = Layout::new(0, instance.sheet.clone());
+            Self {
+                instance,
+                layout,
+                missing_item_qtys,
+                layout_id_counter: 0,
+                solution_id_counter: 0,
+                transaction: None,
+            }
+        }
+    }
+
+    impl ProblemGeneric for SheetProblem {
+        fn place_item(&mut self, p_opt: PlacingOption) -> (LayoutIndex, PItemKey) {
+            let item = self.instance.item(p_opt.item_id);
+            let pik = self.layout.place_item(item, p_opt.d_transf);
+            self.register_included_item(p_opt.item_id);
+            (p_opt.layout_idx, pik)
+        }
+
+        fn remove_item(
+            &mut self,
+            layout_index: LayoutIndex,
+            pik: PItemKey,
+            commit_instantly: bool,
+        ) -> PlacingOption {
+            let pi = self.layout.remove_item(pik, commit_instantly);
+            self.deregister_included_item(pi.item_id);
+            PlacingOption::from_placed_item(layout_index, &pi)
+        }
+
+        fn create_solution(&mut self, _old_solution: Option<&Solution>) -> Solution {
+            let id = self.next_solution_id();
+            let placed_item_qtys = self.placed_item_qtys().collect_vec();
+            let target_item_qtys = self
+                .instance
+                .items
+                .iter()
+                .map(|(_, qty)| *qty)
+                .collect_vec();
+            let layout_snapshots = vec![self.layout.create_snapshot()];
+            Solution::new(
+                id,
+                layout_snapshots,
+                self.usage(),
+                placed_item_qtys,
+                target_item_qtys,
+                vec![0],
+            )
+        }
+
+        fn restore_to_solution(&mut self, solution: &Solution) {
+            let layout_snapshot = &solution.layout_snapshots[0];
+            match self.layout.id() == layout_snapshot.id {
+                true => self.layout.restore(layout_snapshot),
+                false => self.layout = Layout::from_snapshot(layout_snapshot),
+            }
+            for (i, qty) in self.missing_item_qtys.iter_mut().enumerate() {
+                *qty = (self.instance.item_qty(i) - solution.placed_item_qtys[i]) as isize;
+            }
+        }
+
+        fn layouts(&self) -> &[Layout] {
+            slice::from_ref(&self.layout)
+        }
+
+        fn layouts_mut(&mut self) -> &mut [Layout] {
+            slice::from_mut(&mut self.layout)
+        }
+
+        fn template_layouts(&self) -> &[Layout] {
+            &[]
+        }
+
+        fn missing_item_qtys(&self) -> &[isize] {
+            &self.missing_item_qtys
+        }
+
+        fn bin_qtys(&self) -> &[usize] {
+            &[0]
+        }
+
+        fn instance(&self) -> &dyn InstanceGeneric {
+            &self.instance
+        }
+    }
+
+    impl ProblemGenericPrivate for SheetProblem {
+        fn next_solution_id(&mut self) -> usize {
+            self.solution_id_counter += 1;
+            self.solution_id_counter
+        }
+
+        fn next_layout_id(&mut self) -> usize {
+            self.layout_id_counter += 1;
+            self.layout_id_counter
+        }
+
+        fn missing_item_qtys_mut(&mut self) -> &mut [isize] {
+            &mut self.missing_item_qtys
+        }
+
+        fn transaction_mut(&mut self) -> &mut Option<Transaction> {
+            &mut self.transaction
+        }
+
+        fn import_bins(&self, layouts: &[CompactLayout]) -> Result<(Vec<Bin>, Vec<usize>), String> {
+            match layouts {
+                [cl] if cl.bin_id == self.instance.sheet.id => {
+                    Ok((vec![self.instance.sheet.clone()], vec![0]))
+                }
+                _ => Err("expected a single layout on the sheet".to_string()),
+            }
+        }
+    }
+
+    #[test]
+    fn test_custom_problem_variant() {
+        let json_instance = io::read_json_instance(Path::new("../assets/baldacci1.json"));
+        let config = test_config();
+        let parser = test_parser(&config);
+        let instance = parser.parse(&json_instance);
+        let mut optimizer = LBFOptimizer::new(instance.clone(), config, SmallRng::seed_from_u64(0));
+        let bp_solution = optimizer.solve();
+        let bp_layout = &bp_solution.layout_snapshots[0];
+
+        //the custom instance is built from the same JSON instance with the building blocks of the parser
+        let items = parser.try_parse_items(&json_instance.items).unwrap();
+        let bins = parser
+            .try_parse_bins(json_instance.bins.as_ref().unwrap(), &items)
+            .unwrap();
+        let sheet = bins[bp_layout.bin.id].0.clone();
+        let mut problem = SheetProblem::new(SheetInstance { items, sheet });
+
+        //replay the first layout of the bin packing solution on the sheet
+        for pi in bp_layout.placed_items.values() {
+            problem.place_item(PlacingOption {
+                layout_idx: LayoutIndex::Real(0),
+                item_id: pi.item_id,
+                d_transf: pi.d_transf,
+            });
+        }
+        let solution = problem.create_solution(None);
+        assert!(assertions::problem_matches_solution(&problem, &solution));
+        assert_eq!(solution.n_items_placed(), bp_layout.placed_items.len());
+        assert_eq!(solution.layout_snapshots[0].usage, bp_layout.usage);
+
+        //transactions are supported without any extra work
+        problem.begin();
+        let keys = problem.layout.placed_items().keys().collect_vec();
+        for pik in keys {
+            problem.remove_item(LayoutIndex::Real(0), pik, false);
+        }
+        assert_eq!(problem.placed_item_qtys().sum::<usize>(), 0);
+        problem.rollback();
+        assert!(assertions::problem_matches_solution(&problem, &solution));
+        assert_eq!(
+            problem.placed_item_qtys().collect_vec(),
+            solution.placed_item_qtys
+        );
+
+        //as are compact and JSON solutions
+        let imported = problem
+            .import_solution(&CompactSolution::from(&solution))
+            .unwrap();
+        problem.restore_to_solution(&imported);
+        assert!(assertions::problem_matches_solution(&problem, &imported));
+        let json_solution =
+            parser::compose_json_solution(&imported, &problem.instance, Instant::now());
+        assert!(matches!(
+            json_solution.layouts[..],
+            [JsonLayout {
+                container: JsonContainer::Bin { index },
+                ..
+            }] if index == bp_layout.bin.id
+        ));
+        assert_eq!(
+            json_solution.layouts[0].placed_items.len(),
+            bp_layout.placed_items.len()
+        );
+        assert_eq!(json_solution.value, None);
+    }
+
+    #[cfg(feature = "cde-stats")]
+    #[test]
+    fn test_cde_stats() {
+        let json_instance = io::read_json_instance(Path::new("../assets/swim.json"));
+        let config = test_config();
+        let parser = test_parser(&config);
+        let instance = parser.parse(&json_instance);
+
+        let mut optimizer = LBFOptimizer::new(instance.clone(), config, SmallRng::seed_from_u64(0));
+        optimizer.solve();
+        let Problem::SP(sp_problem) = &mut optimizer.problem else {
+            panic!("expected a strip packing problem");
+        };
+
+        //the statistics were harvested when the strip was fitted, which reset them
+        assert!(optimizer.replaced_cde_stats.n_surrogate_queries > 0);
+        assert_eq!(sp_problem.layout.take_cde_stats(), CDEStats::default());
+
+        //every query is counted exactly once, together with how it was resolved
+        let mut rng = SmallRng::seed_from_u64(0);
+        let strip = sp_problem.layout.bin().bbox();
+        let cde = sp_problem.layout.cde();
+        let (n_queries, mut n_poly_collisions, mut n_surrogate_collisions) = (500, 0, 0);
+        for _ in 0..n_queries {
+            let (item, _) = instance.items().iter().choose(&mut rng).unwrap();
+            let transform =
+                Transformation::from_rotation(rng.gen_range(0.0..2.0 * PI)).translate((
+                    rng.gen_range(strip.x_min..strip.x_max * 1.1),
+                    rng.gen_range(strip.y_min..strip.y_max),
+                ));
+            let shape = item.shape.transform_clone(&transform);
+            n_poly_collisions += cde.poly_collides(&shape, &[]) as u64;
+            n_surrogate_collisions +=
+                cde.surrogate_collides(item.shape.surrogate(), &transform, &[]) as u64;
+        }
+        //a single query beyond the bounding box of the CDE
+        let (item, _) = &instance.items()[0];
+        let outside = Transformation::from_translation((cde.bbox().x_max, cde.bbox().y_max));
+        assert!(cde.poly_collides(&item.shape.transform_clone(&outside), &[]));
+        n_poly_collisions += 1;
+
+        let stats = cde.stats();
+        assert_eq!(stats.n_poly_queries, n_queries + 1);
+        assert_eq!(stats.n_detected_by_bbox, 1);
+        assert_eq!(stats.n_surrogate_queries, n_queries);
+        assert_eq!(
+            stats.n_poly_queries - stats.n_poly_passed(),
+            n_poly_collisions
+        );
+        assert_eq!(
+            stats.n_surrogate_queries - stats.n_surrogate_passed(),
+            n_surrogate_collisions
+        );
+        assert!(stats.n_detected_by_entire_nodes <= n_poly_collisions + n_surrogate_collisions);
+        assert!(stats.edge_intersection_time + stats.containment_time <= stats.poly_time);
+
+        //resizing the strip in place does not carry over statistics which have already been harvested
+        sp_problem.layout.take_cde_stats();
+        sp_problem.modify_strip_in_back(sp_problem.strip_width() * 1.2);
+        assert_eq!(sp_problem.layout.take_cde_stats(), CDEStats::default());
+    }
+
+    #[test_case(JoinStyle::Miter { limit: 2.0 }; "miter")]
+    #[test_case(JoinStyle::Round { tolerance: 0.01 }; "round")]
+    #[test_case(JoinStyle::Bevel; "bevel")]
+    fn test_offset_join_styles(join: JoinStyle) {
+        //a square, inflated and deflated by 1
+        let square = SimplePolygon::from(AARectangle::new(0.0, 0.0, 10.0, 10.0));
+        let inflated = square.offset(1.0, join);
+        assert_eq!(inflated.len(), 1);
+        assert_offset_distance(&square, &inflated, 1.0);
+        let exact_area = 100.0 + 40.0 + PI;
+        let area = inflated[0].area();
+        match join {
+            //the miter vertices lie at sqrt(2) from the corners, within the limit
+            JoinStyle::Miter { .. } => assert_approx_eq(area, 144.0),
+            //every corner is cut by a tangent at the midpoint of the arc
+            JoinStyle::Bevel => {
+                assert_approx_eq(area, 144.0 - 4.0 * (fsize::sqrt(2.0) - 1.0).powi(2))
+            }
+            //the tangent segments deviate at most the tolerance from the arcs
+            JoinStyle::Round { tolerance } => {
+                assert!(area >= exact_area - 1e-3);
+                assert!(area <= exact_area + 2.0 * PI * tolerance + 1e-3);
+            }
+        }
+
+        let deflated = square.offset(-1.0, join);
+        assert_eq!(deflated.len(), 1);
+        assert_offset_distance(&square, &deflated, -1.0);
+        assert_approx_eq(deflated[0].area(), 64.0);
+
+        //a concave item, inflated and deflated by a fraction of its diameter
+        let json_instance = io::read_json_instance(Path::new("../assets/swim.json"));
+        let config = LBFConfig::default();
+        let instance = test_parser(&config).parse(&json_instance);
+        let shape = instance
+            .items()
+            .iter()
+            .map(|(item, _)| item.shape.as_ref())
+            .find(|s| s.area() < 0.9 * s.surrogate().convex_hull_area)
+            .expect("no concave item");
+        let distance = 0.02 * shape.diameter();
+
+        let inflated = shape.offset(distance, join);
+        assert_eq!(inflated.len(), 1);
+        assert_offset_distance(shape, &inflated, distance);
+        assert!(inflated[0].area() > shape.area());
+
+        let deflated = shape.offset(-distance, join);
+        assert!(!deflated.is_empty());
+        assert_offset_distance(shape, &deflated, -distance);
+        assert!(deflated.iter().map(|p| p.area()).sum::<fsize>() < shape.area());
+    }
+
+    #[test]
+    fn test_offset_splits_and_vanishes() {
+        //two 10x10 squares connected by a corridor with a width of 2
+        let dumbbell = SimplePolygon::new(
+            [
+                (0.0, 0.0),
+                (10.0, 0.0),
+                (10.0, 4.0),
+                (20.0, 4.0),
+                (20.0, 0.0),
+                (30.0, 0.0),
+                (30.0, 10.0),
+                (20.0, 10.0),
+                (20.0, 6.0),
+                (10.0, 6.0),
+                (10.0, 10.0),
+                (0.0, 10.0),
+            ]
+            .map(Point::from)
+            .to_vec(),
+        );
+        let joins = [
+            JoinStyle::Miter { limit: 2.0 },
+            JoinStyle::Round { tolerance: 0.01 },
+            JoinStyle::Bevel,
+        ];
+        for join in joins {
+            //the corridor disappears, leaving a component on either side
+            let deflated = dumbbell.offset(-1.5, join);
+            assert_eq!(deflated.len(), 2);
+            assert_offset_distance(&dumbbell, &deflated, -1.5);
+            for component in deflated.iter() {
+                //a 7x7 square, with a small cusp towards the corridor where the arcs around its corners meet
+                assert!(component.area() >= 49.0 - 1e-3 && component.area() < 50.0);
+            }
+            assert_ne!(
+                deflated[0].centroid().0 < 15.0,
+                deflated[1].centroid().0 < 15.0
+            );
+
+            //nothing remains when deflating by more than half of the width of the squares
+            assert!(dumbbell.offset(-6.0, join).is_empty());
+        }
+    }
+
+    #[test]
+    fn test_offset_degenerate_inputs() {
+        let joins = [
+            JoinStyle::Miter {
+                limit: fsize::INFINITY,
+            },
+            JoinStyle::Round { tolerance: 0.01 },
+            JoinStyle::Bevel,
+        ];
+        //a sliver with an extremely sharp apex, and thus an extreme miter
+        let sliver = SimplePolygon::new(
+            [(0.0, 0.0), (10.0, 0.0), (5.0, 1e-6)]
+                .map(Point::from)
+                .to_vec(),
+        );
+        let square = SimplePolygon::from(AARectangle::new(0.0, 0.0, 10.0, 10.0));
+        for join in joins {
+            //inflation results in at most one polygon, which should contain the original (up to rounding errors,
+            //as the approximated round join degenerates to a chord through the apex when the tolerance exceeds the distance)
+            for distance in [1e-6, 1.0] {
+                let inflated = sliver.offset(distance, join);
+                assert!(inflated.len() <= 1);
+                for polygon in inflated.iter() {
+                    assert!(sliver
+                        .points
+                        .iter()
+                        .all(|p| polygon.distance(p) <= 1e-3 * distance));
+                }
+            }
+            assert!(sliver.offset(-1.0, join).is_empty());
+
+            //a non-finite distance does not result in any polygon
+            assert!(square.offset(fsize::NAN, join).is_empty());
+            assert!(square.offset(-fsize::NAN, join).is_empty());
+        }
+    }
+
+    #[test]
+    fn test_max_translation() {
+        let config = LBFConfig::default();
+        let strip = AARectangle::new(0.0, 0.0, 100.0, 50.0);
+        let mut layout = Layout::new(0, Bin::from_strip(strip, config.cde_config));
+        let square = SimplePolygon::from(AARectangle::new(0.0, 0.0, 10.0, 10.0));
+        //a 10x10 square at [40, 50] x [20, 30]
+        let shape = square.transform_clone(&Transformation::from_translation((40.0, 20.0)));
+
+        //sliding toward the exterior of the bin, the direction does not need to be normalized
+        let exterior_cases = [
+            ((-1.0, 0.0), 40.0),
+            ((-3.0, 0.0), 40.0),
+            ((1.0, 0.0), 50.0),
+            ((0.0, -1.0), 20.0),
+            ((0.0, 1.0), 20.0),
+            ((1.0, 1.0), 20.0 * fsize::sqrt(2.0)),
+        ];
+        for (direction, gap) in exterior_cases {
+            let (distance, entity) = layout.cde().max_translation(&shape, direction, &[]);
+            assert_approx_eq(distance, gap);
+            assert_eq!(entity, Some(HazardEntity::BinExterior));
+        }
+
+        //sliding toward an item placed at [70, 80] x [15, 25]
+        let item = Item::new(
+            0,
+            square.clone(),
+            1,
+            AllowedRotation::None,
+            Transformation::empty(),
+            None,
+            config.cde_config.item_surrogate_config,
+        );
+        let pik = layout.place_item(&item, DTransformation::new(0.0, (70.0, 15.0)));
+        let placed_item = Some(HazardEntity::PlacedItem(pik));
+        let cde = layout.cde();
+
+        let (distance, entity) = cde.max_translation(&shape, (1.0, 0.0), &[]);
+        assert_approx_eq(distance, 20.0);
+        assert_eq!(entity, placed_item);
+        //diagonally, the left face of the item is still reached before the bottom of the bin
+        let (distance, entity) = cde.max_translation(&shape, (1.0, -0.25), &[]);
+        assert_approx_eq(distance, 20.0 * fsize::sqrt(1.0 + 0.25 * 0.25));
+        assert_eq!(entity, placed_item);
+        //passing above the item
+        let (distance, entity) = cde.max_translation(&shape, (1.0, 0.5), &[]);
+        assert_approx_eq(distance, 40.0 * fsize::sqrt(1.0 + 0.5 * 0.5));
+        assert_eq!(entity, Some(HazardEntity::BinExterior));
+        //irrelevant hazards do not block the translation
+        let (distance, entity) =
+            cde.max_translation(&shape, (1.0, 0.0), &[HazardEntity::PlacedItem(pik)]);
+        assert_approx_eq(distance, 50.0);
+        assert_eq!(entity, Some(HazardEntity::BinExterior));
+    }
+
+    #[test]
+    fn test_slide_to_contact() {
+        let json_instance = io::read_json_instance(Path::new("../assets/swim.json"));
+        let solve = |slide_to_contact: bool| {
+            let config = LBFConfig {
+                slide_to_contact,
+                ..test_config()
+            };
+            let parser = test_parser(&config);
+            let instance = parser.parse(&json_instance);
+            let mut optimizer = LBFOptimizer::new(instance, config, SmallRng::seed_from_u64(0));
+            optimizer.solve();
+            optimizer.problem
+        };
+
+        //sliding is disabled by default
+        assert!(!LBFConfig::default().slide_to_contact);
+        let (slid, not_slid) = (solve(true), solve(false));
+
+        //slid items never collide with anything
+        let layout = &slid.layouts()[0];
+        for (pik, pi) in layout.placed_items().iter() {
+            let irrelevant = [HazardEntity::PlacedItem(pik)];
+            assert!(pi
+                .shapes()
+                .all(|s| !layout.cde().poly_collides(s, &irrelevant)));
+        }
+        //the placements differ from those found without sliding
+        let transformations = |p: &Problem| {
+            p.layouts()[0]
+                .placed_items()
+                .values()
+                .map(|pi| pi.d_transf)
+                .collect_vec()
+        };
+        assert_ne!(transformations(&slid), transformations(&not_slid));
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds")]
+    fn test_border_edges_of_polygon_with_hole() {
+        let hole = SimplePolygon::from(AARectangle::new(2.0, 2.0, 4.0, 4.0));
+        let outer = SimplePolygon::from(AARectangle::new(0.0, 0.0, 10.0, 10.0));
+        let shape = SimplePolygon::with_holes(outer.points.clone(), vec![hole]);
+
+        //the points and edges only describe the outer boundary, the border edges include the hole
+        assert_eq!(shape.number_of_points(), 4);
+        assert_eq!(shape.edge_iter().count(), 4);
+        assert_eq!(shape.number_of_border_edges(), 8);
+        for (i, edge) in shape.border_edge_iter().enumerate() {
+            assert_eq!(shape.get_border_edge(i), edge);
+        }
+        shape.get_border_edge(shape.number_of_border_edges());
+    }
+
+    fn assert_approx_eq(a: fsize, b: fsize) {
+        assert!((a - b).abs() < 1e-3, "{a} != {b}");
+    }
+
+    /// Asserts that every point on the border of the offset polygons lies at least `distance` from the original border,
+    /// outside it when inflating and inside it when deflating, and that inflated polygons contain the original.
+    fn assert_offset_distance(original: &SimplePolygon, offset: &[SimplePolygon], distance: fsize) {
+        let (expected_pos, min_distance) = match distance > 0.0 {
+            true => (GeoPosition::Exterior, distance * (1.0 - 1e-3)),
+            false => (GeoPosition::Interior, -distance * (1.0 - 1e-3)),
+        };
+        for edge in offset.iter().flat_map(|p| p.border_edge_iter()) {
+            for i in 0..10 {
+                let t = i as fsize / 10.0;
+                let point = Point(
+                    edge.start.0 + t * (edge.end.0 - edge.start.0),
+                    edge.start.1 + t * (edge.end.1 - edge.start.1),
+                );
+                let (pos, dist) = original.distance_from_border(&point);
+                assert_eq!(pos, expected_pos);
+                assert!(dist >= min_distance, "{dist} < {min_distance}");
+            }
+        }
+        if distance > 0.0 {
+            for point in original.points.iter() {
+                let (pos, dist) = offset[0].distance_from_border(point);
+                assert_eq!(pos, GeoPosition::Interior);
+                assert!(dist >= min_distance, "{dist} < {min_distance}");
+            }
+        }
+    }
 }
@@ -2,6 +2,7 @@ use serde::{Deserialize, Serialize};
 
 use jagua_rs::fsize;
 use jagua_rs::util::config::{CDEConfig, SPSurrogateConfig};
+use jagua_rs::util::polygon_validation::PolyValidationPolicy;
 
 use crate::io::svg_util::SvgDrawOptions;
 
@@ -12,6 +13,9 @@ pub struct SDRConfig {
     pub cde_config: CDEConfig,
     /// Max deviation from the original polygon area as a fraction. If undefined, the algorithm will run without simplification
     pub poly_simpl_tolerance: Option<fsize>,
+    /// How to handle polygons with defects, such as self-intersections, collinear points or spikes
+    #[serde(default)]
+    pub poly_validation: PolyValidationPolicy,
     /// Seed for the PRNG. If undefined, the algorithm will run in non-deterministic mode using entropy
     pub prng_seed: Option<u64>,
     /// Total budget of samples per item per layout
@@ -35,8 +39,10 @@ impl Default for SDRConfig {
                     n_ff_poles: 2,
                     n_ff_piers: 0,
                 },
+                robust_predicates: false,
             },
             poly_simpl_tolerance: Some(0.001),
+            poly_validation: PolyValidationPolicy::Disabled,
             prng_seed: Some(0),
             n_samples: 5000,
             ls_frac: 0.2,
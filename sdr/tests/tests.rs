@@ -175,7 +175,12 @@ mod tests {
             None => PolySimplConfig::Disabled,
         };
 
-        let parser = Parser::new(poly_simpl_config, config.cde_config, true);
+        let parser = Parser::new(
+            poly_simpl_config,
+            config.poly_validation,
+            config.cde_config,
+            true,
+        );
         let instance = parser.parse(&json_instance);
         
         let mut totalDuration = Duration::new(0, 0);